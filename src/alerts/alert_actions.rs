@@ -0,0 +1,88 @@
+//! Alert actions.
+//!
+//! Writes alert events to the alert log file and runs the configured alert command.
+
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::{Child, Command};
+use std::thread::{self, JoinHandle};
+
+use crate::{alerts::alert_event_struct::AlertEvent, data::format::format_timestamp};
+
+/// Formats an alert event as one line of the alert log.
+///
+/// * Parameters
+/// `alert_event` The event to format
+///
+/// * Returns
+/// The line, e.g. "2024-01-31 13:05:09 FIRED cpu > 90 for 30s (value 93.2)"
+pub fn format_alert_event(alert_event: &AlertEvent) -> String {
+    format!(
+        "{} {} {} (value {:.1})",
+        format_timestamp(alert_event.timestamp_milliseconds / 1000),
+        alert_event.state_label(),
+        alert_event.rule_description,
+        alert_event.value
+    )
+}
+
+/// Appends an alert event to the alert log file, creating it if needed.
+///
+/// * Parameters
+/// `path` The alert log file
+/// `alert_event` The event to append
+///
+/// * Returns
+/// The error met writing, if any
+pub fn append_alert_log(path: &Path, alert_event: &AlertEvent) -> io::Result<()> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", format_alert_event(alert_event))
+}
+
+/// Runs the alert command through the shell without waiting for it.
+///
+/// The command can read the event from the TM_ALERT_STATE, TM_ALERT_RULE and
+/// TM_ALERT_VALUE environment variables.
+///
+/// * Parameters
+/// `command` The shell command
+/// `alert_event` The event that triggered the command
+///
+/// * Returns
+/// The error met starting the command, if any
+pub fn run_alert_command(command: &str, alert_event: &AlertEvent) -> io::Result<()> {
+    let mut shell: Command = shell_command(command);
+    let mut child: Child = shell
+        .env("TM_ALERT_STATE", alert_event.state_label())
+        .env("TM_ALERT_RULE", &alert_event.rule_description)
+        .env("TM_ALERT_VALUE", format!("{:.1}", alert_event.value))
+        .spawn()?;
+
+    // waits on a separate thread so the finished command doesn't linger as a zombie
+    let _handle: JoinHandle<()> = thread::spawn(move || {
+        let _ = child.wait();
+    });
+    Ok(())
+}
+
+/// Builds the command running a line through the platform's shell.
+///
+/// * Parameters
+/// `command` The line to run
+///
+/// * Returns
+/// The command, ready to spawn
+#[cfg(not(windows))]
+fn shell_command(command: &str) -> Command {
+    let mut shell: Command = Command::new("sh");
+    shell.arg("-c").arg(command);
+    shell
+}
+
+#[cfg(windows)]
+fn shell_command(command: &str) -> Command {
+    let mut shell: Command = Command::new("cmd");
+    shell.arg("/C").arg(command);
+    shell
+}
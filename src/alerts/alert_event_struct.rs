@@ -0,0 +1,24 @@
+//! Alert event structure.
+//!
+//! Represents an alert firing or clearing.
+
+/// Structure holding a change in the state of an alert rule.
+///
+/// Note: the timestamp is in milliseconds since the unix epoch.
+#[derive(Clone)]
+pub struct AlertEvent {
+    pub timestamp_milliseconds: u64,
+    pub rule_description: String,
+    pub value: f32,
+    pub fired: bool,
+}
+
+impl AlertEvent {
+    /// Returns the word describing the change.
+    ///
+    /// * Returns
+    /// "FIRED" or "CLEARED"
+    pub fn state_label(&self) -> &'static str {
+        if self.fired { "FIRED" } else { "CLEARED" }
+    }
+}
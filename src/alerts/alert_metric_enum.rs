@@ -0,0 +1,36 @@
+//! Alert metric enum.
+//!
+//! Represents the series an alert rule watches.
+
+/// Enum holding the metric of an alert rule.
+///
+/// CPU metrics use the exponential moving average, so a single spike doesn't
+/// count towards an alert.
+#[derive(Clone, Copy, PartialEq)]
+pub enum AlertMetric {
+    OverallCpu,
+    CoreCpu(usize),
+    AnyCoreCpu,
+    ProcessCount,
+}
+
+impl AlertMetric {
+    /// Parses the metric name used in alert rules.
+    ///
+    /// * Parameters
+    /// `name` One of "cpu", "coreN", "any_core" or "processes"
+    ///
+    /// * Returns
+    /// The metric, or None if the name isn't known
+    pub fn parse(name: &str) -> Option<AlertMetric> {
+        match name {
+            "cpu" => Some(AlertMetric::OverallCpu),
+            "any_core" => Some(AlertMetric::AnyCoreCpu),
+            "processes" => Some(AlertMetric::ProcessCount),
+            _ => name
+                .strip_prefix("core")
+                .and_then(|index| index.parse().ok())
+                .map(AlertMetric::CoreCpu),
+        }
+    }
+}
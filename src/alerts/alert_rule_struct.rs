@@ -0,0 +1,246 @@
+//! Alert rule structure.
+//!
+//! Represents a user-defined alert rule and parses it from text.
+
+use crate::alerts::alert_metric_enum::AlertMetric;
+
+/// Share of the threshold a value has to move back past it before a firing
+/// alert clears, when the rule doesn't give a clear threshold.
+const DEFAULT_HYSTERESIS_RATIO: f32 = 0.05;
+
+/// Structure holding an alert rule.
+///
+/// The rule fires once its metric has been past the threshold for the hold time,
+/// and clears once the metric is back past the clear threshold. Keeping the clear
+/// threshold apart from the threshold stops alerts flapping.
+#[derive(Clone)]
+pub struct AlertRule {
+    pub description: String,
+    pub metric: AlertMetric,
+    pub above: bool,
+    pub threshold: f32,
+    pub clear_threshold: f32,
+    pub hold_milliseconds: u64,
+}
+
+impl AlertRule {
+    /// Parses a rule such as "cpu > 90 for 30s" or "core3 > 99 for 2m clear 90".
+    ///
+    /// The form is `<metric> <'>' or '<'> <threshold> [for <duration>] [clear <value>]`,
+    /// where the metric is cpu, coreN, any_core or processes, and the duration is
+    /// a number of seconds with an optional s, m or h unit.
+    ///
+    /// * Parameters
+    /// `text` The rule
+    ///
+    /// * Returns
+    /// The rule, or a message describing what is wrong with it
+    pub fn parse(text: &str) -> Result<AlertRule, String> {
+        let words: Vec<&str> = text.split_whitespace().collect();
+        let [metric, comparison, threshold, rest @ ..] = words.as_slice() else {
+            return Err(String::from(
+                "expected '<metric> <comparison> <threshold>', such as 'cpu > 90'",
+            ));
+        };
+
+        let metric: AlertMetric = AlertMetric::parse(metric).ok_or_else(|| {
+            format!(
+                "unknown metric '{}', expected cpu, coreN, any_core or processes",
+                metric
+            )
+        })?;
+        let above: bool = match *comparison {
+            ">" => true,
+            "<" => false,
+            _ => {
+                return Err(format!(
+                    "unknown comparison '{}', expected > or <",
+                    comparison
+                ));
+            }
+        };
+        let threshold: f32 = parse_value(threshold)?;
+
+        let mut hold_milliseconds: u64 = 0;
+        let mut clear_threshold: Option<f32> = None;
+        let mut rest = rest.iter();
+        while let Some(keyword) = rest.next() {
+            let value: &str = rest
+                .next()
+                .ok_or_else(|| format!("'{}' expects a value", keyword))?;
+            match *keyword {
+                "for" => hold_milliseconds = parse_duration_milliseconds(value)?,
+                "clear" => clear_threshold = Some(parse_value(value)?),
+                _ => {
+                    return Err(format!(
+                        "unknown keyword '{}', expected for or clear",
+                        keyword
+                    ));
+                }
+            }
+        }
+
+        let margin: f32 = threshold.abs() * DEFAULT_HYSTERESIS_RATIO;
+        let clear_threshold: f32 = match clear_threshold {
+            Some(clear_threshold) if above && clear_threshold > threshold => {
+                return Err(String::from(
+                    "the clear value must not be above the threshold",
+                ));
+            }
+            Some(clear_threshold) if !above && clear_threshold < threshold => {
+                return Err(String::from(
+                    "the clear value must not be below the threshold",
+                ));
+            }
+            Some(clear_threshold) => clear_threshold,
+            None if above => threshold - margin,
+            None => threshold + margin,
+        };
+
+        Ok(AlertRule {
+            description: words.join(" "),
+            metric,
+            above,
+            threshold,
+            clear_threshold,
+            hold_milliseconds,
+        })
+    }
+
+    /// Checks whether a value is past the threshold.
+    ///
+    /// * Parameters
+    /// `value` The latest value of the metric
+    ///
+    /// * Returns
+    /// true if the value counts towards firing the alert
+    pub fn is_breached(&self, value: f32) -> bool {
+        if self.above {
+            value > self.threshold
+        } else {
+            value < self.threshold
+        }
+    }
+
+    /// Checks whether a value is back past the clear threshold.
+    ///
+    /// * Parameters
+    /// `value` The latest value of the metric
+    ///
+    /// * Returns
+    /// true if a firing alert should clear
+    pub fn is_cleared(&self, value: f32) -> bool {
+        if self.above {
+            value <= self.clear_threshold
+        } else {
+            value >= self.clear_threshold
+        }
+    }
+}
+
+/// Parses a threshold, allowing a trailing percent sign.
+///
+/// * Parameters
+/// `text` The threshold, e.g. "90" or "90%"
+///
+/// * Returns
+/// The value, or a message saying it isn't a number
+fn parse_value(text: &str) -> Result<f32, String> {
+    text.trim_end_matches('%')
+        .parse()
+        .ok()
+        .filter(|value: &f32| value.is_finite())
+        .ok_or_else(|| format!("'{}' isn't a number", text))
+}
+
+/// Parses a duration such as "30", "30s", "2m" or "1h".
+///
+/// * Parameters
+/// `text` The duration, in seconds when it has no unit
+///
+/// * Returns
+/// The duration in milliseconds, or a message saying it isn't a duration
+fn parse_duration_milliseconds(text: &str) -> Result<u64, String> {
+    let (number, unit_milliseconds): (&str, f64) = if let Some(number) = text.strip_suffix('h') {
+        (number, 3_600_000.0)
+    } else if let Some(number) = text.strip_suffix('m') {
+        (number, 60_000.0)
+    } else {
+        (text.strip_suffix('s').unwrap_or(text), 1_000.0)
+    };
+
+    number
+        .parse()
+        .ok()
+        .filter(|number: &f64| number.is_finite() && *number >= 0.0)
+        .map(|number| (number * unit_milliseconds) as u64)
+        .ok_or_else(|| format!("'{}' isn't a duration, such as 30s or 2m", text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_hold_time_and_clear_value() {
+        let rule: AlertRule = AlertRule::parse("core3  >  99% for 2m clear 90").unwrap();
+
+        assert!(rule.metric == AlertMetric::CoreCpu(3));
+        assert!(rule.above);
+        assert_eq!(rule.threshold, 99.0);
+        assert_eq!(rule.clear_threshold, 90.0);
+        assert_eq!(rule.hold_milliseconds, 120_000);
+        assert_eq!(rule.description, "core3 > 99% for 2m clear 90");
+    }
+
+    #[test]
+    fn defaults_to_no_hold_time_and_a_clear_margin() {
+        let above: AlertRule = AlertRule::parse("cpu > 80").unwrap();
+        let below: AlertRule = AlertRule::parse("processes < 100").unwrap();
+
+        assert_eq!(above.hold_milliseconds, 0);
+        assert_eq!(above.clear_threshold, 76.0);
+        assert_eq!(below.clear_threshold, 105.0);
+    }
+
+    #[test]
+    fn rejects_a_clear_value_on_the_wrong_side() {
+        assert_eq!(
+            AlertRule::parse("cpu > 90 clear 95").err(),
+            Some(String::from(
+                "the clear value must not be above the threshold"
+            ))
+        );
+        assert_eq!(
+            AlertRule::parse("processes < 10 clear 5").err(),
+            Some(String::from(
+                "the clear value must not be below the threshold"
+            ))
+        );
+        assert_eq!(
+            AlertRule::parse("cpu > 90 clear high").err(),
+            Some(String::from("'high' isn't a number"))
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_rules() {
+        assert!(AlertRule::parse("cpu >").is_err());
+        assert!(AlertRule::parse("gpu > 90").is_err());
+        assert!(AlertRule::parse("cpu = 90").is_err());
+        assert!(AlertRule::parse("cpu > 90 for").is_err());
+        assert!(AlertRule::parse("cpu > 90 during 30s").is_err());
+    }
+
+    #[test]
+    fn parses_durations_with_units() {
+        assert_eq!(parse_duration_milliseconds("30"), Ok(30_000));
+        assert_eq!(parse_duration_milliseconds("30s"), Ok(30_000));
+        assert_eq!(parse_duration_milliseconds("1.5s"), Ok(1_500));
+        assert_eq!(parse_duration_milliseconds("2m"), Ok(120_000));
+        assert_eq!(parse_duration_milliseconds("1h"), Ok(3_600_000));
+        for text in ["", "s", "-5s", "5d", "inf"] {
+            assert!(parse_duration_milliseconds(text).is_err(), "{:?}", text);
+        }
+    }
+}
@@ -0,0 +1,9 @@
+//! Alert rules.
+//!
+//! Defines the rules that raise alerts when a metric stays past a threshold, and
+//! the events they produce.
+
+pub mod alert_actions;
+pub mod alert_event_struct;
+pub mod alert_metric_enum;
+pub mod alert_rule_struct;
@@ -0,0 +1,269 @@
+//! Alert monitor module.
+//!
+//! This module defines the AlertMonitor struct for evaluating alert rules.
+
+use std::collections::VecDeque;
+use std::path::PathBuf;
+
+use crate::{
+    alerts::{
+        alert_actions::{append_alert_log, run_alert_command},
+        alert_event_struct::AlertEvent,
+        alert_metric_enum::AlertMetric,
+        alert_rule_struct::AlertRule,
+    },
+    app::cpu_monitor::CpuMonitor,
+};
+
+/// Number of alert events kept for the alert log panel.
+const ALERT_LOG_LENGTH: usize = 100;
+
+/// Structure holding an alert rule and how far it is from firing or clearing.
+///
+/// Note: the breach start is in milliseconds since the unix epoch, and None while
+/// the metric is within the threshold.
+pub struct AlertRuleState {
+    pub rule: AlertRule,
+    pub breached_since_milliseconds: Option<u64>,
+    pub firing: bool,
+    pub latest_value: Option<f32>,
+}
+
+/// A sub-structure of the AppMonitor structure.
+/// Stores the alert rules and their state, the latest alert events, and what to
+/// do when an alert fires or clears.
+pub struct AlertMonitor {
+    pub rule_states: Vec<AlertRuleState>,
+    pub alert_log: VecDeque<AlertEvent>,
+    pub alert_command: Option<String>,
+    pub alert_log_path: Option<PathBuf>,
+}
+
+impl AlertMonitor {
+    /// Constructor for the AlertMonitor structure.
+    ///
+    /// * Returns
+    /// AlertMonitor structure without any rules
+    pub fn new() -> AlertMonitor {
+        AlertMonitor::build(Vec::new(), None, None)
+    }
+
+    /// Constructs an AlertMonitor structure from the configured rules and actions.
+    ///
+    /// * Parameters
+    /// `alert_rules` The rules to evaluate
+    /// `alert_command` The shell command to run when an alert fires or clears
+    /// `alert_log_path` The file to append alert events to
+    ///
+    /// * Returns
+    /// AlertMonitor structure with no alert firing
+    pub fn build(
+        alert_rules: Vec<AlertRule>,
+        alert_command: Option<String>,
+        alert_log_path: Option<PathBuf>,
+    ) -> AlertMonitor {
+        AlertMonitor {
+            rule_states: alert_rules
+                .into_iter()
+                .map(|rule| AlertRuleState {
+                    rule,
+                    breached_since_milliseconds: None,
+                    firing: false,
+                    latest_value: None,
+                })
+                .collect(),
+            alert_log: VecDeque::with_capacity(ALERT_LOG_LENGTH),
+            alert_command,
+            alert_log_path,
+        }
+    }
+
+    /// Evaluates every rule against the latest values, firing and clearing alerts.
+    ///
+    /// * Parameters
+    /// `cpu_monitor` Reference to the cpu monitor holding the EMA series
+    /// `process_count` The number of running processes
+    /// `timestamp_milliseconds` The time of the latest CpuSnapshot
+    pub fn alert_monitor_evaluate(
+        &mut self,
+        cpu_monitor: &CpuMonitor,
+        process_count: usize,
+        timestamp_milliseconds: u64,
+    ) {
+        let mut alert_events: Vec<AlertEvent> = Vec::new();
+
+        for rule_state in &mut self.rule_states {
+            let Some(value) = metric_value(&rule_state.rule, cpu_monitor, process_count) else {
+                continue;
+            };
+            rule_state.latest_value = Some(value);
+
+            if rule_state.firing {
+                if rule_state.rule.is_cleared(value) {
+                    rule_state.firing = false;
+                    rule_state.breached_since_milliseconds = None;
+                    alert_events.push(AlertEvent {
+                        timestamp_milliseconds,
+                        rule_description: rule_state.rule.description.clone(),
+                        value,
+                        fired: false,
+                    });
+                }
+                continue;
+            }
+
+            if !rule_state.rule.is_breached(value) {
+                rule_state.breached_since_milliseconds = None;
+                continue;
+            }
+            let breached_since: u64 = *rule_state
+                .breached_since_milliseconds
+                .get_or_insert(timestamp_milliseconds);
+            if timestamp_milliseconds.saturating_sub(breached_since)
+                >= rule_state.rule.hold_milliseconds
+            {
+                rule_state.firing = true;
+                alert_events.push(AlertEvent {
+                    timestamp_milliseconds,
+                    rule_description: rule_state.rule.description.clone(),
+                    value,
+                    fired: true,
+                });
+            }
+        }
+
+        for alert_event in alert_events {
+            self.handle_alert_event(alert_event);
+        }
+    }
+
+    /// Returns the rules whose alert is currently firing.
+    ///
+    /// * Returns
+    /// An iterator over the firing rule states
+    pub fn firing_rules(&self) -> impl Iterator<Item = &AlertRuleState> {
+        self.rule_states
+            .iter()
+            .filter(|rule_state| rule_state.firing)
+    }
+
+    /// Logs an alert event and runs the alert command.
+    ///
+    /// Failures are reported on stderr, they never stop the monitoring.
+    ///
+    /// * Parameters
+    /// `alert_event` The event to handle
+    fn handle_alert_event(&mut self, alert_event: AlertEvent) {
+        if let Some(alert_log_path) = &self.alert_log_path
+            && let Err(e) = append_alert_log(alert_log_path, &alert_event)
+        {
+            eprintln!(
+                "Error writing alert log {}: {}",
+                alert_log_path.display(),
+                e
+            );
+        }
+        if let Some(alert_command) = &self.alert_command
+            && let Err(e) = run_alert_command(alert_command, &alert_event)
+        {
+            eprintln!("Error running alert command: {}", e);
+        }
+
+        self.alert_log.push_back(alert_event);
+        if self.alert_log.len() > ALERT_LOG_LENGTH {
+            self.alert_log.pop_front();
+        }
+    }
+}
+
+/// Reads the latest value of the metric a rule watches.
+///
+/// For any_core, the busiest core is used by rules firing above their threshold
+/// and the idlest core by rules firing below it.
+///
+/// * Parameters
+/// `rule` The rule
+/// `cpu_monitor` Reference to the cpu monitor holding the EMA series
+/// `process_count` The number of running processes
+///
+/// * Returns
+/// The value, or None if there is no data for the metric yet
+fn metric_value(rule: &AlertRule, cpu_monitor: &CpuMonitor, process_count: usize) -> Option<f32> {
+    match rule.metric {
+        AlertMetric::OverallCpu => cpu_monitor.previous_ema,
+        AlertMetric::CoreCpu(index) => cpu_monitor.per_core_previous_ema.get(index).copied()?,
+        AlertMetric::AnyCoreCpu => {
+            let core_emas = cpu_monitor.per_core_previous_ema.iter().flatten().copied();
+            if rule.above {
+                core_emas.reduce(f32::max)
+            } else {
+                core_emas.reduce(f32::min)
+            }
+        }
+        AlertMetric::ProcessCount => Some(process_count as f32),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn monitor(rule: &str) -> AlertMonitor {
+        AlertMonitor::build(vec![AlertRule::parse(rule).unwrap()], None, None)
+    }
+
+    fn events(alert_monitor: &AlertMonitor) -> Vec<(u64, bool)> {
+        alert_monitor
+            .alert_log
+            .iter()
+            .map(|alert_event| (alert_event.timestamp_milliseconds, alert_event.fired))
+            .collect()
+    }
+
+    #[test]
+    fn fires_only_once_breached_for_the_hold_time() {
+        let mut alert_monitor: AlertMonitor = monitor("processes > 100 for 3s");
+        let cpu_monitor: CpuMonitor = CpuMonitor::new();
+
+        alert_monitor.alert_monitor_evaluate(&cpu_monitor, 150, 10_000);
+        alert_monitor.alert_monitor_evaluate(&cpu_monitor, 150, 12_000);
+        // Dropping back under the threshold restarts the hold time.
+        alert_monitor.alert_monitor_evaluate(&cpu_monitor, 90, 13_000);
+        alert_monitor.alert_monitor_evaluate(&cpu_monitor, 150, 14_000);
+        alert_monitor.alert_monitor_evaluate(&cpu_monitor, 150, 16_000);
+        assert!(events(&alert_monitor).is_empty());
+
+        alert_monitor.alert_monitor_evaluate(&cpu_monitor, 150, 17_000);
+        alert_monitor.alert_monitor_evaluate(&cpu_monitor, 150, 18_000);
+        assert_eq!(events(&alert_monitor), vec![(17_000, true)]);
+        assert_eq!(alert_monitor.firing_rules().count(), 1);
+    }
+
+    #[test]
+    fn clears_only_once_back_past_the_clear_value() {
+        let mut alert_monitor: AlertMonitor = monitor("cpu > 90 for 1s clear 80");
+        let mut cpu_monitor: CpuMonitor = CpuMonitor::new();
+
+        for (value, timestamp_milliseconds) in [(95.0, 1_000), (95.0, 2_000), (85.0, 3_000)] {
+            cpu_monitor.previous_ema = Some(value);
+            alert_monitor.alert_monitor_evaluate(&cpu_monitor, 0, timestamp_milliseconds);
+        }
+        assert_eq!(events(&alert_monitor), vec![(2_000, true)]);
+
+        cpu_monitor.previous_ema = Some(80.0);
+        alert_monitor.alert_monitor_evaluate(&cpu_monitor, 0, 4_000);
+        assert_eq!(events(&alert_monitor), vec![(2_000, true), (4_000, false)]);
+        assert_eq!(alert_monitor.firing_rules().count(), 0);
+        assert_eq!(alert_monitor.rule_states[0].latest_value, Some(80.0));
+    }
+
+    #[test]
+    fn waits_for_data_before_evaluating_cpu_rules() {
+        let mut alert_monitor: AlertMonitor = monitor("core7 > 50");
+
+        alert_monitor.alert_monitor_evaluate(&CpuMonitor::new(), 0, 1_000);
+
+        assert!(events(&alert_monitor).is_empty());
+        assert_eq!(alert_monitor.rule_states[0].latest_value, None);
+    }
+}
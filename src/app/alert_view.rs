@@ -0,0 +1,76 @@
+//! Alert view module.
+//!
+//! This module renders the banner of firing alerts and the alert log.
+
+use crate::{
+    alerts::alert_actions::format_alert_event,
+    app::alert_monitor::AlertMonitor,
+    config::style::{ALERT_BANNER_COLOR, ALERT_CLEARED_COLOR, CRITICAL_SENSOR_COLOR},
+};
+
+use eframe::egui::{Color32, Frame, RichText, Ui};
+
+/// Renders a red banner listing every firing alert with its latest value.
+///
+/// * Parameters
+/// `ui` The UI to draw in
+/// `alert_monitor` Reference to the alert monitor for data access
+pub fn render_alert_banner(ui: &mut Ui, alert_monitor: &AlertMonitor) {
+    Frame::new()
+        .fill(ALERT_BANNER_COLOR)
+        .inner_margin(6.0)
+        .show(ui, |ui| {
+            ui.set_width(ui.available_width());
+            for rule_state in alert_monitor.firing_rules() {
+                ui.label(
+                    RichText::new(format!(
+                        "ALERT: {} (value {:.1})",
+                        rule_state.rule.description,
+                        rule_state.latest_value.unwrap_or(0.0)
+                    ))
+                    .color(Color32::WHITE)
+                    .strong(),
+                );
+            }
+        });
+}
+
+/// Renders the alert rules with their state, followed by the latest alert
+/// events, newest first.
+///
+/// * Parameters
+/// `ui` The UI to draw in
+/// `alert_monitor` Reference to the alert monitor for data access
+pub fn render_alert_panel(ui: &mut Ui, alert_monitor: &AlertMonitor) {
+    if alert_monitor.rule_states.is_empty() {
+        ui.label("No alert rules, add one with --alert \"cpu > 90 for 30s\"");
+        return;
+    }
+
+    for rule_state in &alert_monitor.rule_states {
+        let state: RichText = if rule_state.firing {
+            RichText::new("firing").color(CRITICAL_SENSOR_COLOR)
+        } else if rule_state.breached_since_milliseconds.is_some() {
+            RichText::new("pending")
+        } else {
+            RichText::new("ok").color(ALERT_CLEARED_COLOR)
+        };
+        ui.horizontal(|ui| {
+            ui.label(state);
+            ui.label(&rule_state.rule.description);
+        });
+    }
+
+    ui.separator();
+    if alert_monitor.alert_log.is_empty() {
+        ui.label("No alerts yet");
+    }
+    for alert_event in alert_monitor.alert_log.iter().rev() {
+        let color: Color32 = if alert_event.fired {
+            CRITICAL_SENSOR_COLOR
+        } else {
+            ALERT_CLEARED_COLOR
+        };
+        ui.label(RichText::new(format_alert_event(alert_event)).color(color));
+    }
+}
@@ -1,6 +1,10 @@
 use crate::app::{
-    app_receivers::AppReceivers, channels::Channels, cpu_monitor::CpuMonitor,
-    process_monitor::ProcessMonitor, system_monitor::SystemMonitor, update::update,
+    alert_monitor::AlertMonitor, app_receivers::AppReceivers, app_senders::AppSenders,
+    channels::Channels, cpu_monitor::CpuMonitor, cpu_times_monitor::CpuTimesMonitor,
+    disk_monitor::DiskMonitor, export_monitor::ExportMonitor, load_monitor::LoadMonitor,
+    memory_monitor::MemoryMonitor, network_monitor::NetworkMonitor,
+    process_monitor::ProcessMonitor, replay_monitor::ReplayMonitor, sensor_monitor::SensorMonitor,
+    settings_monitor::SettingsMonitor, system_monitor::SystemMonitor, update::update,
 };
 
 use eframe::egui::Context;
@@ -15,6 +19,16 @@ pub struct AppMonitor {
     pub cpu_monitor: CpuMonitor,
     pub system_monitor: SystemMonitor,
     pub process_monitor: ProcessMonitor,
+    pub memory_monitor: MemoryMonitor,
+    pub network_monitor: NetworkMonitor,
+    pub disk_monitor: DiskMonitor,
+    pub sensor_monitor: SensorMonitor,
+    pub cpu_times_monitor: CpuTimesMonitor,
+    pub load_monitor: LoadMonitor,
+    pub export_monitor: ExportMonitor,
+    pub alert_monitor: AlertMonitor,
+    pub replay_monitor: ReplayMonitor,
+    pub settings_monitor: SettingsMonitor,
 }
 
 impl AppMonitor {
@@ -22,15 +36,31 @@ impl AppMonitor {
     ///
     /// * Parameters
     /// `app_receivers` The receivers for various data snapshots
+    /// `app_senders` The senders for commands to the workers
     ///
     /// * Returns
     /// A new AppMonitor instance
-    pub fn new(app_receivers: AppReceivers) -> Self {
+    pub fn new(app_receivers: AppReceivers, app_senders: AppSenders) -> Self {
+        let process_monitor: ProcessMonitor = ProcessMonitor::new();
         Self {
-            channels: Channels::new(app_receivers),
+            channels: Channels::new(
+                app_receivers,
+                app_senders,
+                process_monitor.process_filter.clone(),
+            ),
             cpu_monitor: CpuMonitor::new(),
             system_monitor: SystemMonitor::new(),
-            process_monitor: ProcessMonitor::new(),
+            process_monitor,
+            memory_monitor: MemoryMonitor::new(),
+            network_monitor: NetworkMonitor::new(),
+            disk_monitor: DiskMonitor::new(),
+            sensor_monitor: SensorMonitor::new(),
+            cpu_times_monitor: CpuTimesMonitor::new(),
+            load_monitor: LoadMonitor::new(),
+            export_monitor: ExportMonitor::new(),
+            alert_monitor: AlertMonitor::new(),
+            replay_monitor: ReplayMonitor::new(),
+            settings_monitor: SettingsMonitor::new(),
         }
     }
 }
@@ -39,4 +69,11 @@ impl eframe::App for AppMonitor {
     fn update(&mut self, ctx: &Context, _frame: &mut eframe::Frame) {
         update(self, ctx);
     }
+
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        // the --export option writes the history once the window closes
+        self.export_monitor.export_on_exit(&self.cpu_monitor);
+        // changes made in the settings window are kept for the next run
+        self.settings_monitor.save_on_exit();
+    }
 }
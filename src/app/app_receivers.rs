@@ -3,9 +3,17 @@
 //! The AppReceivers struct is what is used to construct AppMonitor, as it
 //! solely builds the Channels sub-structure.
 
-use crate::snapshots::{
-    cpu_snapshot_struct::CpuSnapshot, processes_snapshot_struct::ProcessesSnapshot,
-    system_snapshot_struct::SystemSnapshot,
+use crate::{
+    commands::process_command_outcome_struct::ProcessCommandOutcome,
+    recording::replay_status_struct::ReplayStatus,
+    snapshots::{
+        cpu_snapshot_struct::CpuSnapshot, cpu_times_snapshot_struct::CpuTimesSnapshot,
+        disks_snapshot_struct::DisksSnapshot, load_snapshot_struct::LoadSnapshot,
+        memory_snapshot_struct::MemorySnapshot, network_snapshot_struct::NetworkSnapshot,
+        process_environment_struct::ProcessEnvironment,
+        processes_snapshot_struct::ProcessesSnapshot, sensors_snapshot_struct::SensorsSnapshot,
+        system_snapshot_struct::SystemSnapshot, threads_snapshot_struct::ThreadsSnapshot,
+    },
 };
 
 use std::sync::mpsc::Receiver;
@@ -16,6 +24,16 @@ pub struct AppReceivers {
     pub cpu_snapshot_receiver: Receiver<CpuSnapshot>,
     pub system_snapshot_receiver: Receiver<SystemSnapshot>,
     pub processes_snapshot_receiver: Receiver<ProcessesSnapshot>,
+    pub process_command_outcome_receiver: Receiver<ProcessCommandOutcome>,
+    pub process_environment_receiver: Receiver<ProcessEnvironment>,
+    pub memory_snapshot_receiver: Receiver<MemorySnapshot>,
+    pub network_snapshot_receiver: Receiver<NetworkSnapshot>,
+    pub disks_snapshot_receiver: Receiver<DisksSnapshot>,
+    pub sensors_snapshot_receiver: Receiver<SensorsSnapshot>,
+    pub cpu_times_snapshot_receiver: Receiver<CpuTimesSnapshot>,
+    pub load_snapshot_receiver: Receiver<LoadSnapshot>,
+    pub threads_snapshot_receiver: Receiver<ThreadsSnapshot>,
+    pub replay_status_receiver: Option<Receiver<ReplayStatus>>,
 }
 
 impl AppReceivers {
@@ -23,15 +41,37 @@ impl AppReceivers {
     ///
     /// * Parameters
     /// `cpu_snapshot_receiver` Receiver for CpuSnapshot structure.
+    // one argument per worker channel, grouping them would only move the list elsewhere
+    #[allow(clippy::too_many_arguments)]
     pub fn build(
         cpu_snapshot_receiver: Receiver<CpuSnapshot>,
         system_snapshot_receiver: Receiver<SystemSnapshot>,
         processes_snapshot_receiver: Receiver<ProcessesSnapshot>,
+        process_command_outcome_receiver: Receiver<ProcessCommandOutcome>,
+        process_environment_receiver: Receiver<ProcessEnvironment>,
+        memory_snapshot_receiver: Receiver<MemorySnapshot>,
+        network_snapshot_receiver: Receiver<NetworkSnapshot>,
+        disks_snapshot_receiver: Receiver<DisksSnapshot>,
+        sensors_snapshot_receiver: Receiver<SensorsSnapshot>,
+        cpu_times_snapshot_receiver: Receiver<CpuTimesSnapshot>,
+        load_snapshot_receiver: Receiver<LoadSnapshot>,
+        threads_snapshot_receiver: Receiver<ThreadsSnapshot>,
+        replay_status_receiver: Option<Receiver<ReplayStatus>>,
     ) -> AppReceivers {
         AppReceivers {
             cpu_snapshot_receiver,
             system_snapshot_receiver,
             processes_snapshot_receiver,
+            process_command_outcome_receiver,
+            process_environment_receiver,
+            memory_snapshot_receiver,
+            network_snapshot_receiver,
+            disks_snapshot_receiver,
+            sensors_snapshot_receiver,
+            cpu_times_snapshot_receiver,
+            load_snapshot_receiver,
+            threads_snapshot_receiver,
+            replay_status_receiver,
         }
     }
 }
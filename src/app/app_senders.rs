@@ -0,0 +1,41 @@
+//! Defines AppSenders struct and implementation.
+//!
+//! The AppSenders struct is used alongside AppReceivers to construct AppMonitor,
+//! and holds the senders the GUI uses to send commands back to the workers.
+
+use crate::commands::{process_command_enum::ProcessCommand, replay_command_enum::ReplayCommand};
+use crate::data::process_key_struct::ProcessKey;
+
+use std::sync::mpsc::Sender;
+
+/// AppSenders struct.
+/// Stores all senders made by the .split() function that flow from the GUI to the workers.
+pub struct AppSenders {
+    pub process_command_sender: Sender<ProcessCommand>,
+    pub thread_watch_sender: Sender<Option<ProcessKey>>,
+    pub detail_watch_sender: Sender<Vec<u32>>,
+    pub replay_command_sender: Option<Sender<ReplayCommand>>,
+}
+
+impl AppSenders {
+    /// Constructs an AppSenders struct from given Sender<T>s.
+    ///
+    /// * Parameters
+    /// `process_command_sender` Sender for ProcessCommand enum.
+    /// `thread_watch_sender` Sender for the process whose threads are listed.
+    /// `detail_watch_sender` Sender for the pids whose details are read.
+    /// `replay_command_sender` Sender for ReplayCommand enum, only present during a replay.
+    pub fn build(
+        process_command_sender: Sender<ProcessCommand>,
+        thread_watch_sender: Sender<Option<ProcessKey>>,
+        detail_watch_sender: Sender<Vec<u32>>,
+        replay_command_sender: Option<Sender<ReplayCommand>>,
+    ) -> AppSenders {
+        AppSenders {
+            process_command_sender,
+            thread_watch_sender,
+            detail_watch_sender,
+            replay_command_sender,
+        }
+    }
+}
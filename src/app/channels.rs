@@ -1,30 +1,86 @@
 //! Defines Channels structure and implementation.
 
-use crate::app::app_receivers::AppReceivers;
-use crate::{CpuSnapshot, ProcessesSnapshot, SystemSnapshot};
-use std::sync::mpsc::Receiver;
+use crate::app::{app_receivers::AppReceivers, app_senders::AppSenders};
+use crate::commands::{
+    process_command_enum::ProcessCommand, process_command_outcome_struct::ProcessCommandOutcome,
+    replay_command_enum::ReplayCommand,
+};
+use crate::data::process_key_struct::ProcessKey;
+use crate::filter::{
+    filtered_processes_struct::FilteredProcesses, process_filter_struct::ProcessFilter,
+    process_filter_worker::filter_channel,
+};
+use crate::recording::replay_status_struct::ReplayStatus;
+use crate::snapshots::{
+    cpu_times_snapshot_struct::CpuTimesSnapshot, disks_snapshot_struct::DisksSnapshot,
+    load_snapshot_struct::LoadSnapshot, memory_snapshot_struct::MemorySnapshot,
+    network_snapshot_struct::NetworkSnapshot, process_environment_struct::ProcessEnvironment,
+    sensors_snapshot_struct::SensorsSnapshot, threads_snapshot_struct::ThreadsSnapshot,
+};
+use crate::{CpuSnapshot, SystemSnapshot};
+use std::sync::mpsc::{Receiver, Sender};
 
 /// A sub-structure of the AppMonitor structure.
-/// Stores all receivers used in the GUI.
+/// Stores all receivers and senders used in the GUI.
 pub struct Channels {
     pub cpu_snapshot_receiver: Receiver<CpuSnapshot>,
     pub system_snapshot_receiver: Receiver<SystemSnapshot>,
-    pub processes_snapshot_receiver: Receiver<ProcessesSnapshot>,
+    pub filtered_processes_receiver: Receiver<FilteredProcesses>,
+    pub process_command_outcome_receiver: Receiver<ProcessCommandOutcome>,
+    pub process_environment_receiver: Receiver<ProcessEnvironment>,
+    pub memory_snapshot_receiver: Receiver<MemorySnapshot>,
+    pub network_snapshot_receiver: Receiver<NetworkSnapshot>,
+    pub disks_snapshot_receiver: Receiver<DisksSnapshot>,
+    pub sensors_snapshot_receiver: Receiver<SensorsSnapshot>,
+    pub cpu_times_snapshot_receiver: Receiver<CpuTimesSnapshot>,
+    pub load_snapshot_receiver: Receiver<LoadSnapshot>,
+    pub threads_snapshot_receiver: Receiver<ThreadsSnapshot>,
+    pub replay_status_receiver: Option<Receiver<ReplayStatus>>,
+    pub process_command_sender: Sender<ProcessCommand>,
+    pub process_filter_sender: Sender<ProcessFilter>,
+    pub thread_watch_sender: Sender<Option<ProcessKey>>,
+    pub detail_watch_sender: Sender<Vec<u32>>,
+    pub replay_command_sender: Option<Sender<ReplayCommand>>,
 }
 
 impl Channels {
-    /// Constructor for the Channels structure, consumes an AppReceivers structure.
+    /// Constructor for the Channels structure, consumes AppReceivers and AppSenders structures.
+    /// The processes snapshots pass through the process filter worker on their way in.
     ///
     /// # Parameters
     /// * `app_receivers` AppReceivers structure
+    /// * `app_senders` AppSenders structure
+    /// * `process_filter` The process filter to start with
     ///
     /// # Returns
     /// Channels structure
-    pub fn new(app_receivers: AppReceivers) -> Channels {
+    pub fn new(
+        app_receivers: AppReceivers,
+        app_senders: AppSenders,
+        process_filter: ProcessFilter,
+    ) -> Channels {
+        let (filtered_processes_receiver, process_filter_sender) =
+            filter_channel(app_receivers.processes_snapshot_receiver, process_filter);
+
         Channels {
             cpu_snapshot_receiver: app_receivers.cpu_snapshot_receiver,
             system_snapshot_receiver: app_receivers.system_snapshot_receiver,
-            processes_snapshot_receiver: app_receivers.processes_snapshot_receiver,
+            filtered_processes_receiver,
+            process_command_outcome_receiver: app_receivers.process_command_outcome_receiver,
+            process_environment_receiver: app_receivers.process_environment_receiver,
+            memory_snapshot_receiver: app_receivers.memory_snapshot_receiver,
+            network_snapshot_receiver: app_receivers.network_snapshot_receiver,
+            disks_snapshot_receiver: app_receivers.disks_snapshot_receiver,
+            sensors_snapshot_receiver: app_receivers.sensors_snapshot_receiver,
+            cpu_times_snapshot_receiver: app_receivers.cpu_times_snapshot_receiver,
+            load_snapshot_receiver: app_receivers.load_snapshot_receiver,
+            threads_snapshot_receiver: app_receivers.threads_snapshot_receiver,
+            replay_status_receiver: app_receivers.replay_status_receiver,
+            process_command_sender: app_senders.process_command_sender,
+            process_filter_sender,
+            thread_watch_sender: app_senders.thread_watch_sender,
+            detail_watch_sender: app_senders.detail_watch_sender,
+            replay_command_sender: app_senders.replay_command_sender,
         }
     }
 }
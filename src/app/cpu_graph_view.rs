@@ -0,0 +1,172 @@
+//! CPU graph view module.
+//!
+//! This module makes the CPU usage graphs interactive: hovering shows the values under
+//! the pointer, dragging pans back through the history and scrolling zooms the time axis.
+
+use std::collections::VecDeque;
+use std::ops::Range;
+
+use crate::{
+    app::top_consumers_view::render_top_consumers,
+    config::style::RECT_SHRINK_AMNT,
+    data::{format::format_timestamp_milliseconds, top_consumer_struct::TopConsumer},
+    graph::{
+        draw::draw_graph_cursor,
+        graph_viewport_struct::{GraphViewport, ZOOM_PER_SCROLL_POINT},
+        time_window_enum::TimeWindow,
+    },
+};
+
+use eframe::egui::{Rect, Response, Ui};
+
+/// Renders a button per time window, choosing how much history the CPU graphs show,
+/// and the toggle linking the cursors of the core graphs.
+///
+/// * Parameters
+/// `ui` The UI to draw in
+/// `graph_viewport` Mutable reference to the viewport shared by the CPU graphs
+pub fn render_time_window_selector(ui: &mut Ui, graph_viewport: &mut GraphViewport) {
+    ui.horizontal(|ui| {
+        ui.label("Show the last");
+        for time_window in TimeWindow::ALL {
+            if ui
+                .selectable_label(graph_viewport.is_showing(time_window), time_window.label())
+                .clicked()
+            {
+                graph_viewport.show_time_window(time_window);
+            }
+        }
+        ui.checkbox(&mut graph_viewport.link_cursors, "Link core cursors");
+
+        if graph_viewport.pinned_end_timestamp.is_some() {
+            ui.weak("Panned back, double-click a graph to follow the newest snapshot");
+        }
+    })
+    .response
+    .on_hover_text("Drag a graph to pan back in time, scroll over it to zoom");
+}
+
+/// Handles the pointer over a CPU usage graph and draws its time cursor.
+///
+/// Dragging pans the viewport, scrolling zooms it around the pointer and a double
+/// click goes back to following the newest snapshot. The hovered point is shown in
+/// a tooltip, with the busiest processes at that point when they are given, and in
+/// every other graph when the cursors are linked.
+///
+/// * Parameters
+/// `ui` The UI the graph is in
+/// `response` The response of the graph's rectangle, sensing clicks and drags
+/// `graph_viewport` Mutable reference to the viewport shared by the CPU graphs
+/// `timestamp_history` The timestamps of the points in the histories
+/// `history` The usage history drawn in the graph
+/// `ema_history` The EMA history drawn over it, if any
+/// `top_consumers_history` The busiest processes at each point, None for graphs without them
+/// `visible_range` The indices of the points drawn
+// the viewport is borrowed mutably from the monitor holding the histories, so the
+// monitor itself can't be passed instead
+#[allow(clippy::too_many_arguments)]
+pub fn handle_cpu_graph_pointer(
+    ui: &mut Ui,
+    response: &Response,
+    graph_viewport: &mut GraphViewport,
+    timestamp_history: &VecDeque<u64>,
+    history: &VecDeque<f32>,
+    ema_history: Option<&VecDeque<f32>>,
+    top_consumers_history: Option<&VecDeque<Vec<TopConsumer>>>,
+    visible_range: Range<usize>,
+) {
+    let (Some(oldest), Some(latest)) = (timestamp_history.front(), timestamp_history.back()) else {
+        return;
+    };
+    let plot_rect: Rect = response.rect.shrink(RECT_SHRINK_AMNT);
+
+    if response.double_clicked() {
+        graph_viewport.follow_latest();
+    } else if response.dragged() {
+        // dragging right brings older points into view
+        let milliseconds: f64 = response.drag_delta().x as f64 / plot_rect.width() as f64
+            * graph_viewport.span_milliseconds as f64;
+        graph_viewport.pan(milliseconds, *oldest, *latest);
+    }
+
+    if let Some(pointer) = response.hover_pos() {
+        let pointer_fraction: f32 =
+            ((pointer.x - plot_rect.left()) / plot_rect.width()).clamp(0.0, 1.0);
+
+        let scroll: f32 = ui.input(|input| input.smooth_scroll_delta.y);
+        if scroll != 0.0 {
+            let factor: f64 = (-scroll as f64 * ZOOM_PER_SCROLL_POINT).exp();
+            graph_viewport.zoom(factor, pointer_fraction as f64, *oldest, *latest);
+            // consumed, so the scroll area around the core graphs doesn't scroll too
+            ui.ctx().input_mut(|input| input.smooth_scroll_delta.y = 0.0);
+        }
+
+        let Some(index) = index_at_fraction(&visible_range, pointer_fraction) else {
+            return;
+        };
+        graph_viewport.hovered_timestamp = Some(timestamp_history[index]);
+        draw_graph_cursor(
+            &response.rect,
+            ui,
+            x_of_index(&plot_rect, &visible_range, index),
+            None,
+        );
+
+        response.clone().on_hover_ui_at_pointer(|ui| {
+            ui.label(format_timestamp_milliseconds(timestamp_history[index]));
+            ui.monospace(format!("Usage {:>5.1}%", history[index]));
+            if let Some(ema) = ema_history.and_then(|history| history.get(index)) {
+                ui.monospace(format!("EMA   {:>5.1}%", ema));
+            }
+            if let Some(top_consumers) =
+                top_consumers_history.and_then(|history| history.get(index))
+                && !top_consumers.is_empty()
+            {
+                ui.separator();
+                ui.label("Top consumers");
+                render_top_consumers(ui, top_consumers);
+            }
+        });
+    } else if graph_viewport.link_cursors
+        && let Some(cursor_timestamp) = graph_viewport.cursor_timestamp
+    {
+        let index: usize =
+            timestamp_history.partition_point(|timestamp| *timestamp < cursor_timestamp);
+        if visible_range.contains(&index) && index < history.len() {
+            draw_graph_cursor(
+                &response.rect,
+                ui,
+                x_of_index(&plot_rect, &visible_range, index),
+                Some(&format!("{:.1}%", history[index])),
+            );
+        }
+    }
+}
+
+/// Finds the point nearest to a position across a graph.
+///
+/// * Parameters
+/// `visible_range` The indices of the points drawn
+/// `fraction` The position, from 0 at the left edge to 1 at the right
+///
+/// * Returns
+/// The index of the point, or None when no points are drawn
+fn index_at_fraction(visible_range: &Range<usize>, fraction: f32) -> Option<usize> {
+    let last_offset: usize = visible_range.len().checked_sub(1)?;
+    Some(visible_range.start + (fraction * last_offset as f32).round() as usize)
+}
+
+/// Finds where a point is drawn across a graph.
+///
+/// * Parameters
+/// `plot_rect` The area the lines of the graph are drawn in
+/// `visible_range` The indices of the points drawn
+/// `index` The index of the point
+///
+/// * Returns
+/// The x-coordinate of the point
+fn x_of_index(plot_rect: &Rect, visible_range: &Range<usize>, index: usize) -> f32 {
+    let last_offset: usize = visible_range.len().saturating_sub(1).max(1);
+    let fraction: f32 = (index - visible_range.start) as f32 / last_offset as f32;
+    plot_rect.left() + fraction * plot_rect.width()
+}
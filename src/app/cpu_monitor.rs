@@ -1,16 +1,29 @@
 use std::collections::VecDeque;
+use std::ops::Range;
 
 use crate::{
-    config::app_variables::{CORES_UPPER_LIMIT, MAX_LINE_GRAPH_POINTS},
-    data::exponential_moving_average::{
-        get_cpu_exponential_moving_average, get_per_core_exponential_moving_average,
+    config::{app_variables::CORES_UPPER_LIMIT, settings::settings_monitoring},
+    data::{
+        exponential_moving_average::{
+            get_cpu_exponential_moving_average, get_per_core_exponential_moving_average,
+        },
+        history::{history_add_point_with_limit, history_trim},
+        time::unix_time_milliseconds,
+        top_consumer_struct::TopConsumer,
     },
+    export::{metrics_export_struct::MetricsExport, metrics_sample_struct::MetricsSample},
+    graph::graph_viewport_struct::GraphViewport,
     snapshots::cpu_snapshot_struct::CpuSnapshot,
 };
 
 /// A sub-structure of the AppMonitor structure.
 /// Stores all relevant cpu data produced from consuming a CpuSnapshot structure.
 /// Note: ema stands for exponential moving average.
+/// Note: frequencies are in MHz.
+/// Note: timestamps are in milliseconds since the unix epoch, one per point in the histories.
+/// Note: the histories hold history_seconds of snapshots, up to MAX_CPU_HISTORY_POINTS, the
+/// graphs show the graph_viewport.
+/// Note: the top consumers are the processes using the most CPU when each snapshot arrived.
 pub struct CpuMonitor {
     pub average_cpu_usage: f32,
     pub cumulative_cpu_usage: f32,
@@ -21,18 +34,27 @@ pub struct CpuMonitor {
     pub overall_cpu_history: VecDeque<f32>,
     pub overall_ema_cpu_history: VecDeque<f32>,
     pub total_snapshots_received: i128,
+    pub timestamp_history: VecDeque<u64>,
+    pub average_cpu_history: VecDeque<f32>,
+    pub process_count_history: VecDeque<usize>,
+    pub top_consumers_history: VecDeque<Vec<TopConsumer>>,
+    pub per_core_frequency: Vec<u64>,
+    pub per_core_frequency_limits: Vec<Option<(u64, u64)>>,
+    pub per_core_frequency_history: Option<Vec<VecDeque<f32>>>,
+    pub show_frequency_graphs: bool,
+    pub graph_viewport: GraphViewport,
 }
 
 impl CpuMonitor {
-    /// Constructor for the CpuMonitor structure.
-    ///
-    /// # Returns
-    /// CpuMonitor struture
-
     // INVARIANTS:
     // Per core functionality is represented as an Option initialized as None because
     // we have no way of knowing how many cores the device has until the first CpuSnapshot
     // arrives.
+
+    /// Constructor for the CpuMonitor structure.
+    ///
+    /// # Returns
+    /// CpuMonitor struture
     pub fn new() -> CpuMonitor {
         CpuMonitor {
             average_cpu_usage: 0.0,
@@ -45,20 +67,105 @@ impl CpuMonitor {
             overall_cpu_history: VecDeque::with_capacity(CORES_UPPER_LIMIT),
             overall_ema_cpu_history: VecDeque::with_capacity(CORES_UPPER_LIMIT),
             total_snapshots_received: 0,
+            timestamp_history: VecDeque::new(),
+            average_cpu_history: VecDeque::new(),
+            process_count_history: VecDeque::new(),
+            top_consumers_history: VecDeque::new(),
+            per_core_frequency: Vec::new(),
+            per_core_frequency_limits: Vec::new(),
+            per_core_frequency_history: None,
+            show_frequency_graphs: false,
+            graph_viewport: GraphViewport::new(),
         }
     }
 
-    /// Takes in a CpuSnapshot struct and updates the fields in CpuMonitor.
+    /// Clears every history, for when the snapshots stop following on from each
+    /// other, such as after seeking in a replay. The graph settings are kept, but the
+    /// graphs go back to following the newest snapshot.
+    pub fn cpu_monitor_reset(&mut self) {
+        let show_frequency_graphs: bool = self.show_frequency_graphs;
+        let graph_viewport: GraphViewport = GraphViewport {
+            pinned_end_timestamp: None,
+            ..std::mem::replace(&mut self.graph_viewport, GraphViewport::new())
+        };
+        *self = CpuMonitor::new();
+        self.show_frequency_graphs = show_frequency_graphs;
+        self.graph_viewport = graph_viewport;
+    }
+
+    /// Finds the time range the CPU graphs show.
     ///
-    /// * Parameters
-    /// `cpu_snapshot` CpuSnapshot structure
+    /// * Returns
+    /// The (start, end) timestamps, both inclusive, or None before the first snapshot
+    pub fn visible_time_range(&self) -> Option<(u64, u64)> {
+        let latest: &u64 = self.timestamp_history.back()?;
+        Some(self.graph_viewport.visible_time_range(*latest))
+    }
+
+    /// Finds the points of the histories that fall in the time range shown.
+    ///
+    /// * Returns
+    /// The indices of the points to draw
+    pub fn visible_range(&self) -> Range<usize> {
+        let Some((start, end)) = self.visible_time_range() else {
+            return 0..0;
+        };
+        let first_visible: usize = self
+            .timestamp_history
+            .partition_point(|timestamp| *timestamp < start);
+        let last_visible: usize = self
+            .timestamp_history
+            .partition_point(|timestamp| *timestamp <= end);
+        first_visible..last_visible
+    }
+
+    /// Trims every history to the history length in the settings, for when it was
+    /// lowered in the settings window. Longer lengths fill up as snapshots arrive.
+    pub fn cpu_monitor_trim_histories(&mut self) {
+        let history_points: usize = cpu_history_points();
+
+        history_trim(&mut self.overall_cpu_history, history_points);
+        history_trim(&mut self.overall_ema_cpu_history, history_points);
+        history_trim(&mut self.timestamp_history, history_points);
+        history_trim(&mut self.average_cpu_history, history_points);
+        history_trim(&mut self.process_count_history, history_points);
+        history_trim(&mut self.top_consumers_history, history_points);
+        for per_core_histories in [
+            &mut self.per_core_cpu_history,
+            &mut self.per_core_ema_cpu_history,
+            &mut self.per_core_frequency_history,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            for history in per_core_histories.iter_mut() {
+                history_trim(history, history_points);
+            }
+        }
+    }
 
     // INVARIANTS:
     // All per core charts are constructed when the first CpuSnapshot structure is received.
     // exponential moving average is guaranteed to exist after the first cpu snapshot.
-    // history charts have a maximum of 10 data points - that is what MAX_LINE_GRAPH_POINTS refers to.
+    // history charts keep cpu_history_points() data points, enough for history_seconds.
+
+    /// Takes in a CpuSnapshot struct and updates the fields in CpuMonitor.
+    ///
+    /// * Parameters
+    /// `cpu_snapshot` CpuSnapshot structure
     pub fn cpu_monitor_apply_cpu_snapshot(&mut self, cpu_snapshot: CpuSnapshot) {
         self.adjust_average_cpu_usage(&cpu_snapshot);
+        let history_points: usize = cpu_history_points();
+        history_add_point_with_limit(
+            &mut self.timestamp_history,
+            cpu_snapshot.timestamp_milliseconds,
+            history_points,
+        );
+        history_add_point_with_limit(
+            &mut self.average_cpu_history,
+            self.average_cpu_usage,
+            history_points,
+        );
 
         self.overall_cpu_history_add_point(&cpu_snapshot);
         self.overall_ema_cpu_history_add_point(&cpu_snapshot);
@@ -76,6 +183,64 @@ impl CpuMonitor {
         self.per_core_ema_cpu_history_add_point();
 
         self.per_core_cpu_history_add_point(&cpu_snapshot);
+
+        self.per_core_frequency_history_add_point(&cpu_snapshot);
+        self.per_core_frequency = cpu_snapshot.per_core_frequency;
+        self.per_core_frequency_limits = cpu_snapshot.per_core_frequency_limits;
+    }
+
+    /// Adds the number of processes running when the latest CpuSnapshot arrived
+    /// to the process count history.
+    ///
+    /// * Parameters
+    /// `process_count` The number of processes
+    pub fn cpu_monitor_add_process_count(&mut self, process_count: usize) {
+        history_add_point_with_limit(
+            &mut self.process_count_history,
+            process_count,
+            cpu_history_points(),
+        );
+    }
+
+    /// Adds the processes using the most CPU when the latest CpuSnapshot arrived to
+    /// the top consumers history.
+    ///
+    /// * Parameters
+    /// `top_consumers` The busiest processes, busiest first
+    pub fn cpu_monitor_add_top_consumers(&mut self, top_consumers: Vec<TopConsumer>) {
+        history_add_point_with_limit(
+            &mut self.top_consumers_history,
+            top_consumers,
+            cpu_history_points(),
+        );
+    }
+
+    /// Collects the histories into a MetricsExport, one sample per CpuSnapshot
+    /// still held in the histories.
+    ///
+    /// * Returns
+    /// MetricsExport structure, oldest sample first
+    pub fn cpu_monitor_metrics_export(&self) -> MetricsExport {
+        let samples: Vec<MetricsSample> = self
+            .timestamp_history
+            .iter()
+            .enumerate()
+            .map(|(index, timestamp_milliseconds)| MetricsSample {
+                timestamp_milliseconds: *timestamp_milliseconds,
+                overall_cpu_usage: self.overall_cpu_history[index],
+                overall_ema_cpu_usage: self.overall_ema_cpu_history[index],
+                average_cpu_usage: self.average_cpu_history[index],
+                process_count: self.process_count_history.get(index).copied().unwrap_or(0),
+                per_core_cpu_usage: per_core_values_at(&self.per_core_cpu_history, index),
+                per_core_ema_cpu_usage: per_core_values_at(&self.per_core_ema_cpu_history, index),
+            })
+            .collect();
+
+        MetricsExport {
+            exported_at_milliseconds: unix_time_milliseconds(),
+            average_cpu_usage: self.average_cpu_usage,
+            samples,
+        }
     }
 
     /// Adjusts average cpu usage
@@ -95,11 +260,11 @@ impl CpuMonitor {
     /// * Parameters
     /// `cpu_snapshot` Reference to the CPU snapshot containing the usage data
     fn overall_cpu_history_add_point(&mut self, cpu_snapshot: &CpuSnapshot) {
-        self.overall_cpu_history
-            .push_back(cpu_snapshot.overall_cpu_usage);
-        if self.overall_cpu_history.len() > MAX_LINE_GRAPH_POINTS {
-            self.overall_cpu_history.pop_front();
-        }
+        history_add_point_with_limit(
+            &mut self.overall_cpu_history,
+            cpu_snapshot.overall_cpu_usage,
+            cpu_history_points(),
+        );
     }
 
     /// Adds a data point to the overall EMA CPU history.
@@ -113,11 +278,11 @@ impl CpuMonitor {
             get_cpu_exponential_moving_average(self.previous_ema, cpu_snapshot.overall_cpu_usage);
 
         self.previous_ema = Some(overall_cpu_exponential_moving_average);
-        self.overall_ema_cpu_history
-            .push_back(overall_cpu_exponential_moving_average);
-        if self.overall_ema_cpu_history.len() > MAX_LINE_GRAPH_POINTS {
-            self.overall_ema_cpu_history.pop_front();
-        }
+        history_add_point_with_limit(
+            &mut self.overall_ema_cpu_history,
+            overall_cpu_exponential_moving_average,
+            cpu_history_points(),
+        );
     }
 
     /// Constructs per-core line charts if they haven't been initialized yet.
@@ -127,11 +292,12 @@ impl CpuMonitor {
     /// * Parameters
     /// `cpu_snapshot` Reference to the CPU snapshot to determine the number of cores
     fn construct_per_core_line_charts(&mut self, cpu_snapshot: &CpuSnapshot) {
-        if let None = self.per_core_cpu_history {
+        if self.per_core_cpu_history.is_none() {
             let n: usize = cpu_snapshot.per_core_cpu_usage.len();
 
             self.per_core_cpu_history = Some(vec![VecDeque::new(); n]);
             self.per_core_ema_cpu_history = Some(vec![VecDeque::new(); n]);
+            self.per_core_frequency_history = Some(vec![VecDeque::new(); n]);
 
             // constructs per core ema history
             self.per_core_previous_ema = vec![None; n];
@@ -142,13 +308,11 @@ impl CpuMonitor {
     ///
     /// Iterates through each core's EMA and appends to the history, maintaining max size.
     fn per_core_ema_cpu_history_add_point(&mut self) {
+        let history_points: usize = cpu_history_points();
         for (index, ema) in self.per_core_previous_ema.iter().enumerate() {
             let per_core_ema_values: &mut VecDeque<f32> =
                 &mut self.per_core_ema_cpu_history.as_mut().unwrap()[index];
-            per_core_ema_values.push_back(ema.unwrap());
-            if per_core_ema_values.len() > MAX_LINE_GRAPH_POINTS {
-                per_core_ema_values.pop_front();
-            }
+            history_add_point_with_limit(per_core_ema_values, ema.unwrap(), history_points);
         }
     }
 
@@ -161,13 +325,54 @@ impl CpuMonitor {
     fn per_core_cpu_history_add_point(&mut self, cpu_snapshot: &CpuSnapshot) {
         let per_core_cpu_history: &mut Vec<VecDeque<f32>> =
             self.per_core_cpu_history.as_mut().unwrap();
+        let history_points: usize = cpu_history_points();
 
         for (index, value) in cpu_snapshot.per_core_cpu_usage.iter().enumerate() {
             let per_core_values: &mut VecDeque<f32> = &mut per_core_cpu_history[index];
-            per_core_values.push_back(*value);
-            if per_core_values.len() > MAX_LINE_GRAPH_POINTS {
-                per_core_values.pop_front();
-            }
+            history_add_point_with_limit(per_core_values, *value, history_points);
         }
     }
+
+    /// Adds the latest per-core frequencies to the history.
+    ///
+    /// * Parameters
+    /// `cpu_snapshot` Reference to the CPU snapshot containing per-core frequency data
+    fn per_core_frequency_history_add_point(&mut self, cpu_snapshot: &CpuSnapshot) {
+        let per_core_frequency_history: &mut Vec<VecDeque<f32>> =
+            self.per_core_frequency_history.as_mut().unwrap();
+        let history_points: usize = cpu_history_points();
+
+        for (index, frequency) in cpu_snapshot.per_core_frequency.iter().enumerate() {
+            history_add_point_with_limit(
+                &mut per_core_frequency_history[index],
+                *frequency as f32,
+                history_points,
+            );
+        }
+    }
+}
+
+/// Finds how many points the histories keep, enough for history_seconds of
+/// snapshots at the sampling interval up to MAX_CPU_HISTORY_POINTS.
+///
+/// * Returns
+/// The number of points
+fn cpu_history_points() -> usize {
+    settings_monitoring().cpu_history_points()
+}
+
+/// Takes the value at one point in the history of every core.
+///
+/// * Parameters
+/// `per_core_history` The per-core histories, None before the first CpuSnapshot
+/// `index` The point in the histories
+///
+/// * Returns
+/// One value per core
+fn per_core_values_at(per_core_history: &Option<Vec<VecDeque<f32>>>, index: usize) -> Vec<f32> {
+    per_core_history
+        .iter()
+        .flatten()
+        .filter_map(|history| history.get(index).copied())
+        .collect()
 }
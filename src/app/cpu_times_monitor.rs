@@ -0,0 +1,122 @@
+//! CPU times monitor module.
+//!
+//! This module defines the CpuTimesMonitor struct for tracking how the CPUs spend their time.
+
+use std::collections::VecDeque;
+use std::ops::Range;
+
+use crate::{
+    config::settings::settings_monitoring,
+    data::history::history_add_point_with_limit,
+    snapshots::{
+        cpu_times_record_struct::CpuTimesRecord, cpu_times_snapshot_struct::CpuTimesSnapshot,
+    },
+};
+
+/// A sub-structure of the AppMonitor structure.
+/// Stores the history of the CPU time breakdown of all CPUs together and of each
+/// logical CPU, and whether the breakdown is drawn instead of plain utilisation.
+/// Note: the histories stay empty where the breakdown isn't available (outside Linux).
+/// Note: the histories are as long as the CPU histories, and the timestamps line up
+/// with the newest points of every history. A per-core history can be shorter, when
+/// it was started again.
+pub struct CpuTimesMonitor {
+    pub timestamp_history: VecDeque<u64>,
+    pub overall_history: VecDeque<CpuTimesRecord>,
+    pub per_core_history: Vec<VecDeque<CpuTimesRecord>>,
+    pub show_breakdown: bool,
+}
+
+impl CpuTimesMonitor {
+    /// Constructor for the CpuTimesMonitor structure.
+    ///
+    /// * Returns
+    /// CpuTimesMonitor structure with empty histories
+    pub fn new() -> CpuTimesMonitor {
+        CpuTimesMonitor {
+            timestamp_history: VecDeque::new(),
+            overall_history: VecDeque::new(),
+            per_core_history: Vec::new(),
+            show_breakdown: true,
+        }
+    }
+
+    /// Takes in a CpuTimesSnapshot struct and updates the fields in CpuTimesMonitor.
+    ///
+    /// The per-core histories are started again if the number of CPUs changes.
+    ///
+    /// * Parameters
+    /// `cpu_times_snapshot` CpuTimesSnapshot structure
+    pub fn cpu_times_monitor_apply_cpu_times_snapshot(
+        &mut self,
+        cpu_times_snapshot: CpuTimesSnapshot,
+    ) {
+        let history_points: usize = settings_monitoring().cpu_history_points();
+        history_add_point_with_limit(
+            &mut self.timestamp_history,
+            cpu_times_snapshot.timestamp_milliseconds,
+            history_points,
+        );
+        history_add_point_with_limit(
+            &mut self.overall_history,
+            cpu_times_snapshot.overall,
+            history_points,
+        );
+
+        // a snapshot without them leaves a gap, so the per-core histories start again
+        if self.per_core_history.len() != cpu_times_snapshot.per_core.len() {
+            self.per_core_history = vec![VecDeque::new(); cpu_times_snapshot.per_core.len()];
+        }
+        for (history, record) in self
+            .per_core_history
+            .iter_mut()
+            .zip(cpu_times_snapshot.per_core)
+        {
+            history_add_point_with_limit(history, record, history_points);
+        }
+    }
+
+    /// Finds the breakdown history to draw for a graph, if the breakdown is shown.
+    ///
+    /// * Parameters
+    /// `core_index` The index of the logical CPU, or None for all CPUs together
+    ///
+    /// * Returns
+    /// Some(history) if the breakdown is shown and has been received, None otherwise
+    pub fn visible_history(&self, core_index: Option<usize>) -> Option<&VecDeque<CpuTimesRecord>> {
+        if !self.show_breakdown {
+            return None;
+        }
+        let history: &VecDeque<CpuTimesRecord> = match core_index {
+            Some(index) => self.per_core_history.get(index)?,
+            None => &self.overall_history,
+        };
+        (!history.is_empty()).then_some(history)
+    }
+
+    /// Finds the points of a breakdown history that fall in the time range shown.
+    ///
+    /// * Parameters
+    /// `history` The breakdown history, one of the histories of this monitor
+    /// `visible_time_range` The (start, end) timestamps shown, both inclusive
+    ///
+    /// * Returns
+    /// The indices of the points to draw
+    pub fn visible_range(
+        &self,
+        history: &VecDeque<CpuTimesRecord>,
+        (start, end): (u64, u64),
+    ) -> Range<usize> {
+        // the history holds the newest of the timestamps
+        let skipped: usize = self.timestamp_history.len().saturating_sub(history.len());
+        let first_visible: usize = self
+            .timestamp_history
+            .partition_point(|timestamp| *timestamp < start)
+            .max(skipped);
+        let last_visible: usize = self
+            .timestamp_history
+            .partition_point(|timestamp| *timestamp <= end)
+            .max(first_visible);
+        first_visible - skipped..last_visible - skipped
+    }
+}
@@ -0,0 +1,73 @@
+//! CPU times view module.
+//!
+//! This module renders the CPU time breakdown as stacked area graphs and their legend.
+
+use std::collections::VecDeque;
+use std::ops::Range;
+
+use crate::{
+    app::cpu_times_monitor::CpuTimesMonitor, config::style::CPU_TIME_COLORS,
+    graph::draw::draw_stacked_ui_graph, snapshots::cpu_times_record_struct::CpuTimesRecord,
+};
+
+use eframe::egui::{Color32, Rect, Ui};
+
+/// Renders the breakdown toggle and, when the breakdown is shown, the legend of
+/// its colours with the latest overall share of each state.
+///
+/// * Parameters
+/// `ui` The UI to draw in
+/// `cpu_times_monitor` Mutable reference to the CPU times monitor for data access
+pub fn render_cpu_times_legend(ui: &mut Ui, cpu_times_monitor: &mut CpuTimesMonitor) {
+    ui.horizontal_wrapped(|ui| {
+        ui.checkbox(
+            &mut cpu_times_monitor.show_breakdown,
+            "Show CPU time breakdown",
+        );
+        let Some(latest) = cpu_times_monitor
+            .visible_history(None)
+            .and_then(|history| history.back())
+        else {
+            return;
+        };
+
+        for ((state, time), color) in CpuTimesRecord::BUSY_STATES
+            .iter()
+            .zip(latest.busy_times())
+            .zip(CPU_TIME_COLORS)
+        {
+            ui.colored_label(color, format!("{} {:.1}%", state, time));
+        }
+        ui.label(format!("idle {:.1}%", latest.idle));
+    });
+}
+
+/// Draws a CPU time breakdown history as a stacked area graph.
+///
+/// Idle time is left unfilled, so the height of the stack is the utilisation.
+///
+/// * Parameters
+/// `rect` The rectangle to draw in
+/// `ui` The UI context
+/// `history` The breakdown history to plot
+/// `visible_range` The indices of the points to draw
+pub fn draw_cpu_times_graph(
+    rect: &Rect,
+    ui: &mut Ui,
+    history: &VecDeque<CpuTimesRecord>,
+    visible_range: Range<usize>,
+) {
+    let layers: Vec<(VecDeque<f32>, Color32)> = CPU_TIME_COLORS
+        .iter()
+        .enumerate()
+        .map(|(state_index, color)| {
+            let layer: VecDeque<f32> = history
+                .range(visible_range.clone())
+                .map(|record| record.busy_times()[state_index])
+                .collect();
+            (layer, *color)
+        })
+        .collect();
+
+    draw_stacked_ui_graph(rect, ui, &layers);
+}
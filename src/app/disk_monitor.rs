@@ -0,0 +1,81 @@
+//! Disk monitor module.
+//!
+//! This module defines the DiskMonitor struct for tracking filesystem usage and
+//! block device I/O.
+
+use std::collections::{BTreeMap, VecDeque};
+
+use crate::{
+    data::history::history_add_point,
+    snapshots::{
+        block_device_record_struct::BlockDeviceRecord, disks_snapshot_struct::DisksSnapshot,
+        filesystem_record_struct::FilesystemRecord,
+    },
+};
+
+/// I/O history of a single block device.
+///
+/// Stores the latest record and the read and write byte rate histories.
+pub struct BlockDeviceHistory {
+    pub latest_record: BlockDeviceRecord,
+    pub read_history: VecDeque<f32>,
+    pub write_history: VecDeque<f32>,
+}
+
+/// A sub-structure of the AppMonitor structure.
+/// Stores the latest filesystem records and the I/O history of every block device,
+/// ordered by name.
+pub struct DiskMonitor {
+    pub filesystems: Vec<FilesystemRecord>,
+    pub block_devices: BTreeMap<String, BlockDeviceHistory>,
+}
+
+impl DiskMonitor {
+    /// Constructor for the DiskMonitor structure.
+    ///
+    /// * Returns
+    /// DiskMonitor structure with no filesystems or block devices
+    pub fn new() -> DiskMonitor {
+        DiskMonitor {
+            filesystems: Vec::new(),
+            block_devices: BTreeMap::new(),
+        }
+    }
+
+    /// Takes in a DisksSnapshot struct and updates the fields in DiskMonitor.
+    ///
+    /// Block devices missing from the snapshot are removed along with their history.
+    ///
+    /// * Parameters
+    /// `disks_snapshot` DisksSnapshot structure
+    pub fn disk_monitor_apply_disks_snapshot(&mut self, disks_snapshot: DisksSnapshot) {
+        self.filesystems = disks_snapshot.filesystems;
+
+        self.block_devices.retain(|name, _| {
+            disks_snapshot
+                .block_devices
+                .iter()
+                .any(|record| &record.name == name)
+        });
+
+        for record in disks_snapshot.block_devices {
+            let block_device_history: &mut BlockDeviceHistory = self
+                .block_devices
+                .entry(record.name.clone())
+                .or_insert_with(|| BlockDeviceHistory {
+                    latest_record: record.clone(),
+                    read_history: VecDeque::new(),
+                    write_history: VecDeque::new(),
+                });
+            history_add_point(
+                &mut block_device_history.read_history,
+                record.read_bytes_per_second as f32,
+            );
+            history_add_point(
+                &mut block_device_history.write_history,
+                record.written_bytes_per_second as f32,
+            );
+            block_device_history.latest_record = record;
+        }
+    }
+}
@@ -0,0 +1,134 @@
+//! Disk view module.
+//!
+//! This module renders the filesystem usage table and one I/O row per block device.
+
+use crate::{
+    app::disk_monitor::{BlockDeviceHistory, DiskMonitor},
+    config::{
+        layout::{
+            CELL_HEIGHT_PX, DEVICE_LEFT_CELL_WIDTH_PX, PROGRESS_BAR_HEIGHT_PX,
+            PROGRESS_BAR_ROUNDING_PX, PROGRESS_BAR_WIDTH_PX, TEXT_SPACING_PX,
+        },
+        style::{HALF_OPACITY, READ_LINE_COLOR, WRITE_LINE_COLOR},
+    },
+    data::format::{format_byte_rate, format_bytes},
+    graph::{
+        draw::{build_progress_bar, draw_scaled_ui_graph},
+        geometry::byte_rate_axis,
+        style::get_color,
+    },
+};
+
+use eframe::egui::{Grid, Sense, Ui, UiBuilder, Vec2, vec2};
+
+/// Renders the disk panel.
+///
+/// Draws the filesystem table followed by a row per block device with its
+/// current rates and a rolling graph of bytes read and written per second.
+///
+/// * Parameters
+/// `ui` The UI to draw in
+/// `disk_monitor` Reference to the disk monitor for data access
+pub fn render_disk_panel(ui: &mut Ui, disk_monitor: &DiskMonitor) {
+    Grid::new("filesystem_table")
+        .striped(true)
+        .num_columns(5)
+        .show(ui, |ui| {
+            for header in ["Mount point", "Device", "Type", "Used", ""] {
+                ui.strong(header);
+            }
+            ui.end_row();
+
+            for filesystem in &disk_monitor.filesystems {
+                let used_space: u64 = filesystem
+                    .total_space
+                    .saturating_sub(filesystem.available_space);
+                let usage: f32 = match filesystem.total_space {
+                    0 => 0.0,
+                    total_space => (used_space as f64 / total_space as f64 * 100.0) as f32,
+                };
+
+                ui.label(&filesystem.mount_point);
+                ui.label(&filesystem.device_name);
+                ui.label(&filesystem.filesystem_type);
+                ui.monospace(format!(
+                    "{} / {}",
+                    format_bytes(used_space),
+                    format_bytes(filesystem.total_space)
+                ));
+                ui.add(build_progress_bar(
+                    usage,
+                    PROGRESS_BAR_WIDTH_PX,
+                    PROGRESS_BAR_HEIGHT_PX,
+                    PROGRESS_BAR_ROUNDING_PX,
+                    get_color(usage, HALF_OPACITY),
+                ));
+                ui.end_row();
+            }
+        });
+
+    ui.add_space(TEXT_SPACING_PX);
+
+    for block_device_history in disk_monitor.block_devices.values() {
+        render_block_device_row(ui, block_device_history);
+        ui.add_space(TEXT_SPACING_PX);
+    }
+}
+
+/// Renders a single block device row.
+///
+/// * Parameters
+/// `ui` The UI to draw in
+/// `block_device_history` The I/O history of the block device
+fn render_block_device_row(ui: &mut Ui, block_device_history: &BlockDeviceHistory) {
+    let record = &block_device_history.latest_record;
+
+    ui.group(|ui| {
+        ui.horizontal(|ui| {
+            let left_cell: Vec2 = vec2(DEVICE_LEFT_CELL_WIDTH_PX, CELL_HEIGHT_PX);
+            let (rect, _) = ui.allocate_exact_size(left_cell, Sense::hover());
+
+            ui.scope_builder(UiBuilder::new().max_rect(rect), |ui| {
+                ui.label(&record.name);
+                ui.colored_label(
+                    READ_LINE_COLOR,
+                    format!(
+                        "read  {} ({:.0} IOPS)",
+                        format_byte_rate(record.read_bytes_per_second),
+                        record.read_operations_per_second
+                    ),
+                );
+                ui.colored_label(
+                    WRITE_LINE_COLOR,
+                    format!(
+                        "write {} ({:.0} IOPS)",
+                        format_byte_rate(record.written_bytes_per_second),
+                        record.write_operations_per_second
+                    ),
+                );
+            });
+
+            let right_cell: Vec2 = vec2(ui.available_width(), CELL_HEIGHT_PX);
+            let (rect, _) = ui.allocate_exact_size(right_cell, Sense::hover());
+
+            // both lines share one axis so their heights can be compared
+            let max_rate: f32 = block_device_history
+                .read_history
+                .iter()
+                .chain(block_device_history.write_history.iter())
+                .fold(0.0, |max, value| max.max(*value));
+            let (upper_bound, upper_bound_label) = byte_rate_axis(max_rate);
+
+            draw_scaled_ui_graph(
+                &rect,
+                ui,
+                &[
+                    (&block_device_history.read_history, READ_LINE_COLOR),
+                    (&block_device_history.write_history, WRITE_LINE_COLOR),
+                ],
+                upper_bound,
+                &upper_bound_label,
+            );
+        });
+    });
+}
@@ -0,0 +1,39 @@
+//! Export monitor module.
+//!
+//! This module defines the ExportMonitor struct for tracking metrics exports.
+
+use std::path::PathBuf;
+
+use crate::app::{cpu_monitor::CpuMonitor, menu_view::export_cpu_history};
+
+/// A sub-structure of the AppMonitor structure.
+/// Stores where to export the metrics history when the application closes, and
+/// the outcome of the latest export made from the menu.
+pub struct ExportMonitor {
+    pub exit_export_path: Option<PathBuf>,
+    pub last_export_message: Option<String>,
+}
+
+impl ExportMonitor {
+    /// Constructor for the ExportMonitor structure.
+    ///
+    /// * Returns
+    /// ExportMonitor structure that doesn't export on exit
+    pub fn new() -> ExportMonitor {
+        ExportMonitor {
+            exit_export_path: None,
+            last_export_message: None,
+        }
+    }
+
+    /// Exports the metrics history to the path given by the --export option, if any,
+    /// and prints the outcome.
+    ///
+    /// * Parameters
+    /// `cpu_monitor` Reference to the cpu monitor holding the history
+    pub fn export_on_exit(&self, cpu_monitor: &CpuMonitor) {
+        if let Some(exit_export_path) = &self.exit_export_path {
+            println!("{}", export_cpu_history(cpu_monitor, exit_export_path));
+        }
+    }
+}
@@ -0,0 +1,115 @@
+//! Headless module.
+//!
+//! This module runs the monitors without a GUI, printing a summary of what they
+//! see at a fixed interval.
+
+use std::sync::mpsc::RecvTimeoutError;
+use std::time::{Duration, Instant};
+
+use crate::{
+    app::{app_monitor::AppMonitor, update::receive_snapshots},
+    channel::Channel,
+    config::settings::settings_monitoring,
+    data::{
+        format::{format_bytes, format_timestamp},
+        time::unix_time_milliseconds,
+    },
+};
+
+/// Receives snapshots until the duration has passed or Ctrl-C is pressed,
+/// printing a summary every interval.
+///
+/// The metrics history is exported on the way out when --export was given, and
+/// recordings and the metrics endpoint keep being fed by the channels as usual.
+///
+/// * Parameters
+/// `app_monitor` The app monitor to drive
+/// `duration` How long to run for, or None to run until Ctrl-C
+/// `summary_interval` How often to print a summary
+pub fn run_headless(
+    mut app_monitor: AppMonitor,
+    duration: Option<Duration>,
+    summary_interval: Duration,
+) {
+    // the stop sender is kept alive here, so the channel stays open even when the
+    // Ctrl-C handler couldn't be installed
+    let (stop_sender, stop_receiver) = Channel::<()>::new().split();
+    let handler_stop_sender = stop_sender.clone();
+    if let Err(e) = ctrlc::set_handler(move || {
+        let _ = handler_stop_sender.send(());
+    }) {
+        eprintln!("Error: could not handle Ctrl-C: {}", e);
+    }
+
+    let started: Instant = Instant::now();
+    let mut last_summary: Instant = started;
+    loop {
+        match stop_receiver.recv_timeout(Duration::from_millis(
+            settings_monitoring().refresh_milliseconds,
+        )) {
+            Ok(()) | Err(RecvTimeoutError::Disconnected) => break,
+            Err(RecvTimeoutError::Timeout) => {}
+        }
+
+        receive_snapshots(&mut app_monitor);
+        if last_summary.elapsed() >= summary_interval {
+            println!("{}", format_headless_summary(&app_monitor));
+            last_summary = Instant::now();
+        }
+
+        if duration.is_some_and(|duration| started.elapsed() >= duration) {
+            break;
+        }
+    }
+    drop(stop_sender);
+
+    app_monitor
+        .export_monitor
+        .export_on_exit(&app_monitor.cpu_monitor);
+}
+
+/// Formats one line summarising the latest CPU, process, memory and load data.
+///
+/// Memory and load are left out until their first snapshot has arrived.
+///
+/// * Parameters
+/// `app_monitor` Reference to the app monitor for data access
+///
+/// * Returns
+/// The summary, starting with the current UTC time
+fn format_headless_summary(app_monitor: &AppMonitor) -> String {
+    let cpu_monitor = &app_monitor.cpu_monitor;
+    let mut summary: String = format!(
+        "{} | CPU {:>5.1}% (EMA {:>5.1}%, average {:>5.1}%) | Processes {}",
+        format_timestamp(unix_time_milliseconds() / 1000),
+        cpu_monitor
+            .overall_cpu_history
+            .back()
+            .copied()
+            .unwrap_or(0.0),
+        cpu_monitor.previous_ema.unwrap_or(0.0),
+        cpu_monitor.average_cpu_usage,
+        app_monitor.process_monitor.processes.len()
+    );
+
+    let memory_snapshot = &app_monitor.memory_monitor.latest_snapshot;
+    if memory_snapshot.total_memory > 0 {
+        summary.push_str(&format!(
+            " | Memory {} / {}",
+            format_bytes(memory_snapshot.used_memory),
+            format_bytes(memory_snapshot.total_memory)
+        ));
+    }
+
+    if !app_monitor.load_monitor.load_average_history.is_empty() {
+        let load_snapshot = &app_monitor.load_monitor.latest_snapshot;
+        summary.push_str(&format!(
+            " | Load {:.2} {:.2} {:.2}",
+            load_snapshot.load_average_one,
+            load_snapshot.load_average_five,
+            load_snapshot.load_average_fifteen
+        ));
+    }
+
+    summary
+}
@@ -0,0 +1,69 @@
+//! Load monitor module.
+//!
+//! This module defines the LoadMonitor struct for tracking load averages and
+//! resource pressure.
+
+use std::collections::VecDeque;
+
+use crate::{
+    data::history::history_add_point,
+    snapshots::{load_snapshot_struct::LoadSnapshot, pressure_record_struct::PressureRecord},
+};
+
+/// A sub-structure of the AppMonitor structure.
+/// Stores the latest load snapshot, the history of the 1 minute load average and
+/// the history of the 10 second "some" pressure of each resource.
+pub struct LoadMonitor {
+    pub latest_snapshot: LoadSnapshot,
+    pub load_average_history: VecDeque<f32>,
+    pub cpu_pressure_history: VecDeque<f32>,
+    pub memory_pressure_history: VecDeque<f32>,
+    pub io_pressure_history: VecDeque<f32>,
+}
+
+impl LoadMonitor {
+    /// Constructor for the LoadMonitor structure.
+    ///
+    /// * Returns
+    /// LoadMonitor structure with empty histories
+    pub fn new() -> LoadMonitor {
+        LoadMonitor {
+            latest_snapshot: LoadSnapshot::new(),
+            load_average_history: VecDeque::new(),
+            cpu_pressure_history: VecDeque::new(),
+            memory_pressure_history: VecDeque::new(),
+            io_pressure_history: VecDeque::new(),
+        }
+    }
+
+    /// Takes in a LoadSnapshot struct and updates the fields in LoadMonitor.
+    ///
+    /// * Parameters
+    /// `load_snapshot` LoadSnapshot structure
+    pub fn load_monitor_apply_load_snapshot(&mut self, load_snapshot: LoadSnapshot) {
+        history_add_point(
+            &mut self.load_average_history,
+            load_snapshot.load_average_one as f32,
+        );
+        pressure_history_add_point(&mut self.cpu_pressure_history, &load_snapshot.cpu_pressure);
+        pressure_history_add_point(
+            &mut self.memory_pressure_history,
+            &load_snapshot.memory_pressure,
+        );
+        pressure_history_add_point(&mut self.io_pressure_history, &load_snapshot.io_pressure);
+        self.latest_snapshot = load_snapshot;
+    }
+}
+
+/// Adds the 10 second "some" pressure of a resource to its history.
+///
+/// Nothing is added when the pressure isn't reported, so the history stays empty.
+///
+/// * Parameters
+/// `history` The history to add to
+/// `pressure` The pressure on the resource, if reported
+fn pressure_history_add_point(history: &mut VecDeque<f32>, pressure: &Option<PressureRecord>) {
+    if let Some(pressure) = pressure {
+        history_add_point(history, pressure.some.avg10);
+    }
+}
@@ -0,0 +1,106 @@
+//! Load view module.
+//!
+//! This module renders the load averages and resource pressure in the header.
+
+use std::collections::VecDeque;
+
+use crate::{
+    app::load_monitor::LoadMonitor,
+    config::{
+        layout::{SPARKLINE_HEIGHT_PX, SPARKLINE_WIDTH_PX},
+        style::{LOAD_LINE_COLOR, PRESSURE_LINE_COLOR},
+    },
+    graph::{draw::draw_sparkline, geometry::nice_upper_bound},
+    snapshots::pressure_record_struct::PressureRecord,
+};
+
+use eframe::egui::{Color32, Sense, Ui, vec2};
+
+/// Renders the load averages and the pressure on each resource, each followed by
+/// a sparkline of its recent history.
+///
+/// The pressure shown is the share of the last 10 seconds in which at least one
+/// task was stalled on the resource, the other averages are shown on hover.
+///
+/// * Parameters
+/// `ui` The UI to draw in
+/// `load_monitor` Reference to the load monitor for data access
+pub fn render_load_row(ui: &mut Ui, load_monitor: &LoadMonitor) {
+    let load_snapshot = &load_monitor.latest_snapshot;
+
+    ui.horizontal(|ui| {
+        ui.label(format!(
+            "Load: {:.2} {:.2} {:.2}",
+            load_snapshot.load_average_one,
+            load_snapshot.load_average_five,
+            load_snapshot.load_average_fifteen
+        ))
+        .on_hover_text("1, 5 and 15 minute load averages");
+        render_sparkline(ui, &load_monitor.load_average_history, LOAD_LINE_COLOR);
+
+        for (resource, pressure, history) in [
+            (
+                "CPU",
+                &load_snapshot.cpu_pressure,
+                &load_monitor.cpu_pressure_history,
+            ),
+            (
+                "Memory",
+                &load_snapshot.memory_pressure,
+                &load_monitor.memory_pressure_history,
+            ),
+            (
+                "IO",
+                &load_snapshot.io_pressure,
+                &load_monitor.io_pressure_history,
+            ),
+        ] {
+            let Some(pressure) = pressure else {
+                continue;
+            };
+            ui.add_space(20.0);
+            ui.label(format!(
+                "{} pressure: {:.1}%",
+                resource, pressure.some.avg10
+            ))
+            .on_hover_text(describe_pressure(pressure));
+            render_sparkline(ui, history, PRESSURE_LINE_COLOR);
+        }
+    });
+}
+
+/// Renders a sparkline scaled to the largest value in its history.
+///
+/// * Parameters
+/// `ui` The UI to draw in
+/// `history` The history to plot
+/// `color` The colour of the line
+fn render_sparkline(ui: &mut Ui, history: &VecDeque<f32>, color: Color32) {
+    let (rect, _) = ui.allocate_exact_size(
+        vec2(SPARKLINE_WIDTH_PX, SPARKLINE_HEIGHT_PX),
+        Sense::hover(),
+    );
+    let max_value: f32 = history.iter().fold(0.0, |max, value| max.max(*value));
+    draw_sparkline(&rect, ui, history, nice_upper_bound(max_value), color);
+}
+
+/// Describes every average of a resource's pressure.
+///
+/// * Parameters
+/// `pressure` The pressure on the resource
+///
+/// * Returns
+/// One line for "some" and, where reported, one for "full"
+fn describe_pressure(pressure: &PressureRecord) -> String {
+    let mut description: String = format!(
+        "some: {:.2}% / {:.2}% / {:.2}% (10s / 60s / 300s)",
+        pressure.some.avg10, pressure.some.avg60, pressure.some.avg300
+    );
+    if let Some(full) = &pressure.full {
+        description.push_str(&format!(
+            "\nfull: {:.2}% / {:.2}% / {:.2}% (10s / 60s / 300s)",
+            full.avg10, full.avg60, full.avg300
+        ));
+    }
+    description
+}
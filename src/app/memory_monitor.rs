@@ -0,0 +1,63 @@
+//! Memory monitor module.
+//!
+//! This module defines the MemoryMonitor struct for tracking RAM and swap usage.
+
+use std::collections::VecDeque;
+
+use crate::{
+    config::settings::settings_monitoring, data::history::history_add_point,
+    snapshots::memory_snapshot_struct::MemorySnapshot,
+};
+
+/// A sub-structure of the AppMonitor structure.
+/// Stores the latest memory snapshot and the history of RAM and swap usage percentages.
+pub struct MemoryMonitor {
+    pub latest_snapshot: MemorySnapshot,
+    pub memory_usage_history: VecDeque<f32>,
+    pub swap_usage_history: VecDeque<f32>,
+}
+
+impl MemoryMonitor {
+    /// Constructor for the MemoryMonitor structure.
+    ///
+    /// * Returns
+    /// MemoryMonitor structure with empty histories
+    pub fn new() -> MemoryMonitor {
+        MemoryMonitor {
+            latest_snapshot: MemorySnapshot::new(),
+            memory_usage_history: VecDeque::with_capacity(settings_monitoring().history_points),
+            swap_usage_history: VecDeque::with_capacity(settings_monitoring().history_points),
+        }
+    }
+
+    /// Takes in a MemorySnapshot struct and updates the fields in MemoryMonitor.
+    ///
+    /// * Parameters
+    /// `memory_snapshot` MemorySnapshot structure
+    pub fn memory_monitor_apply_memory_snapshot(&mut self, memory_snapshot: MemorySnapshot) {
+        history_add_point(
+            &mut self.memory_usage_history,
+            percentage(memory_snapshot.used_memory, memory_snapshot.total_memory),
+        );
+        history_add_point(
+            &mut self.swap_usage_history,
+            percentage(memory_snapshot.used_swap, memory_snapshot.total_swap),
+        );
+        self.latest_snapshot = memory_snapshot;
+    }
+}
+
+/// Calculates what percentage of a total is used.
+///
+/// * Parameters
+/// `used` The used amount
+/// `total` The total amount
+///
+/// * Returns
+/// The percentage used, or 0 if the total is 0 (e.g. when there is no swap)
+fn percentage(used: u64, total: u64) -> f32 {
+    match total {
+        0 => 0.0,
+        _ => (used as f64 / total as f64 * 100.0) as f32,
+    }
+}
@@ -0,0 +1,111 @@
+//! Memory view module.
+//!
+//! This module renders the RAM and swap usage rows.
+
+use std::collections::VecDeque;
+
+use crate::{
+    app::memory_monitor::MemoryMonitor,
+    config::{
+        layout::{
+            CELL_HEIGHT_PX, LEFT_CELL_WIDTH_PX, PROGRESS_BAR_HEIGHT_PX, PROGRESS_BAR_ROUNDING_PX,
+            PROGRESS_BAR_SPACING_PX, PROGRESS_BAR_WIDTH_PX, TEXT_SPACING_PX,
+        },
+        style::HALF_OPACITY,
+    },
+    data::format::format_bytes,
+    graph::{
+        draw::{build_progress_bar, draw_ui_graph},
+        style::get_color,
+    },
+};
+
+use eframe::egui::{Color32, ProgressBar, Response, Sense, Ui, UiBuilder, Vec2, vec2};
+
+/// Renders the memory panel.
+///
+/// Draws one row for RAM and one for swap, each with a progress bar and a rolling graph.
+///
+/// * Parameters
+/// `ui` The UI to draw in
+/// `memory_monitor` Reference to the memory monitor for data access
+pub fn render_memory_panel(ui: &mut Ui, memory_monitor: &MemoryMonitor) {
+    let snapshot = &memory_monitor.latest_snapshot;
+
+    let mut memory_details: String = format!(
+        "{} / {} used, {} available",
+        format_bytes(snapshot.used_memory),
+        format_bytes(snapshot.total_memory),
+        format_bytes(snapshot.available_memory)
+    );
+    if let Some(cached_memory) = snapshot.cached_memory {
+        memory_details.push_str(&format!(", {} cached", format_bytes(cached_memory)));
+    }
+    render_memory_row(
+        ui,
+        "RAM ",
+        &memory_details,
+        &memory_monitor.memory_usage_history,
+    );
+
+    ui.add_space(TEXT_SPACING_PX);
+
+    let swap_details: String = format!(
+        "{} / {} used",
+        format_bytes(snapshot.used_swap),
+        format_bytes(snapshot.total_swap)
+    );
+    render_memory_row(
+        ui,
+        "Swap",
+        &swap_details,
+        &memory_monitor.swap_usage_history,
+    );
+}
+
+/// Renders a single memory row.
+///
+/// * Parameters
+/// `ui` The UI to draw in
+/// `label` The name of the row
+/// `details` The absolute amounts shown below the row
+/// `history` The usage percentage history
+fn render_memory_row(ui: &mut Ui, label: &str, details: &str, history: &VecDeque<f32>) {
+    ui.group(|ui| {
+        ui.horizontal(|ui| {
+            let left_cell: Vec2 = vec2(LEFT_CELL_WIDTH_PX, CELL_HEIGHT_PX);
+            let (rect, _) = ui.allocate_exact_size(left_cell, Sense::hover());
+
+            ui.scope_builder(UiBuilder::new().max_rect(rect), |ui| {
+                ui.label(label);
+                ui.add_space(TEXT_SPACING_PX);
+
+                if let Some(usage) = history.back() {
+                    // value formatted to one decimal place
+                    ui.monospace(format!("{:>5.1}%", usage));
+                    ui.add_space(PROGRESS_BAR_SPACING_PX);
+
+                    let color: Color32 = get_color(*usage, HALF_OPACITY);
+
+                    let progress_bar: ProgressBar = build_progress_bar(
+                        *usage,
+                        PROGRESS_BAR_WIDTH_PX,
+                        PROGRESS_BAR_HEIGHT_PX,
+                        PROGRESS_BAR_ROUNDING_PX,
+                        color,
+                    );
+
+                    let _response: Response = ui.add(progress_bar);
+
+                    ui.add_space(TEXT_SPACING_PX);
+                }
+            });
+
+            let right_cell: Vec2 = vec2(ui.available_width(), CELL_HEIGHT_PX);
+            let (rect, _) = ui.allocate_exact_size(right_cell, Sense::hover());
+
+            draw_ui_graph(&rect, ui, history, None, 0..history.len());
+        });
+        ui.small(details);
+    });
+}
@@ -0,0 +1,75 @@
+//! Menu view module.
+//!
+//! This module renders the menu bar at the top of the window.
+
+use std::path::{Path, PathBuf};
+
+use crate::{
+    app::{
+        cpu_monitor::CpuMonitor, export_monitor::ExportMonitor, settings_monitor::SettingsMonitor,
+    },
+    export::{
+        export_format_enum::ExportFormat,
+        exporter::{default_export_path, write_metrics_export},
+    },
+};
+
+use eframe::egui::{MenuBar, Ui};
+
+/// Renders the menu bar, with the actions exporting the metrics history, the outcome
+/// of the latest export and the button opening the settings window.
+///
+/// * Parameters
+/// `ui` The UI to draw in
+/// `cpu_monitor` Reference to the cpu monitor holding the history
+/// `export_monitor` Mutable reference to the export monitor
+/// `settings_monitor` Mutable reference to the settings monitor
+pub fn render_menu_bar(
+    ui: &mut Ui,
+    cpu_monitor: &CpuMonitor,
+    export_monitor: &mut ExportMonitor,
+    settings_monitor: &mut SettingsMonitor,
+) {
+    MenuBar::new().ui(ui, |ui| {
+        ui.menu_button("File", |ui| {
+            for (text, export_format) in [
+                ("Export history as CSV", ExportFormat::Csv),
+                ("Export history as JSON", ExportFormat::Json),
+            ] {
+                if ui.button(text).clicked() {
+                    let path: PathBuf = default_export_path(export_format);
+                    export_monitor.last_export_message =
+                        Some(export_cpu_history(cpu_monitor, &path));
+                    ui.close();
+                }
+            }
+        });
+        if ui.button("Settings").clicked() {
+            settings_monitor.window_open = true;
+        }
+
+        if let Some(last_export_message) = &export_monitor.last_export_message {
+            ui.add_space(20.0);
+            ui.label(last_export_message);
+        }
+    });
+}
+
+/// Exports the metrics history of the cpu monitor to a file.
+///
+/// * Parameters
+/// `cpu_monitor` Reference to the cpu monitor holding the history
+/// `path` The file to write, CSV unless it ends in ".json"
+///
+/// * Returns
+/// A message describing the outcome
+pub fn export_cpu_history(cpu_monitor: &CpuMonitor, path: &Path) -> String {
+    match write_metrics_export(&cpu_monitor.cpu_monitor_metrics_export(), path) {
+        Ok(()) => format!("Exported metrics history to {}", path.display()),
+        Err(e) => format!(
+            "Could not export metrics history to {}: {}",
+            path.display(),
+            e
+        ),
+    }
+}
@@ -3,12 +3,40 @@
 //! This module contains the core structures and logic for the CPU monitoring application.
 //! It includes components for monitoring CPU usage, system information, processes, and the GUI.
 
+pub mod alert_monitor;
+mod alert_view;
 pub mod app_monitor;
 pub mod app_receivers;
+pub mod app_senders;
 mod channels;
+mod cpu_graph_view;
 mod cpu_monitor;
+mod cpu_times_monitor;
+mod cpu_times_view;
+mod disk_monitor;
+mod disk_view;
+mod export_monitor;
+pub mod headless;
+mod load_monitor;
+mod load_view;
+mod memory_monitor;
+mod memory_view;
+mod menu_view;
+mod network_monitor;
+mod network_view;
+mod process_detail_view;
 mod process_monitor;
+mod process_view;
+mod replay_monitor;
+mod replay_view;
+mod sensor_monitor;
+mod sensor_view;
+mod settings_monitor;
+mod settings_view;
 mod state;
 mod system_monitor;
+mod top_consumers_view;
+pub mod tui;
+mod tui_view;
 mod update;
 mod view;
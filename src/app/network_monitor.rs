@@ -0,0 +1,75 @@
+//! Network monitor module.
+//!
+//! This module defines the NetworkMonitor struct for tracking per-interface traffic.
+
+use std::collections::{BTreeMap, VecDeque};
+
+use crate::{
+    data::history::history_add_point,
+    snapshots::{
+        network_interface_record_struct::NetworkInterfaceRecord,
+        network_snapshot_struct::NetworkSnapshot,
+    },
+};
+
+/// Traffic history of a single network interface.
+///
+/// Stores the latest record and the received and transmitted byte rate histories.
+pub struct InterfaceHistory {
+    pub latest_record: NetworkInterfaceRecord,
+    pub received_history: VecDeque<f32>,
+    pub transmitted_history: VecDeque<f32>,
+}
+
+/// A sub-structure of the AppMonitor structure.
+/// Stores the traffic history of every network interface, ordered by name.
+pub struct NetworkMonitor {
+    pub interfaces: BTreeMap<String, InterfaceHistory>,
+}
+
+impl NetworkMonitor {
+    /// Constructor for the NetworkMonitor structure.
+    ///
+    /// * Returns
+    /// NetworkMonitor structure with no interfaces
+    pub fn new() -> NetworkMonitor {
+        NetworkMonitor {
+            interfaces: BTreeMap::new(),
+        }
+    }
+
+    /// Takes in a NetworkSnapshot struct and updates the fields in NetworkMonitor.
+    ///
+    /// Interfaces missing from the snapshot are removed along with their history.
+    ///
+    /// * Parameters
+    /// `network_snapshot` NetworkSnapshot structure
+    pub fn network_monitor_apply_network_snapshot(&mut self, network_snapshot: NetworkSnapshot) {
+        self.interfaces.retain(|name, _| {
+            network_snapshot
+                .interfaces
+                .iter()
+                .any(|record| &record.name == name)
+        });
+
+        for record in network_snapshot.interfaces {
+            let interface_history: &mut InterfaceHistory = self
+                .interfaces
+                .entry(record.name.clone())
+                .or_insert_with(|| InterfaceHistory {
+                    latest_record: record.clone(),
+                    received_history: VecDeque::new(),
+                    transmitted_history: VecDeque::new(),
+                });
+            history_add_point(
+                &mut interface_history.received_history,
+                record.received_bytes_per_second as f32,
+            );
+            history_add_point(
+                &mut interface_history.transmitted_history,
+                record.transmitted_bytes_per_second as f32,
+            );
+            interface_history.latest_record = record;
+        }
+    }
+}
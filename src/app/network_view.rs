@@ -0,0 +1,95 @@
+//! Network view module.
+//!
+//! This module renders one traffic row per network interface.
+
+use crate::{
+    app::network_monitor::{InterfaceHistory, NetworkMonitor},
+    config::{
+        layout::{CELL_HEIGHT_PX, DEVICE_LEFT_CELL_WIDTH_PX, TEXT_SPACING_PX},
+        style::{RECEIVED_LINE_COLOR, TRANSMITTED_LINE_COLOR},
+    },
+    data::format::format_byte_rate,
+    graph::{draw::draw_scaled_ui_graph, geometry::byte_rate_axis},
+};
+
+use eframe::egui::{Sense, Ui, UiBuilder, Vec2, vec2};
+
+/// Renders the network panel.
+///
+/// Draws a row per interface with its current rates and a rolling graph of
+/// received and transmitted bytes per second.
+///
+/// * Parameters
+/// `ui` The UI to draw in
+/// `network_monitor` Reference to the network monitor for data access
+pub fn render_network_panel(ui: &mut Ui, network_monitor: &NetworkMonitor) {
+    for interface_history in network_monitor.interfaces.values() {
+        render_interface_row(ui, interface_history);
+        ui.add_space(TEXT_SPACING_PX);
+    }
+}
+
+/// Renders a single network interface row.
+///
+/// * Parameters
+/// `ui` The UI to draw in
+/// `interface_history` The traffic history of the interface
+fn render_interface_row(ui: &mut Ui, interface_history: &InterfaceHistory) {
+    let record = &interface_history.latest_record;
+
+    ui.group(|ui| {
+        ui.horizontal(|ui| {
+            let left_cell: Vec2 = vec2(DEVICE_LEFT_CELL_WIDTH_PX, CELL_HEIGHT_PX);
+            let (rect, _) = ui.allocate_exact_size(left_cell, Sense::hover());
+
+            ui.scope_builder(UiBuilder::new().max_rect(rect), |ui| {
+                ui.label(&record.name);
+                ui.colored_label(
+                    RECEIVED_LINE_COLOR,
+                    format!(
+                        "down {} ({:.0} pkt/s)",
+                        format_byte_rate(record.received_bytes_per_second),
+                        record.received_packets_per_second
+                    ),
+                );
+                ui.colored_label(
+                    TRANSMITTED_LINE_COLOR,
+                    format!(
+                        "up   {} ({:.0} pkt/s)",
+                        format_byte_rate(record.transmitted_bytes_per_second),
+                        record.transmitted_packets_per_second
+                    ),
+                );
+                ui.small(format!(
+                    "errors: {} in, {} out",
+                    record.total_receive_errors, record.total_transmit_errors
+                ));
+            });
+
+            let right_cell: Vec2 = vec2(ui.available_width(), CELL_HEIGHT_PX);
+            let (rect, _) = ui.allocate_exact_size(right_cell, Sense::hover());
+
+            // both lines share one axis so their heights can be compared
+            let max_rate: f32 = interface_history
+                .received_history
+                .iter()
+                .chain(interface_history.transmitted_history.iter())
+                .fold(0.0, |max, value| max.max(*value));
+            let (upper_bound, upper_bound_label) = byte_rate_axis(max_rate);
+
+            draw_scaled_ui_graph(
+                &rect,
+                ui,
+                &[
+                    (&interface_history.received_history, RECEIVED_LINE_COLOR),
+                    (
+                        &interface_history.transmitted_history,
+                        TRANSMITTED_LINE_COLOR,
+                    ),
+                ],
+                upper_bound,
+                &upper_bound_label,
+            );
+        });
+    });
+}
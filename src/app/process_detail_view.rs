@@ -0,0 +1,309 @@
+//! Process detail view module.
+//!
+//! This module renders the detail window of a single process, with a rolling graph
+//! of each resource it uses and its environment variables.
+
+use std::collections::VecDeque;
+
+use crate::{
+    app::process_monitor::ProcessMonitor,
+    config::{
+        layout::{
+            CELL_HEIGHT_PX, DEVICE_LEFT_CELL_WIDTH_PX, PROCESS_COMMAND_MAX_CHARS,
+            PROCESS_DETAIL_WIDTH_PX, PROCESS_ENVIRONMENT_HEIGHT_PX, TEXT_SPACING_PX,
+        },
+        style::{PROCESS_LINE_COLOR, READ_LINE_COLOR, WRITE_LINE_COLOR},
+    },
+    data::{
+        format::{format_byte_rate, format_bytes, format_core_list, format_timestamp},
+        process_history_struct::ProcessHistory,
+        process_key_struct::ProcessKey,
+    },
+    graph::{
+        draw::draw_scaled_ui_graph,
+        geometry::{byte_axis, byte_rate_axis, nice_upper_bound},
+    },
+    snapshots::process_record_struct::ProcessRecord,
+};
+
+use eframe::egui::{
+    CollapsingHeader, Color32, Context, Id, ScrollArea, Sense, Ui, UiBuilder, Vec2, Window, vec2,
+};
+
+/// Renders the detail window of the process opened from the process table.
+///
+/// The environment is read by the processes worker once when the window opens, and
+/// again when the user asks for it. It isn't recorded, so it can't be shown during a
+/// replay.
+///
+/// * Parameters
+/// `ctx` The egui context
+/// `process_monitor` Mutable reference to the process monitor for data access
+pub fn render_process_detail_window(ctx: &Context, process_monitor: &mut ProcessMonitor) {
+    let Some(process_key) = process_monitor.detail_process else {
+        return;
+    };
+
+    let mut open: bool = true;
+    let mut refresh_environment: bool = false;
+    // the id is fixed so opening another process reuses the window where it was
+    Window::new(format!("Process {}", process_key.pid))
+        .id(Id::new("process_detail_window"))
+        .open(&mut open)
+        .default_width(PROCESS_DETAIL_WIDTH_PX)
+        .show(ctx, |ui| {
+            let process: Option<&ProcessRecord> = process_monitor.find_process_by_key(process_key);
+            let process_history: Option<&ProcessHistory> =
+                process_monitor.process_histories.get(&process_key);
+            let (Some(process), Some(process_history)) = (process, process_history) else {
+                ui.label(format!("Process {} has exited.", process_key.pid));
+                return;
+            };
+
+            render_process_summary(ui, process);
+            ui.add_space(TEXT_SPACING_PX);
+            render_resource_rows(ui, process, process_history);
+            ui.add_space(TEXT_SPACING_PX);
+            refresh_environment =
+                render_environment(ui, process_key, &process_monitor.detail_environment);
+        });
+
+    if !open {
+        process_monitor.close_process_detail();
+    } else if refresh_environment {
+        // asked for again at the next frame
+        process_monitor.refresh_process_environment();
+    }
+}
+
+/// Renders the name, owner, state, start time, scheduling and command line of a
+/// process.
+///
+/// * Parameters
+/// `ui` The UI to draw in
+/// `process` The latest record of the process
+fn render_process_summary(ui: &mut Ui, process: &ProcessRecord) {
+    ui.heading(&process.name);
+    ui.label(format!(
+        "User {}, {}, started {}",
+        process.user,
+        process.status,
+        format_timestamp(process.start_time)
+    ));
+    if let (Some(nice), Some(affinity)) = (process.nice, &process.affinity) {
+        ui.label(format!(
+            "Nice {}, runs on cores {}",
+            nice,
+            format_core_list(affinity)
+        ));
+    }
+
+    // long command lines are shortened, the full command is shown on hover
+    let command: String = match process
+        .command
+        .char_indices()
+        .nth(PROCESS_COMMAND_MAX_CHARS)
+    {
+        Some((index, _)) => format!("{}...", &process.command[..index]),
+        None => process.command.clone(),
+    };
+    ui.monospace(command).on_hover_text(&process.command);
+}
+
+/// Renders a row per resource the process uses, with its current value and its
+/// history.
+///
+/// * Parameters
+/// `ui` The UI to draw in
+/// `process` The latest record of the process
+/// `process_history` The histories of the process
+fn render_resource_rows(ui: &mut Ui, process: &ProcessRecord, process_history: &ProcessHistory) {
+    // a process using several cores goes above 100%
+    let max_cpu_usage: f32 = history_max(&[&process_history.cpu_usage]);
+    let cpu_upper_bound: f32 = nice_upper_bound(max_cpu_usage).max(100.0);
+    render_resource_row(
+        ui,
+        "CPU",
+        &[(format!("{:.1}%", process.cpu_usage), PROCESS_LINE_COLOR)],
+        &[(&process_history.cpu_usage, PROCESS_LINE_COLOR)],
+        (cpu_upper_bound, format!("{}%", cpu_upper_bound)),
+    );
+
+    render_resource_row(
+        ui,
+        "Resident memory",
+        &[(format_bytes(process.memory), PROCESS_LINE_COLOR)],
+        &[(&process_history.resident_memory, PROCESS_LINE_COLOR)],
+        byte_axis(history_max(&[&process_history.resident_memory])),
+    );
+
+    render_resource_row(
+        ui,
+        "Virtual memory",
+        &[(format_bytes(process.virtual_memory), PROCESS_LINE_COLOR)],
+        &[(&process_history.virtual_memory, PROCESS_LINE_COLOR)],
+        byte_axis(history_max(&[&process_history.virtual_memory])),
+    );
+
+    let max_thread_count: f32 = nice_upper_bound(history_max(&[&process_history.thread_count]));
+    render_resource_row(
+        ui,
+        "Threads",
+        &[(format_count(process.thread_count), PROCESS_LINE_COLOR)],
+        &[(&process_history.thread_count, PROCESS_LINE_COLOR)],
+        (max_thread_count, format!("{} threads", max_thread_count)),
+    );
+
+    let max_open_file_count: f32 =
+        nice_upper_bound(history_max(&[&process_history.open_file_count]));
+    render_resource_row(
+        ui,
+        "Open files",
+        &[(format_count(process.open_file_count), PROCESS_LINE_COLOR)],
+        &[(&process_history.open_file_count, PROCESS_LINE_COLOR)],
+        (
+            max_open_file_count,
+            format!("{} files", max_open_file_count),
+        ),
+    );
+
+    // both lines share one axis so their heights can be compared
+    render_resource_row(
+        ui,
+        "Disk",
+        &[
+            (
+                format!(
+                    "read  {}",
+                    format_byte_rate(process.disk_read_bytes_per_second)
+                ),
+                READ_LINE_COLOR,
+            ),
+            (
+                format!(
+                    "write {}",
+                    format_byte_rate(process.disk_written_bytes_per_second)
+                ),
+                WRITE_LINE_COLOR,
+            ),
+        ],
+        &[
+            (&process_history.disk_read_bytes_per_second, READ_LINE_COLOR),
+            (
+                &process_history.disk_written_bytes_per_second,
+                WRITE_LINE_COLOR,
+            ),
+        ],
+        byte_rate_axis(history_max(&[
+            &process_history.disk_read_bytes_per_second,
+            &process_history.disk_written_bytes_per_second,
+        ])),
+    );
+}
+
+/// Renders a single resource row.
+///
+/// * Parameters
+/// `ui` The UI to draw in
+/// `label` The name of the resource
+/// `values` The current values, each with its text colour
+/// `series` The histories to plot, each with its line colour
+/// `axis` The value drawn at the top of the graph, and its label
+fn render_resource_row(
+    ui: &mut Ui,
+    label: &str,
+    values: &[(String, Color32)],
+    series: &[(&VecDeque<f32>, Color32)],
+    axis: (f32, String),
+) {
+    ui.horizontal(|ui| {
+        let left_cell: Vec2 = vec2(DEVICE_LEFT_CELL_WIDTH_PX, CELL_HEIGHT_PX);
+        let (rect, _) = ui.allocate_exact_size(left_cell, Sense::hover());
+
+        ui.scope_builder(UiBuilder::new().max_rect(rect), |ui| {
+            ui.label(label);
+            for (value, color) in values {
+                ui.colored_label(*color, value);
+            }
+        });
+
+        let right_cell: Vec2 = vec2(ui.available_width(), CELL_HEIGHT_PX);
+        let (rect, _) = ui.allocate_exact_size(right_cell, Sense::hover());
+        let (upper_bound, upper_bound_label) = axis;
+        draw_scaled_ui_graph(&rect, ui, series, upper_bound, &upper_bound_label);
+    });
+}
+
+/// Renders the environment variables of a process, with a button to read them again.
+///
+/// * Parameters
+/// `ui` The UI to draw in
+/// `process_key` The pid and start time of the process
+/// `environment` The variables read, or why they couldn't be
+///
+/// * Returns
+/// true if the user asked to read the variables again
+fn render_environment(
+    ui: &mut Ui,
+    process_key: ProcessKey,
+    environment: &Option<Result<Vec<String>, String>>,
+) -> bool {
+    let mut refresh: bool = false;
+    CollapsingHeader::new("Environment")
+        .id_salt((
+            "process_environment",
+            process_key.pid,
+            process_key.start_time,
+        ))
+        .default_open(true)
+        .show(ui, |ui| {
+            refresh = ui.small_button("Refresh").clicked();
+            match environment {
+                Some(Ok(variables)) => {
+                    ScrollArea::vertical()
+                        .max_height(PROCESS_ENVIRONMENT_HEIGHT_PX)
+                        .auto_shrink([false, true])
+                        .show(ui, |ui| {
+                            for variable in variables {
+                                ui.monospace(variable);
+                            }
+                        });
+                }
+                Some(Err(e)) => {
+                    ui.weak(format!("Not available: {}", e));
+                }
+                None => {
+                    ui.weak("Reading...");
+                }
+            }
+        });
+    refresh
+}
+
+/// Finds the largest value in a set of histories.
+///
+/// * Parameters
+/// `histories` The histories to search
+///
+/// * Returns
+/// The largest value, 0 if every history is empty
+fn history_max(histories: &[&VecDeque<f32>]) -> f32 {
+    histories
+        .iter()
+        .flat_map(|history| history.iter())
+        .fold(0.0, |max, value| max.max(*value))
+}
+
+/// Formats a count that can't always be read.
+///
+/// * Parameters
+/// `count` The count, None if it couldn't be read
+///
+/// * Returns
+/// The count, or "N/A"
+fn format_count(count: Option<usize>) -> String {
+    match count {
+        Some(count) => count.to_string(),
+        None => String::from("N/A"),
+    }
+}
@@ -2,34 +2,570 @@
 //!
 //! This module defines the ProcessMonitor struct for tracking system processes.
 
-use crate::ProcessesSnapshot;
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, HashMap, HashSet, hash_map::Entry};
+
+use sysinfo::Signal;
+
+use crate::{
+    commands::{
+        process_command_enum::ProcessCommand, process_command_outcome_struct::ProcessCommandOutcome,
+    },
+    config::settings::{settings, settings_monitoring},
+    data::{
+        process_history_struct::ProcessHistory,
+        process_key_struct::ProcessKey,
+        process_tree::{ProcessTreeRow, build_process_tree},
+        top_consumer_struct::TopConsumer,
+    },
+    filter::{filtered_processes_struct::FilteredProcesses, process_filter_struct::ProcessFilter},
+    snapshots::{
+        process_environment_struct::ProcessEnvironment, process_record_struct::ProcessRecord,
+        threads_snapshot_struct::ThreadsSnapshot,
+    },
+};
+
+/// Columns of the process table.
+///
+/// Each column can be used as the sort key of the table.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ProcessColumn {
+    Pid,
+    Name,
+    User,
+    CpuUsage,
+    Memory,
+    Status,
+    StartTime,
+    Command,
+}
+
+impl ProcessColumn {
+    /// Every column in display order.
+    pub const ALL: [ProcessColumn; 8] = [
+        ProcessColumn::Pid,
+        ProcessColumn::Name,
+        ProcessColumn::User,
+        ProcessColumn::CpuUsage,
+        ProcessColumn::Memory,
+        ProcessColumn::Status,
+        ProcessColumn::StartTime,
+        ProcessColumn::Command,
+    ];
+
+    /// Returns the header label of the column.
+    ///
+    /// * Returns
+    /// The label shown in the table header
+    pub fn label(&self) -> &'static str {
+        match self {
+            ProcessColumn::Pid => "PID",
+            ProcessColumn::Name => "Name",
+            ProcessColumn::User => "User",
+            ProcessColumn::CpuUsage => "CPU %",
+            ProcessColumn::Memory => "Memory",
+            ProcessColumn::Status => "State",
+            ProcessColumn::StartTime => "Started",
+            ProcessColumn::Command => "Command",
+        }
+    }
+
+    /// Compares two process records by the value of this column.
+    ///
+    /// * Parameters
+    /// `a` The first process record
+    /// `b` The second process record
+    ///
+    /// * Returns
+    /// The ascending ordering of the two records
+    pub fn compare(&self, a: &ProcessRecord, b: &ProcessRecord) -> Ordering {
+        match self {
+            ProcessColumn::Pid => a.pid.cmp(&b.pid),
+            ProcessColumn::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+            ProcessColumn::User => a.user.cmp(&b.user),
+            ProcessColumn::CpuUsage => a.cpu_usage.total_cmp(&b.cpu_usage),
+            ProcessColumn::Memory => a.memory.cmp(&b.memory),
+            ProcessColumn::Status => a.status.cmp(&b.status),
+            ProcessColumn::StartTime => a.start_time.cmp(&b.start_time),
+            ProcessColumn::Command => a.command.cmp(&b.command),
+        }
+    }
+}
 
 /// Process monitor structure.
 ///
-/// Stores information about the number of running processes in the system.
+/// Stores the latest process records, how the process table is sorted, and the
+/// state of actions taken on the selected process.
+/// Note: a pending command is one waiting for the user to confirm it.
+/// Note: tree rows are kept up to date even when the flat table is shown.
+/// Note: processes holds every process, matching_pids the ones the filter lets
+/// through, or None when the filter is empty. The tree rows also keep the parents
+/// of matching processes so the tree stays connected.
+/// Note: only the selected process and the one in the detail window have a history,
+/// keyed by pid and start time so a reused pid starts a new one. Histories are
+/// dropped as soon as their process exits or is neither selected nor in the window.
+/// Note: the edited nice value and affinity are what the controls for the selected
+/// process show, loaded from its record once it has them after another process is
+/// selected.
+/// Note: the processes worker only reads the open files, nice value and affinity of
+/// the detail pids, the selected process and the one in the detail window.
+/// Note: the threads worker lists the threads of the watched process, which follows
+/// the selected process. The threads snapshot is dropped when it changes.
+/// Note: the detail environment is asked of the processes worker when the detail
+/// window opens or is refreshed, and is None until the answer arrives.
 pub struct ProcessMonitor {
-    pub processes: usize,
+    pub processes: Vec<ProcessRecord>,
+    pub matching_pids: Option<HashSet<u32>>,
+    pub filter_text: String,
+    pub process_filter: ProcessFilter,
+    pub filter_error: Option<String>,
+    pub sort_column: ProcessColumn,
+    pub sort_descending: bool,
+    pub tree_view: bool,
+    pub collapsed_pids: HashSet<u32>,
+    pub tree_rows: Vec<ProcessTreeRow>,
+    pub selected_pid: Option<u32>,
+    pub selected_signal: Signal,
+    pub edited_nice: i32,
+    pub edited_affinity: BTreeSet<usize>,
+    pub scheduling_loaded: bool,
+    pub pending_command: Option<ProcessCommand>,
+    pub last_command_outcome: Option<ProcessCommandOutcome>,
+    pub watched_process: Option<ProcessKey>,
+    pub threads_snapshot: Option<ThreadsSnapshot>,
+    pub process_histories: HashMap<ProcessKey, ProcessHistory>,
+    pub detail_process: Option<ProcessKey>,
+    pub detail_environment: Option<Result<Vec<String>, String>>,
+    pub detail_environment_requested: bool,
+    pub detail_pids: Vec<u32>,
 }
 
 impl ProcessMonitor {
-    /// Builds a ProcessMonitor from a processes snapshot.
+    /// Creates a new ProcessMonitor with default values.
+    ///
+    /// * Returns
+    /// A ProcessMonitor with no processes, sorted by descending CPU usage and
+    /// filtered with the filter kept in the settings
+    pub fn new() -> ProcessMonitor {
+        let filter_text: String = settings().processes.filter;
+        let (process_filter, filter_error): (ProcessFilter, Option<String>) =
+            match ProcessFilter::parse(&filter_text) {
+                Ok(process_filter) => (process_filter, None),
+                Err(e) => (ProcessFilter::default(), Some(e)),
+            };
+
+        ProcessMonitor {
+            processes: Vec::new(),
+            matching_pids: None,
+            filter_text,
+            process_filter,
+            filter_error,
+            sort_column: ProcessColumn::CpuUsage,
+            sort_descending: true,
+            tree_view: false,
+            collapsed_pids: HashSet::new(),
+            tree_rows: Vec::new(),
+            selected_pid: None,
+            selected_signal: Signal::Hangup,
+            edited_nice: 0,
+            edited_affinity: BTreeSet::new(),
+            scheduling_loaded: false,
+            pending_command: None,
+            last_command_outcome: None,
+            watched_process: None,
+            threads_snapshot: None,
+            process_histories: HashMap::new(),
+            detail_process: None,
+            detail_environment: None,
+            detail_environment_requested: false,
+            detail_pids: Vec::new(),
+        }
+    }
+
+    /// Takes in a filtered ProcessesSnapshot and replaces the stored process records.
+    ///
+    /// The new records are sorted with the current sort settings and, when they come
+    /// from a new snapshot rather than a new filter, added to the process histories.
+    /// Processes that have exited are forgotten from the collapsed set and the
+    /// histories, so a reused pid starts expanded and with no history.
+    ///
+    /// * Parameters
+    /// `filtered_processes` The snapshot containing process data, and the matching pids
+    pub fn process_monitor_apply_filtered_processes(
+        &mut self,
+        filtered_processes: FilteredProcesses,
+    ) {
+        self.processes = filtered_processes.processes_snapshot.processes;
+        self.matching_pids = filtered_processes.matching_pids;
+
+        let live_pids: HashSet<u32> = self.processes.iter().map(|process| process.pid).collect();
+        self.collapsed_pids.retain(|pid| live_pids.contains(pid));
+        if filtered_processes.fresh {
+            self.update_process_histories();
+        }
+        self.load_scheduling();
+
+        self.sort_processes();
+    }
+
+    /// Takes in a ThreadsSnapshot of the watched process, ordering its threads from
+    /// the busiest.
+    ///
+    /// Snapshots of a process watched before are ignored, they were sent before the
+    /// threads worker heard of the change.
     ///
     /// * Parameters
-    /// `processes_snapshot_struct` The snapshot containing process data
+    /// `threads_snapshot` The snapshot containing thread data
+    pub fn process_monitor_apply_threads_snapshot(
+        &mut self,
+        mut threads_snapshot: ThreadsSnapshot,
+    ) {
+        if Some(threads_snapshot.process_key) != self.watched_process {
+            return;
+        }
+        threads_snapshot.threads.sort_by(|a, b| {
+            b.cpu_usage
+                .unwrap_or(0.0)
+                .total_cmp(&a.cpu_usage.unwrap_or(0.0))
+                .then(a.tid.cmp(&b.tid))
+        });
+        self.threads_snapshot = Some(threads_snapshot);
+    }
+
+    /// Finds the process whose threads should be listed, the selected one.
     ///
     /// * Returns
-    /// A ProcessMonitor instance with the process count
-    pub fn build_from_snapshot(processes_snapshot_struct: ProcessesSnapshot) -> ProcessMonitor {
-        ProcessMonitor {
-            processes: processes_snapshot_struct.processes,
+    /// The new process to watch, or Some(None) to stop watching, when it differs
+    /// from the watched one. None when nothing changed.
+    pub fn process_monitor_update_thread_watch(&mut self) -> Option<Option<ProcessKey>> {
+        let selected_process: Option<ProcessKey> = self
+            .selected_pid
+            .and_then(|pid| self.find_process(pid))
+            .map(ProcessKey::from_record);
+        if selected_process == self.watched_process {
+            return None;
         }
+        self.watched_process = selected_process;
+        self.threads_snapshot = None;
+        Some(selected_process)
     }
 
-    /// Creates a new ProcessMonitor with default values.
+    /// Finds the processes whose open files, nice value and affinity should be read,
+    /// the selected one and the one in the detail window.
     ///
     /// * Returns
-    /// A ProcessMonitor instance with processes set to 0
-    pub fn new() -> ProcessMonitor {
-        ProcessMonitor { processes: 0 }
+    /// The new pids to read the details of when they differ from the current ones,
+    /// None when nothing changed
+    pub fn process_monitor_update_detail_watch(&mut self) -> Option<Vec<u32>> {
+        let mut detail_pids: Vec<u32> = self
+            .selected_pid
+            .into_iter()
+            .chain(self.detail_process.map(|process_key| process_key.pid))
+            .collect();
+        detail_pids.sort_unstable();
+        detail_pids.dedup();
+        if detail_pids == self.detail_pids {
+            return None;
+        }
+        self.detail_pids = detail_pids.clone();
+        Some(detail_pids)
+    }
+
+    /// Collapses the children of a process in the tree view, or expands them if
+    /// they are already collapsed.
+    ///
+    /// * Parameters
+    /// `pid` The pid of the process
+    pub fn toggle_collapsed(&mut self, pid: u32) {
+        if !self.collapsed_pids.remove(&pid) {
+            self.collapsed_pids.insert(pid);
+        }
+        self.tree_rows = self.build_filtered_tree();
+    }
+
+    /// Parses the text typed in the filter box.
+    ///
+    /// * Returns
+    /// The new filter if it parsed and differs from the one in use, for the filter
+    /// worker. A filter that doesn't parse is kept in filter_error instead.
+    pub fn process_monitor_update_filter(&mut self) -> Option<ProcessFilter> {
+        match ProcessFilter::parse(&self.filter_text) {
+            Ok(process_filter) => {
+                self.filter_error = None;
+                if process_filter == self.process_filter {
+                    return None;
+                }
+                self.process_filter = process_filter.clone();
+                Some(process_filter)
+            }
+            Err(e) => {
+                self.filter_error = Some(e);
+                None
+            }
+        }
+    }
+
+    /// Checks whether a process matches the filter.
+    ///
+    /// * Parameters
+    /// `pid` The pid of the process
+    ///
+    /// * Returns
+    /// true if the filter is empty or the process matched it
+    pub fn is_matching(&self, pid: u32) -> bool {
+        match &self.matching_pids {
+            Some(matching_pids) => matching_pids.contains(&pid),
+            None => true,
+        }
+    }
+
+    /// Returns the processes that match the filter, in sort order.
+    ///
+    /// * Returns
+    /// An iterator over the matching process records
+    pub fn matching_processes(&self) -> impl Iterator<Item = &ProcessRecord> {
+        self.processes
+            .iter()
+            .filter(|process| self.is_matching(process.pid))
+    }
+
+    /// Looks up the stored record of a process.
+    ///
+    /// * Parameters
+    /// `pid` The pid of the process
+    ///
+    /// * Returns
+    /// Some(&ProcessRecord) if the process was in the latest snapshot, None otherwise
+    pub fn find_process(&self, pid: u32) -> Option<&ProcessRecord> {
+        self.processes.iter().find(|process| process.pid == pid)
+    }
+
+    /// Selects a process to act on, and loads its nice value and affinity into the
+    /// controls.
+    ///
+    /// They are usually only read from the next snapshot on, so until then the
+    /// controls start from the defaults.
+    ///
+    /// * Parameters
+    /// `pid` The pid of the process
+    pub fn select_process(&mut self, pid: u32) {
+        if self.selected_pid == Some(pid) {
+            return;
+        }
+        self.selected_pid = Some(pid);
+        self.edited_nice = 0;
+        self.edited_affinity = BTreeSet::new();
+        self.scheduling_loaded = false;
+        self.load_scheduling();
+    }
+
+    /// Loads the nice value and affinity of the selected process into the controls,
+    /// unless they have been loaded since it was selected.
+    fn load_scheduling(&mut self) {
+        if self.scheduling_loaded {
+            return;
+        }
+        // the details of the selected process may not have been read yet
+        let (nice, affinity): (Option<i32>, Option<Vec<usize>>) =
+            match self.selected_pid.and_then(|pid| self.find_process(pid)) {
+                Some(process) if process.nice.is_some() || process.affinity.is_some() => {
+                    (process.nice, process.affinity.clone())
+                }
+                _ => return,
+            };
+        self.edited_nice = nice.unwrap_or(0);
+        self.edited_affinity = affinity.unwrap_or_default().into_iter().collect();
+        self.scheduling_loaded = true;
+    }
+
+    /// Finds the processes using the most CPU in the latest snapshot, whether or not
+    /// they match the filter.
+    ///
+    /// * Parameters
+    /// `count` The most processes to return
+    ///
+    /// * Returns
+    /// The busiest processes, busiest first, leaving out idle ones
+    pub fn top_consumers(&self, count: usize) -> Vec<TopConsumer> {
+        let mut busy_processes: Vec<&ProcessRecord> = self
+            .processes
+            .iter()
+            .filter(|process| process.cpu_usage > 0.0)
+            .collect();
+        busy_processes.sort_by(|a, b| b.cpu_usage.total_cmp(&a.cpu_usage).then(a.pid.cmp(&b.pid)));
+        busy_processes
+            .into_iter()
+            .take(count)
+            .map(TopConsumer::from_record)
+            .collect()
+    }
+
+    /// Looks up the stored record of a process, unless its pid has been reused.
+    ///
+    /// * Parameters
+    /// `process_key` The pid and start time of the process
+    ///
+    /// * Returns
+    /// Some(&ProcessRecord) if the process was in the latest snapshot, None otherwise
+    pub fn find_process_by_key(&self, process_key: ProcessKey) -> Option<&ProcessRecord> {
+        self.find_process(process_key.pid)
+            .filter(|process| process.start_time == process_key.start_time)
+    }
+
+    /// Opens the detail window of a process, replacing the one shown.
+    ///
+    /// Its history starts with its latest record, unless it has one already.
+    ///
+    /// * Parameters
+    /// `pid` The pid of the process
+    pub fn open_process_detail(&mut self, pid: u32) {
+        // the records are searched directly, as the histories are changed meanwhile
+        let Some(process) = self.processes.iter().find(|process| process.pid == pid) else {
+            return;
+        };
+        let process_key: ProcessKey = ProcessKey::from_record(process);
+        if let Entry::Vacant(entry) = self.process_histories.entry(process_key) {
+            entry
+                .insert(ProcessHistory::new())
+                .process_history_add_record(process, settings_monitoring().history_points);
+        }
+        self.detail_process = Some(process_key);
+        self.refresh_process_environment();
+    }
+
+    /// Closes the detail window.
+    pub fn close_process_detail(&mut self) {
+        self.detail_process = None;
+        self.refresh_process_environment();
+    }
+
+    /// Forgets the environment of the detail process, so it is asked for again.
+    pub fn refresh_process_environment(&mut self) {
+        self.detail_environment = None;
+        self.detail_environment_requested = false;
+    }
+
+    /// Finds the process whose environment should be asked for, the one in the detail
+    /// window when its environment hasn't been asked for yet.
+    ///
+    /// * Returns
+    /// The process to read the environment of, None when nothing needs asking
+    pub fn process_monitor_take_environment_request(&mut self) -> Option<ProcessKey> {
+        if self.detail_environment_requested {
+            return None;
+        }
+        let process_key: ProcessKey = self.detail_process?;
+        self.detail_environment_requested = true;
+        Some(process_key)
+    }
+
+    /// Takes in the environment of a process, keeping it if the process is still the
+    /// one in the detail window.
+    ///
+    /// * Parameters
+    /// `process_environment` The environment read by the processes worker
+    pub fn process_monitor_apply_process_environment(
+        &mut self,
+        process_environment: ProcessEnvironment,
+    ) {
+        if self.detail_process == Some(process_environment.process_key) {
+            self.detail_environment = Some(process_environment.environment);
+        }
+    }
+
+    /// Selects the column the table is sorted by.
+    ///
+    /// Selecting the current sort column again reverses the sort direction.
+    ///
+    /// * Parameters
+    /// `column` The column to sort by
+    pub fn select_sort_column(&mut self, column: ProcessColumn) {
+        if self.sort_column == column {
+            self.sort_descending = !self.sort_descending;
+        } else {
+            self.sort_column = column;
+            self.sort_descending = false;
+        }
+        self.sort_processes();
+    }
+
+    /// Adds the records of the selected process and the detail process to their
+    /// histories, and drops every other history.
+    fn update_process_histories(&mut self) {
+        let history_points: usize = settings_monitoring().history_points;
+        // only these histories are ever shown, keeping one per process would be wasteful
+        let shown_keys: Vec<ProcessKey> = self
+            .selected_pid
+            .and_then(|pid| self.find_process(pid))
+            .map(ProcessKey::from_record)
+            .into_iter()
+            .chain(self.detail_process)
+            .collect();
+
+        let mut live_keys: HashSet<ProcessKey> = HashSet::with_capacity(shown_keys.len());
+        for process in &self.processes {
+            let process_key: ProcessKey = ProcessKey::from_record(process);
+            if !shown_keys.contains(&process_key) {
+                continue;
+            }
+            self.process_histories
+                .entry(process_key)
+                .or_insert_with(ProcessHistory::new)
+                .process_history_add_record(process, history_points);
+            live_keys.insert(process_key);
+        }
+        self.process_histories
+            .retain(|process_key, _| live_keys.contains(process_key));
+    }
+
+    /// Sorts the stored process records by the current sort column and direction,
+    /// then rebuilds the tree rows from them.
+    fn sort_processes(&mut self) {
+        let column: ProcessColumn = self.sort_column;
+        let descending: bool = self.sort_descending;
+        self.processes.sort_by(|a, b| {
+            // ties are broken by pid so rows don't jump around between snapshots
+            let ordering: Ordering = column.compare(a, b).then(a.pid.cmp(&b.pid));
+            if descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+        self.tree_rows = self.build_filtered_tree();
+    }
+
+    /// Builds the tree rows of the matching processes and of their parents, so
+    /// every matching process is shown where it sits in the tree.
+    ///
+    /// * Returns
+    /// The tree rows, in display order
+    fn build_filtered_tree(&self) -> Vec<ProcessTreeRow> {
+        let tree_rows: Vec<ProcessTreeRow> =
+            build_process_tree(&self.processes, &self.collapsed_pids);
+        let Some(matching_pids) = &self.matching_pids else {
+            return tree_rows;
+        };
+
+        let parent_by_pid: HashMap<u32, Option<u32>> = self
+            .processes
+            .iter()
+            .map(|process| (process.pid, process.parent_pid))
+            .collect();
+        let mut shown_pids: HashSet<u32> = HashSet::new();
+        for pid in matching_pids {
+            // stops at a pid already shown, which also ends parent cycles
+            let mut next: Option<u32> = Some(*pid);
+            while let Some(pid) = next
+                && shown_pids.insert(pid)
+            {
+                next = parent_by_pid.get(&pid).copied().flatten();
+            }
+        }
+
+        tree_rows
+            .into_iter()
+            .filter(|tree_row| shown_pids.contains(&self.processes[tree_row.index].pid))
+            .collect()
     }
 }
@@ -0,0 +1,587 @@
+//! Process view module.
+//!
+//! This module renders the filter bar, the sortable process table, in flat or tree
+//! form, and the actions available on the selected process.
+
+use std::ops::RangeInclusive;
+use std::sync::mpsc::Sender;
+
+use crate::{
+    app::process_monitor::{ProcessColumn, ProcessMonitor},
+    commands::{
+        process_command_enum::{ProcessCommand, signal_name},
+        process_command_outcome_struct::ProcessCommandOutcome,
+    },
+    config::layout::{
+        PROCESS_COMMAND_MAX_CHARS, PROCESS_THREADS_HEIGHT_PX, PROCESS_TREE_INDENT_PX,
+    },
+    data::{
+        format::{format_bytes, format_core_list, format_timestamp},
+        process_tree::ProcessTreeRow,
+    },
+    filter::process_filter_struct::ProcessFilter,
+    snapshots::{process_record_struct::ProcessRecord, threads_snapshot_struct::ThreadsSnapshot},
+};
+
+use eframe::egui::{
+    Align2, Button, CollapsingHeader, Color32, ComboBox, Context, DragValue, Grid, Response,
+    ScrollArea, TextEdit, Ui, Window, vec2,
+};
+use sysinfo::{SUPPORTED_SIGNALS, Signal};
+
+/// The nice values a process can be given, from the highest priority to the lowest.
+const NICE_RANGE: RangeInclusive<i32> = -20..=19;
+
+/// Renders the process panel.
+///
+/// Draws the filter bar and the actions for the selected process above the process
+/// table, and the confirmation dialog when an action is waiting to be confirmed.
+///
+/// * Parameters
+/// `ui` The UI to draw in
+/// `process_monitor` Mutable reference to the process monitor for data access
+/// `core_count` The number of cores a process can be pinned to
+/// `process_command_sender` Sender used to pass confirmed commands to the processes worker
+/// `process_filter_sender` Sender used to pass new filters to the process filter worker
+pub fn render_process_panel(
+    ui: &mut Ui,
+    process_monitor: &mut ProcessMonitor,
+    core_count: usize,
+    process_command_sender: &Sender<ProcessCommand>,
+    process_filter_sender: &Sender<ProcessFilter>,
+) {
+    ui.horizontal(|ui| {
+        ui.heading("Processes");
+        ui.add_space(20.0);
+        ui.checkbox(&mut process_monitor.tree_view, "Tree view");
+    });
+    ui.add_space(5.0);
+
+    render_filter_bar(ui, process_monitor, process_filter_sender);
+    ui.add_space(5.0);
+
+    render_process_actions(ui, process_monitor, core_count);
+    render_command_outcome(ui, process_monitor);
+    ui.add_space(5.0);
+
+    render_process_table(ui, process_monitor);
+
+    render_confirmation_dialog(ui.ctx(), process_monitor, process_command_sender);
+}
+
+/// Renders the filter box and what the filter in use lets through.
+///
+/// Words typed in the box are looked for in the process names and command lines,
+/// and `user:`, `state:`, `cpu>`, `cpu<`, `mem>` and `mem<` words narrow the table
+/// down further. A filter is sent to the filter worker as soon as it parses.
+///
+/// * Parameters
+/// `ui` The UI to draw in
+/// `process_monitor` Mutable reference to the process monitor for data access
+/// `process_filter_sender` Sender used to pass new filters to the process filter worker
+fn render_filter_bar(
+    ui: &mut Ui,
+    process_monitor: &mut ProcessMonitor,
+    process_filter_sender: &Sender<ProcessFilter>,
+) {
+    let mut changed: bool = false;
+    ui.horizontal(|ui| {
+        ui.label("Filter");
+        changed = ui
+            .add(
+                TextEdit::singleline(&mut process_monitor.filter_text)
+                    .hint_text("name user:alice cpu>5 mem>1G state:zombie"),
+            )
+            .changed();
+        if !process_monitor.filter_text.is_empty() && ui.small_button("x").clicked() {
+            process_monitor.filter_text.clear();
+            changed = true;
+        }
+    });
+
+    if changed
+        && let Some(process_filter) = process_monitor.process_monitor_update_filter()
+        && process_filter_sender.send(process_filter).is_err()
+    {
+        process_monitor.filter_error = Some(String::from("the process filter worker has stopped"));
+    }
+
+    if let Some(filter_error) = &process_monitor.filter_error {
+        ui.colored_label(Color32::RED, filter_error);
+    } else if let Some(matching_pids) = &process_monitor.matching_pids {
+        ui.label(format!(
+            "{} of {} processes match '{}'",
+            matching_pids.len(),
+            process_monitor.processes.len(),
+            process_monitor.process_filter.expression
+        ));
+    }
+}
+
+/// Renders the buttons that act on the selected process.
+///
+/// The buttons don't act straight away, they set the pending command so the user
+/// can confirm it first.
+///
+/// * Parameters
+/// `ui` The UI to draw in
+/// `process_monitor` Mutable reference to the process monitor for data access
+/// `core_count` The number of cores a process can be pinned to
+fn render_process_actions(ui: &mut Ui, process_monitor: &mut ProcessMonitor, core_count: usize) {
+    let Some(pid) = process_monitor.selected_pid else {
+        ui.label("Select a process to act on it.");
+        return;
+    };
+
+    let name: &str = process_monitor
+        .find_process(pid)
+        .map(|process| process.name.as_str())
+        .unwrap_or("exited");
+    ui.label(format!("Selected: {} ({})", pid, name));
+
+    let mut chosen_signal: Option<Signal> = None;
+    ui.horizontal(|ui| {
+        for (label, signal) in [
+            ("Terminate", Signal::Term),
+            ("Kill", Signal::Kill),
+            ("Stop", Signal::Stop),
+            ("Continue", Signal::Continue),
+        ] {
+            if ui
+                .button(label)
+                .on_hover_text(format!("Send {}", signal_name(signal)))
+                .clicked()
+            {
+                chosen_signal = Some(signal);
+            }
+        }
+
+        ComboBox::from_id_salt("signal_picker")
+            .selected_text(signal_name(process_monitor.selected_signal))
+            .show_ui(ui, |ui| {
+                for signal in SUPPORTED_SIGNALS {
+                    ui.selectable_value(
+                        &mut process_monitor.selected_signal,
+                        *signal,
+                        signal_name(*signal),
+                    );
+                }
+            });
+        if ui.button("Send").clicked() {
+            chosen_signal = Some(process_monitor.selected_signal);
+        }
+    });
+
+    if let Some(signal) = chosen_signal {
+        process_monitor.pending_command = Some(ProcessCommand::SendSignal { pid, signal });
+    }
+
+    render_scheduling_controls(ui, process_monitor, pid, core_count);
+    render_thread_list(ui, process_monitor);
+}
+
+/// Renders the controls changing the nice value and the CPU affinity of the
+/// selected process, next to the values it had in the latest snapshot.
+///
+/// Like the signal buttons, they set the pending command so the user can confirm it
+/// first. There is a checkbox per core, numbered like the per-core rows.
+///
+/// * Parameters
+/// `ui` The UI to draw in
+/// `process_monitor` Mutable reference to the process monitor for data access
+/// `pid` The pid of the selected process
+/// `core_count` The number of cores a process can be pinned to
+fn render_scheduling_controls(
+    ui: &mut Ui,
+    process_monitor: &mut ProcessMonitor,
+    pid: u32,
+    core_count: usize,
+) {
+    let (current_nice, current_affinity): (Option<i32>, Option<Vec<usize>>) =
+        match process_monitor.find_process(pid) {
+            Some(process) => (process.nice, process.affinity.clone()),
+            None => (None, None),
+        };
+    let mut chosen_command: Option<ProcessCommand> = None;
+
+    ui.horizontal(|ui| {
+        ui.label("Nice");
+        ui.add(DragValue::new(&mut process_monitor.edited_nice).range(NICE_RANGE));
+        if ui
+            .button("Renice")
+            .on_hover_text(
+                "Lower values get more CPU time, going below the current value may need root",
+            )
+            .clicked()
+        {
+            chosen_command = Some(ProcessCommand::SetNice {
+                pid,
+                nice: process_monitor.edited_nice,
+            });
+        }
+        if let Some(nice) = current_nice {
+            ui.weak(format!("now {}", nice));
+        }
+    });
+
+    CollapsingHeader::new("CPU affinity")
+        .id_salt("process_affinity")
+        .show(ui, |ui| {
+            ui.horizontal_wrapped(|ui| {
+                for core in 0..core_count {
+                    let mut allowed: bool = process_monitor.edited_affinity.contains(&core);
+                    if ui
+                        .checkbox(&mut allowed, format!("Core {}", core))
+                        .changed()
+                    {
+                        match allowed {
+                            true => process_monitor.edited_affinity.insert(core),
+                            false => process_monitor.edited_affinity.remove(&core),
+                        };
+                    }
+                }
+            });
+            ui.horizontal(|ui| {
+                if ui.small_button("All").clicked() {
+                    process_monitor.edited_affinity = (0..core_count).collect();
+                }
+                // a process has to be allowed at least one core
+                let can_pin: bool = !process_monitor.edited_affinity.is_empty();
+                if ui.add_enabled(can_pin, Button::new("Pin")).clicked() {
+                    chosen_command = Some(ProcessCommand::SetAffinity {
+                        pid,
+                        cores: process_monitor.edited_affinity.iter().copied().collect(),
+                    });
+                }
+                if let Some(cores) = &current_affinity {
+                    ui.weak(format!("now {}", format_core_list(cores)));
+                }
+            });
+        });
+
+    if chosen_command.is_some() {
+        process_monitor.pending_command = chosen_command;
+    }
+}
+
+/// Renders the threads of the selected process, busiest first.
+///
+/// The threads are listed by the threads worker, which follows the selected process
+/// and refreshes the list at every sample.
+///
+/// * Parameters
+/// `ui` The UI to draw in
+/// `process_monitor` Reference to the process monitor for data access
+fn render_thread_list(ui: &mut Ui, process_monitor: &ProcessMonitor) {
+    let threads_snapshot: Option<&ThreadsSnapshot> = process_monitor.threads_snapshot.as_ref();
+    let header: String = match threads_snapshot {
+        Some(threads_snapshot) if threads_snapshot.error.is_none() => {
+            format!("Threads ({})", threads_snapshot.threads.len())
+        }
+        _ => String::from("Threads"),
+    };
+
+    CollapsingHeader::new(header)
+        .id_salt("process_threads")
+        .show(ui, |ui| {
+            let Some(threads_snapshot) = threads_snapshot else {
+                ui.weak("Listing threads...");
+                return;
+            };
+            if let Some(error) = &threads_snapshot.error {
+                ui.weak(format!("Threads can't be listed: {}", error));
+                return;
+            }
+
+            ScrollArea::vertical()
+                .id_salt("thread_table_scroll")
+                .max_height(PROCESS_THREADS_HEIGHT_PX)
+                .auto_shrink([false, true])
+                .show(ui, |ui| {
+                    Grid::new("thread_table")
+                        .striped(true)
+                        .num_columns(5)
+                        .show(ui, |ui| {
+                            for label in ["TID", "Name", "State", "CPU %", "Core"] {
+                                ui.strong(label);
+                            }
+                            ui.end_row();
+
+                            for thread in &threads_snapshot.threads {
+                                ui.monospace(format!("{}", thread.tid));
+                                ui.label(&thread.name);
+                                ui.label(&thread.status);
+                                // the usage needs two samples of the thread
+                                match thread.cpu_usage {
+                                    Some(cpu_usage) => ui.monospace(format!("{:>5.1}", cpu_usage)),
+                                    None => ui.monospace("    -"),
+                                };
+                                match thread.last_core {
+                                    Some(core) => ui.monospace(format!("{}", core)),
+                                    None => ui.monospace("-"),
+                                };
+                                ui.end_row();
+                            }
+                        });
+                });
+        });
+}
+
+/// Renders the outcome of the last command, if any.
+///
+/// * Parameters
+/// `ui` The UI to draw in
+/// `process_monitor` Mutable reference to the process monitor for data access
+fn render_command_outcome(ui: &mut Ui, process_monitor: &mut ProcessMonitor) {
+    let mut dismissed: bool = false;
+    if let Some(outcome) = &process_monitor.last_command_outcome {
+        ui.horizontal(|ui| {
+            let color: Color32 = match outcome.error {
+                Some(_) => Color32::RED,
+                None => Color32::GREEN,
+            };
+            ui.colored_label(color, outcome.message());
+            dismissed = ui.small_button("x").clicked();
+        });
+    }
+    if dismissed {
+        process_monitor.last_command_outcome = None;
+    }
+}
+
+/// Renders the process table.
+///
+/// Clicking a column header sorts the table by that column, clicking it again
+/// reverses the direction. Clicking a pid or name selects the process, and double
+/// clicking it opens the process's detail window. In tree
+/// view, the arrow next to a name collapses or expands its children and the cpu
+/// and memory columns show the totals of the whole subtree.
+///
+/// * Parameters
+/// `ui` The UI to draw in
+/// `process_monitor` Mutable reference to the process monitor for data access
+fn render_process_table(ui: &mut Ui, process_monitor: &mut ProcessMonitor) {
+    ScrollArea::both()
+        .auto_shrink([false, false])
+        .show(ui, |ui| {
+            Grid::new("process_table")
+                .striped(true)
+                .num_columns(ProcessColumn::ALL.len())
+                .show(ui, |ui| {
+                    let mut clicked_column: Option<ProcessColumn> = None;
+                    for column in ProcessColumn::ALL {
+                        let is_sort_column: bool = process_monitor.sort_column == column;
+                        let arrow: &str = match (is_sort_column, process_monitor.sort_descending) {
+                            (true, true) => " v",
+                            (true, false) => " ^",
+                            (false, _) => "",
+                        };
+                        if ui
+                            .selectable_label(
+                                is_sort_column,
+                                format!("{}{}", column.label(), arrow),
+                            )
+                            .clicked()
+                        {
+                            clicked_column = Some(column);
+                        }
+                    }
+                    ui.end_row();
+
+                    // sorts before the rows are drawn so the new order shows up in this frame
+                    if let Some(column) = clicked_column {
+                        process_monitor.select_sort_column(column);
+                    }
+
+                    let mut row_clicks: RowClicks = RowClicks::default();
+                    if process_monitor.tree_view {
+                        for tree_row in &process_monitor.tree_rows {
+                            let process: &ProcessRecord =
+                                &process_monitor.processes[tree_row.index];
+                            let is_selected: bool =
+                                process_monitor.selected_pid == Some(process.pid);
+                            let is_collapsed: bool =
+                                process_monitor.collapsed_pids.contains(&process.pid);
+                            row_clicks.merge(render_process_row(
+                                ui,
+                                process,
+                                is_selected,
+                                Some((tree_row, is_collapsed)),
+                            ));
+                            ui.end_row();
+                        }
+                    } else {
+                        for process in process_monitor.matching_processes() {
+                            let is_selected: bool =
+                                process_monitor.selected_pid == Some(process.pid);
+                            row_clicks.merge(render_process_row(ui, process, is_selected, None));
+                            ui.end_row();
+                        }
+                    }
+
+                    if let Some(pid) = row_clicks.selected_pid {
+                        process_monitor.select_process(pid);
+                    }
+                    if let Some(pid) = row_clicks.toggled_pid {
+                        process_monitor.toggle_collapsed(pid);
+                    }
+                    if let Some(pid) = row_clicks.opened_pid {
+                        process_monitor.open_process_detail(pid);
+                    }
+                });
+        });
+}
+
+/// Clicks made on the rows of the process table during one frame.
+#[derive(Default)]
+struct RowClicks {
+    selected_pid: Option<u32>,
+    toggled_pid: Option<u32>,
+    opened_pid: Option<u32>,
+}
+
+impl RowClicks {
+    /// Records a click on the pid or name of a row, which selects the process, and
+    /// a double click, which also opens its detail window.
+    ///
+    /// * Parameters
+    /// `pid` The pid of the row's process
+    /// `response` The response of the clicked label
+    fn record(&mut self, pid: u32, response: &Response) {
+        if response.clicked() {
+            self.selected_pid = Some(pid);
+        }
+        if response.double_clicked() {
+            self.opened_pid = Some(pid);
+        }
+    }
+
+    /// Keeps the clicks of another row, if it had any.
+    ///
+    /// * Parameters
+    /// `other` The clicks of another row
+    fn merge(&mut self, other: RowClicks) {
+        self.selected_pid = other.selected_pid.or(self.selected_pid);
+        self.toggled_pid = other.toggled_pid.or(self.toggled_pid);
+        self.opened_pid = other.opened_pid.or(self.opened_pid);
+    }
+}
+
+/// Renders a single row of the process table.
+///
+/// * Parameters
+/// `ui` The UI to draw in
+/// `process` The process record to display
+/// `is_selected` Whether the process is the selected process
+/// `tree` The tree row of the process and whether it is collapsed, in tree view
+///
+/// * Returns
+/// The clicks made on the row
+fn render_process_row(
+    ui: &mut Ui,
+    process: &ProcessRecord,
+    is_selected: bool,
+    tree: Option<(&ProcessTreeRow, bool)>,
+) -> RowClicks {
+    let mut row_clicks: RowClicks = RowClicks::default();
+
+    let pid_response: Response = ui.selectable_label(is_selected, format!("{}", process.pid));
+    row_clicks.record(process.pid, &pid_response);
+
+    ui.horizontal(|ui| {
+        if let Some((tree_row, is_collapsed)) = tree {
+            ui.add_space(tree_row.depth as f32 * PROCESS_TREE_INDENT_PX);
+            if tree_row.has_children {
+                let arrow: &str = if is_collapsed { "▶" } else { "▼" };
+                if ui.small_button(arrow).clicked() {
+                    row_clicks.toggled_pid = Some(process.pid);
+                }
+            } else {
+                ui.add_space(PROCESS_TREE_INDENT_PX);
+            }
+        }
+        let name_response: Response = ui.selectable_label(is_selected, &process.name);
+        row_clicks.record(process.pid, &name_response);
+    });
+
+    ui.label(&process.user);
+
+    match tree {
+        Some((tree_row, _)) if tree_row.has_children => {
+            ui.monospace(format!("{:>5.1}", tree_row.subtree_cpu_usage))
+                .on_hover_text(format!("This process: {:.1}", process.cpu_usage));
+            ui.monospace(format_bytes(tree_row.subtree_memory))
+                .on_hover_text(format!("This process: {}", format_bytes(process.memory)));
+        }
+        _ => {
+            ui.monospace(format!("{:>5.1}", process.cpu_usage));
+            ui.monospace(format_bytes(process.memory));
+        }
+    }
+
+    ui.label(&process.status);
+    ui.monospace(format_timestamp(process.start_time));
+
+    // long command lines are shortened, the full command is shown on hover
+    let command: String = match process
+        .command
+        .char_indices()
+        .nth(PROCESS_COMMAND_MAX_CHARS)
+    {
+        Some((index, _)) => format!("{}...", &process.command[..index]),
+        None => process.command.clone(),
+    };
+    ui.label(command).on_hover_text(&process.command);
+
+    row_clicks
+}
+
+/// Renders the confirmation dialog for the pending command.
+///
+/// Confirming sends the command to the processes worker, cancelling discards it.
+///
+/// * Parameters
+/// `ctx` The egui context
+/// `process_monitor` Mutable reference to the process monitor for data access
+/// `process_command_sender` Sender used to pass the confirmed command to the processes worker
+fn render_confirmation_dialog(
+    ctx: &Context,
+    process_monitor: &mut ProcessMonitor,
+    process_command_sender: &Sender<ProcessCommand>,
+) {
+    let Some(command) = process_monitor.pending_command.clone() else {
+        return;
+    };
+
+    let name: &str = process_monitor
+        .find_process(command.pid())
+        .map(|process| process.name.as_str())
+        .unwrap_or("exited");
+
+    let mut confirmed: bool = false;
+    let mut cancelled: bool = false;
+    Window::new("Confirm")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(Align2::CENTER_CENTER, vec2(0.0, 0.0))
+        .show(ctx, |ui| {
+            ui.label(format!("Really {} ({})?", command.describe(), name));
+            ui.horizontal(|ui| {
+                confirmed = ui.button("Confirm").clicked();
+                cancelled = ui.button("Cancel").clicked();
+            });
+        });
+
+    if confirmed {
+        // a failed send hands the command back, to say which one didn't go through
+        if let Err(e) = process_command_sender.send(command) {
+            process_monitor.last_command_outcome = Some(ProcessCommandOutcome {
+                command: e.0,
+                error: Some(String::from("the processes worker has stopped")),
+            });
+        }
+        process_monitor.pending_command = None;
+    } else if cancelled {
+        process_monitor.pending_command = None;
+    }
+}
@@ -0,0 +1,33 @@
+//! Replay monitor module.
+//!
+//! This module defines the ReplayMonitor struct for tracking the playback of a
+//! recording.
+
+use crate::recording::replay_status_struct::ReplayStatus;
+
+/// A sub-structure of the AppMonitor structure.
+/// Stores the latest status reported by the replayer, None until the first one
+/// arrives or when not replaying.
+pub struct ReplayMonitor {
+    pub latest_status: Option<ReplayStatus>,
+}
+
+impl ReplayMonitor {
+    /// Constructor for the ReplayMonitor structure.
+    ///
+    /// * Returns
+    /// ReplayMonitor structure without a status
+    pub fn new() -> ReplayMonitor {
+        ReplayMonitor {
+            latest_status: None,
+        }
+    }
+
+    /// Takes in a ReplayStatus struct and updates the fields in ReplayMonitor.
+    ///
+    /// * Parameters
+    /// `replay_status` ReplayStatus structure
+    pub fn replay_monitor_apply_replay_status(&mut self, replay_status: ReplayStatus) {
+        self.latest_status = Some(replay_status);
+    }
+}
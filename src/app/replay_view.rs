@@ -0,0 +1,95 @@
+//! Replay view module.
+//!
+//! This module renders the playback controls shown while replaying a recording.
+
+use std::sync::mpsc::Sender;
+
+use crate::{
+    app::{cpu_monitor::CpuMonitor, replay_monitor::ReplayMonitor},
+    commands::replay_command_enum::ReplayCommand,
+    recording::replay_status_struct::ReplayStatus,
+};
+
+use eframe::egui::{Slider, Ui};
+
+/// Renders a play/pause button and a slider to seek through the recording.
+///
+/// Seeking clears the CPU histories, since the graphs would otherwise join
+/// snapshots from different points of the recording.
+///
+/// * Parameters
+/// `ui` The UI to draw in
+/// `replay_monitor` Reference to the replay monitor for data access
+/// `cpu_monitor` Mutable reference to the cpu monitor, reset when seeking
+/// `replay_command_sender` Sender used to control the replay
+pub fn render_replay_controls(
+    ui: &mut Ui,
+    replay_monitor: &ReplayMonitor,
+    cpu_monitor: &mut CpuMonitor,
+    replay_command_sender: &Sender<ReplayCommand>,
+) {
+    let Some(replay_status) = replay_monitor.latest_status else {
+        ui.label("Loading recording...");
+        return;
+    };
+
+    ui.horizontal(|ui| {
+        ui.label("Replay");
+        let button_text: &str = if replay_status.playing {
+            "Pause"
+        } else {
+            "Play"
+        };
+        if ui.button(button_text).clicked() {
+            let replay_command: ReplayCommand = if replay_status.playing {
+                ReplayCommand::Pause
+            } else {
+                ReplayCommand::Play
+            };
+            // the replayer only stops when the GUI has closed
+            let _ = replay_command_sender.send(replay_command);
+        }
+
+        let mut position_milliseconds: u64 = replay_status.position_milliseconds;
+        let slider = Slider::new(
+            &mut position_milliseconds,
+            0..=replay_status.duration_milliseconds,
+        )
+        .show_value(false);
+        if ui.add(slider).changed() {
+            cpu_monitor.cpu_monitor_reset();
+            let _ = replay_command_sender.send(ReplayCommand::Seek {
+                position_milliseconds,
+            });
+        }
+
+        ui.label(format_replay_position(&replay_status));
+    });
+}
+
+/// Formats the position of a replay as "m:ss / m:ss".
+///
+/// * Parameters
+/// `replay_status` The status to format
+///
+/// * Returns
+/// The formatted position and duration
+fn format_replay_position(replay_status: &ReplayStatus) -> String {
+    format!(
+        "{} / {}",
+        format_minutes_seconds(replay_status.position_milliseconds),
+        format_minutes_seconds(replay_status.duration_milliseconds)
+    )
+}
+
+/// Formats milliseconds as minutes and seconds.
+///
+/// * Parameters
+/// `milliseconds` The time to format
+///
+/// * Returns
+/// The time as "m:ss"
+fn format_minutes_seconds(milliseconds: u64) -> String {
+    let seconds: u64 = milliseconds / 1000;
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
@@ -0,0 +1,75 @@
+//! Sensor monitor module.
+//!
+//! This module defines the SensorMonitor struct for tracking hardware sensor readings.
+
+use std::collections::{HashMap, VecDeque};
+
+use crate::{
+    data::history::history_add_point,
+    snapshots::{sensor_record_struct::SensorRecord, sensors_snapshot_struct::SensorsSnapshot},
+};
+
+/// Reading history of a single sensor.
+///
+/// Stores the latest record and the history of its readings.
+pub struct SensorHistory {
+    pub latest_record: SensorRecord,
+    pub history: VecDeque<f32>,
+}
+
+/// A sub-structure of the AppMonitor structure.
+/// Stores the reading history of every sensor, in the order the sensors worker
+/// reports them, and the latest temperature of each logical CPU's core.
+pub struct SensorMonitor {
+    pub sensors: Vec<SensorHistory>,
+    pub core_temperatures: Vec<Option<f32>>,
+}
+
+impl SensorMonitor {
+    /// Constructor for the SensorMonitor structure.
+    ///
+    /// * Returns
+    /// SensorMonitor structure with no sensors
+    pub fn new() -> SensorMonitor {
+        SensorMonitor {
+            sensors: Vec::new(),
+            core_temperatures: Vec::new(),
+        }
+    }
+
+    /// Takes in a SensorsSnapshot struct and updates the fields in SensorMonitor.
+    ///
+    /// Sensors missing from the snapshot are removed along with their history.
+    ///
+    /// * Parameters
+    /// `sensors_snapshot` SensorsSnapshot structure
+    pub fn sensor_monitor_apply_sensors_snapshot(&mut self, sensors_snapshot: SensorsSnapshot) {
+        let mut histories: HashMap<String, VecDeque<f32>> = self
+            .sensors
+            .drain(..)
+            .map(|sensor_history| (sensor_history.latest_record.id, sensor_history.history))
+            .collect();
+
+        for record in sensors_snapshot.sensors {
+            let mut history: VecDeque<f32> = histories.remove(&record.id).unwrap_or_default();
+            history_add_point(&mut history, record.value);
+            self.sensors.push(SensorHistory {
+                latest_record: record,
+                history,
+            });
+        }
+
+        self.core_temperatures = sensors_snapshot.core_temperatures;
+    }
+
+    /// Looks up the temperature of a logical CPU's core.
+    ///
+    /// * Parameters
+    /// `index` The index of the logical CPU
+    ///
+    /// * Returns
+    /// Some(degrees Celsius) if a sensor reports the core's temperature, None otherwise
+    pub fn core_temperature(&self, index: usize) -> Option<f32> {
+        self.core_temperatures.get(index).copied().flatten()
+    }
+}
@@ -0,0 +1,94 @@
+//! Sensor view module.
+//!
+//! This module renders one reading row per hardware sensor.
+
+use crate::{
+    app::sensor_monitor::{SensorHistory, SensorMonitor},
+    config::{
+        layout::{
+            CELL_HEIGHT_PX, DEVICE_LEFT_CELL_WIDTH_PX, LINE_THICKNESS_ONE_PX, TEXT_SPACING_PX,
+        },
+        style::{CRITICAL_SENSOR_COLOR, SENSOR_LINE_COLOR},
+    },
+    graph::{
+        draw::draw_scaled_ui_graph,
+        geometry::{make_point, nice_upper_bound},
+    },
+};
+
+use eframe::egui::{Color32, Sense, Stroke, Ui, UiBuilder, Vec2, vec2};
+
+/// Renders the sensor panel.
+///
+/// Draws a row per sensor with its current reading and a rolling graph of its
+/// readings. Sensors at or above their critical threshold are drawn in red.
+///
+/// * Parameters
+/// `ui` The UI to draw in
+/// `sensor_monitor` Reference to the sensor monitor for data access
+pub fn render_sensor_panel(ui: &mut Ui, sensor_monitor: &SensorMonitor) {
+    if sensor_monitor.sensors.is_empty() {
+        ui.label("No sensors found.");
+        return;
+    }
+
+    for sensor_history in &sensor_monitor.sensors {
+        render_sensor_row(ui, sensor_history);
+        ui.add_space(TEXT_SPACING_PX);
+    }
+}
+
+/// Renders a single sensor row.
+///
+/// * Parameters
+/// `ui` The UI to draw in
+/// `sensor_history` The reading history of the sensor
+fn render_sensor_row(ui: &mut Ui, sensor_history: &SensorHistory) {
+    let record = &sensor_history.latest_record;
+    let color: Color32 = match record.is_critical() {
+        true => CRITICAL_SENSOR_COLOR,
+        false => SENSOR_LINE_COLOR,
+    };
+
+    ui.group(|ui| {
+        ui.horizontal(|ui| {
+            let left_cell: Vec2 = vec2(DEVICE_LEFT_CELL_WIDTH_PX, CELL_HEIGHT_PX);
+            let (rect, _) = ui.allocate_exact_size(left_cell, Sense::hover());
+
+            ui.scope_builder(UiBuilder::new().max_rect(rect), |ui| {
+                ui.label(format!("{} {}", record.chip, record.label));
+                ui.colored_label(color, record.kind.format_value(record.value));
+                if let Some(critical) = record.critical {
+                    ui.small(format!("critical: {}", record.kind.format_value(critical)));
+                }
+            });
+
+            let right_cell: Vec2 = vec2(ui.available_width(), CELL_HEIGHT_PX);
+            let (rect, _) = ui.allocate_exact_size(right_cell, Sense::hover());
+
+            // the critical threshold is kept on the graph so the line can be seen approaching it
+            let max_reading: f32 = sensor_history
+                .history
+                .iter()
+                .fold(record.critical.unwrap_or(0.0), |max, value| max.max(*value));
+            let upper_bound: f32 = nice_upper_bound(max_reading);
+
+            draw_scaled_ui_graph(
+                &rect,
+                ui,
+                &[(&sensor_history.history, color)],
+                upper_bound,
+                &format!("{} {}", upper_bound, record.kind.unit()),
+            );
+
+            if let Some(critical) = record.critical.filter(|critical| *critical > 0.0) {
+                let y: f32 = make_point(0, &critical, 2, &rect, upper_bound).y;
+                ui.painter_at(rect).hline(
+                    rect.x_range(),
+                    y,
+                    Stroke::new(LINE_THICKNESS_ONE_PX, CRITICAL_SENSOR_COLOR),
+                );
+            }
+        });
+    });
+}
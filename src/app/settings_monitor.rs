@@ -0,0 +1,114 @@
+//! Settings monitor module.
+//!
+//! This module defines the SettingsMonitor struct for editing the settings at runtime.
+
+use std::path::PathBuf;
+
+use crate::{
+    app::cpu_monitor::CpuMonitor,
+    config::{
+        config_file::save_settings,
+        settings::{Settings, StyleSettings, set_settings, settings},
+    },
+};
+
+/// A sub-structure of the AppMonitor structure.
+/// Stores the settings being edited in the settings window, whether the window is
+/// open, where the settings are saved and the outcome of the latest save.
+/// Note: edited settings are applied as soon as they change, saving only writes them
+/// to the config file.
+/// Note: the saved settings are those loaded at startup or last written, the config
+/// file is only written when the settings in use differ from them.
+pub struct SettingsMonitor {
+    pub edited_settings: Settings,
+    pub window_open: bool,
+    pub config_path: Option<PathBuf>,
+    pub saved_settings: Settings,
+    pub last_save_message: Option<String>,
+}
+
+impl SettingsMonitor {
+    /// Constructor for the SettingsMonitor structure.
+    ///
+    /// * Returns
+    /// SettingsMonitor structure editing the settings in use, saving to the default
+    /// config file
+    pub fn new() -> SettingsMonitor {
+        SettingsMonitor {
+            edited_settings: settings(),
+            window_open: false,
+            config_path: None,
+            saved_settings: settings(),
+            last_save_message: None,
+        }
+    }
+
+    /// Puts the edited settings in use if they changed and are valid.
+    ///
+    /// The workers and the graphs read the settings as they go, only the CPU
+    /// histories need trimming straight away when the history length was lowered.
+    ///
+    /// * Parameters
+    /// `cpu_monitor` Mutable reference to the cpu monitor whose histories are trimmed
+    pub fn settings_monitor_apply(&mut self, cpu_monitor: &mut CpuMonitor) {
+        // the process filter is edited above the process table, not in the settings window
+        self.edited_settings.processes = settings().processes;
+
+        // the settings window says why the thresholds aren't applied
+        let style: StyleSettings = self.edited_settings.style;
+        if self.edited_settings == settings()
+            || style.yellow_line_threshold >= style.red_line_threshold
+        {
+            return;
+        }
+        set_settings(self.edited_settings.clone());
+        cpu_monitor.cpu_monitor_trim_histories();
+    }
+
+    /// Puts a new process filter in use, to be saved with the other settings.
+    ///
+    /// * Parameters
+    /// `filter` The filter expression
+    pub fn settings_monitor_set_process_filter(&mut self, filter: &str) {
+        let mut new_settings: Settings = settings();
+        new_settings.processes.filter = filter.to_string();
+        self.edited_settings.processes = new_settings.processes.clone();
+        set_settings(new_settings);
+    }
+
+    /// Checks whether the settings in use differ from those in the config file.
+    ///
+    /// * Returns
+    /// true if there is something to save
+    pub fn has_unsaved_changes(&self) -> bool {
+        settings() != self.saved_settings
+    }
+
+    /// Writes the settings in use to the config file if they changed since the last save.
+    pub fn settings_monitor_save(&mut self) {
+        if !self.has_unsaved_changes() {
+            return;
+        }
+        let current_settings: Settings = settings();
+        self.last_save_message = Some(
+            match save_settings(self.config_path.as_deref(), &current_settings) {
+                Ok(path) => {
+                    self.saved_settings = current_settings;
+                    format!("Saved settings to {}", path.display())
+                }
+                Err(e) => format!("Could not save settings: {}", e),
+            },
+        );
+    }
+
+    /// Saves the settings when the application closes if they changed, printing the outcome.
+    pub fn save_on_exit(&mut self) {
+        if !self.has_unsaved_changes() {
+            return;
+        }
+        self.settings_monitor_save();
+        if let Some(last_save_message) = &self.last_save_message {
+            println!("{}", last_save_message);
+        }
+    }
+}
@@ -0,0 +1,176 @@
+//! Settings view module.
+//!
+//! This module renders the settings window opened from the menu bar.
+
+use crate::{
+    app::{cpu_monitor::CpuMonitor, settings_monitor::SettingsMonitor},
+    config::{
+        settings::{
+            HISTORY_POINTS_RANGE, HISTORY_SECONDS_RANGE, LINE_THRESHOLD_RANGE,
+            MAX_CPU_HISTORY_POINTS, PanelSettings, REFRESH_MILLISECONDS_RANGE,
+            SAMPLING_MILLISECONDS_RANGE, SMOOTHING_FACTOR_RANGE, Settings,
+        },
+        style::CRITICAL_SENSOR_COLOR,
+    },
+};
+
+use eframe::egui::{Context, Grid, Slider, Ui, Window};
+
+/// Renders the settings window while it is open.
+///
+/// Changes are applied as they are made. The settings are saved to the config
+/// file when the window is closed.
+///
+/// * Parameters
+/// `ctx` The egui context
+/// `settings_monitor` Mutable reference to the settings monitor
+/// `cpu_monitor` Mutable reference to the cpu monitor, whose histories follow the
+/// history length
+pub fn render_settings_window(
+    ctx: &Context,
+    settings_monitor: &mut SettingsMonitor,
+    cpu_monitor: &mut CpuMonitor,
+) {
+    let mut window_open: bool = settings_monitor.window_open;
+    Window::new("Settings")
+        .open(&mut window_open)
+        .resizable(false)
+        .collapsible(false)
+        .show(ctx, |ui| {
+            render_settings_form(ui, &mut settings_monitor.edited_settings);
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                if ui.button("Reset to defaults").clicked() {
+                    // the window size isn't edited here, so it is kept
+                    settings_monitor.edited_settings = Settings {
+                        window: settings_monitor.edited_settings.window,
+                        ..Settings::DEFAULT
+                    };
+                }
+                if let Some(last_save_message) = &settings_monitor.last_save_message {
+                    ui.label(last_save_message);
+                }
+            });
+        });
+
+    settings_monitor.settings_monitor_apply(cpu_monitor);
+    if settings_monitor.window_open && !window_open {
+        settings_monitor.settings_monitor_save();
+    }
+    settings_monitor.window_open = window_open;
+}
+
+/// Renders a control for every setting that can be changed at runtime.
+///
+/// * Parameters
+/// `ui` The UI to draw in
+/// `edited_settings` The settings being edited
+fn render_settings_form(ui: &mut Ui, edited_settings: &mut Settings) {
+    let Settings {
+        monitoring,
+        style,
+        panels,
+        ..
+    } = edited_settings;
+
+    ui.strong("Monitoring");
+    Grid::new("monitoring_settings")
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label("Sampling interval");
+            ui.add(
+                Slider::new(
+                    &mut monitoring.sampling_milliseconds,
+                    SAMPLING_MILLISECONDS_RANGE,
+                )
+                .logarithmic(true)
+                .suffix(" ms"),
+            );
+            ui.end_row();
+
+            ui.label("Refresh interval");
+            ui.add(
+                Slider::new(
+                    &mut monitoring.refresh_milliseconds,
+                    REFRESH_MILLISECONDS_RANGE,
+                )
+                .logarithmic(true)
+                .suffix(" ms"),
+            );
+            ui.end_row();
+
+            ui.label("CPU history kept");
+            ui.add(
+                Slider::new(&mut monitoring.history_seconds, HISTORY_SECONDS_RANGE)
+                    .logarithmic(true)
+                    .suffix(" s"),
+            );
+            ui.end_row();
+
+            let kept_milliseconds: u64 = monitoring.cpu_history_milliseconds();
+            if kept_milliseconds < monitoring.history_seconds * 1000 {
+                ui.label("");
+                ui.weak(format!(
+                    "Only the last {} s are kept, at most {} points per graph",
+                    kept_milliseconds / 1000,
+                    MAX_CPU_HISTORY_POINTS
+                ));
+                ui.end_row();
+            }
+
+            ui.label("Other graph lengths");
+            ui.add(
+                Slider::new(&mut monitoring.history_points, HISTORY_POINTS_RANGE)
+                    .logarithmic(true)
+                    .suffix(" points"),
+            );
+            ui.end_row();
+
+            ui.label("EMA smoothing factor");
+            ui.add(Slider::new(
+                &mut monitoring.smoothing_factor,
+                SMOOTHING_FACTOR_RANGE,
+            ));
+            ui.end_row();
+        });
+
+    ui.add_space(10.0);
+    ui.strong("Graph colours");
+    Grid::new("style_settings").num_columns(2).show(ui, |ui| {
+        ui.label("Yellow from");
+        ui.add(Slider::new(&mut style.yellow_line_threshold, LINE_THRESHOLD_RANGE).suffix("%"));
+        ui.end_row();
+
+        ui.label("Red from");
+        ui.add(Slider::new(&mut style.red_line_threshold, LINE_THRESHOLD_RANGE).suffix("%"));
+        ui.end_row();
+    });
+    if style.yellow_line_threshold >= style.red_line_threshold {
+        ui.colored_label(
+            CRITICAL_SENSOR_COLOR,
+            "The yellow threshold must be below the red threshold",
+        );
+    }
+
+    ui.add_space(10.0);
+    ui.strong("Panels");
+    render_panel_checkboxes(ui, panels);
+}
+
+/// Renders a checkbox per panel that can be hidden.
+///
+/// * Parameters
+/// `ui` The UI to draw in
+/// `panels` The panel settings being edited
+fn render_panel_checkboxes(ui: &mut Ui, panels: &mut PanelSettings) {
+    ui.horizontal_wrapped(|ui| {
+        ui.checkbox(&mut panels.memory, "Memory");
+        ui.checkbox(&mut panels.per_core, "Per core usage");
+        ui.checkbox(&mut panels.network, "Network");
+        ui.checkbox(&mut panels.disks, "Disks");
+        ui.checkbox(&mut panels.sensors, "Sensors");
+        ui.checkbox(&mut panels.alerts, "Alerts");
+        ui.checkbox(&mut panels.processes, "Processes");
+    });
+}
@@ -2,7 +2,10 @@
 //!
 //! This module contains functions for updating the application state based on received snapshots.
 
-use crate::{app::app_monitor::AppMonitor, snapshots::cpu_snapshot_struct::CpuSnapshot};
+use crate::{
+    app::app_monitor::AppMonitor, config::app_variables::TOP_CONSUMER_COUNT,
+    data::top_consumer_struct::TopConsumer, snapshots::cpu_snapshot_struct::CpuSnapshot,
+};
 
 // INVARIANTS:
 // This function only triggers when a valid cpu_snapshot is received.
@@ -10,7 +13,9 @@ use crate::{app::app_monitor::AppMonitor, snapshots::cpu_snapshot_struct::CpuSna
 
 /// Changes the system monitor app state by applying a CPU snapshot.
 ///
-/// Updates the CPU monitor within the app monitor with the new snapshot data.
+/// Updates the CPU monitor within the app monitor with the new snapshot data,
+/// records the current process count and busiest processes alongside it, and
+/// evaluates the alert rules against the new values.
 ///
 /// * Parameters
 /// `cpu_snapshot` The CPU snapshot to apply
@@ -19,4 +24,28 @@ pub fn change_system_monitor_app_state(cpu_snapshot: CpuSnapshot, app_monitor: &
     app_monitor
         .cpu_monitor
         .cpu_monitor_apply_cpu_snapshot(cpu_snapshot);
+
+    let process_count: usize = app_monitor.process_monitor.processes.len();
+    app_monitor
+        .cpu_monitor
+        .cpu_monitor_add_process_count(process_count);
+
+    let top_consumers: Vec<TopConsumer> = app_monitor
+        .process_monitor
+        .top_consumers(TOP_CONSUMER_COUNT);
+    app_monitor
+        .cpu_monitor
+        .cpu_monitor_add_top_consumers(top_consumers);
+
+    let timestamp_milliseconds: u64 = app_monitor
+        .cpu_monitor
+        .timestamp_history
+        .back()
+        .copied()
+        .unwrap_or(0);
+    app_monitor.alert_monitor.alert_monitor_evaluate(
+        &app_monitor.cpu_monitor,
+        process_count,
+        timestamp_milliseconds,
+    );
 }
@@ -0,0 +1,34 @@
+//! Top consumers view module.
+//!
+//! This module renders the processes using the most CPU at a sample, beside the
+//! overall CPU progress bar and in the tooltip of the overall CPU graph.
+
+use crate::{config::layout::TOP_CONSUMER_NAME_MAX_CHARS, data::top_consumer_struct::TopConsumer};
+
+use eframe::egui::Ui;
+
+/// Renders a compact list of the processes using the most CPU, one per line.
+///
+/// * Parameters
+/// `ui` The UI to draw in
+/// `top_consumers` The busiest processes, busiest first
+pub fn render_top_consumers(ui: &mut Ui, top_consumers: &[TopConsumer]) {
+    if top_consumers.is_empty() {
+        ui.weak("No busy processes");
+        return;
+    }
+
+    for top_consumer in top_consumers {
+        // long names are shortened, the pid and full name are shown on hover
+        let name: String = match top_consumer
+            .name
+            .char_indices()
+            .nth(TOP_CONSUMER_NAME_MAX_CHARS)
+        {
+            Some((index, _)) => format!("{}...", &top_consumer.name[..index]),
+            None => top_consumer.name.clone(),
+        };
+        ui.small(format!("{:>5.1}% {}", top_consumer.cpu_usage, name))
+            .on_hover_text(format!("{} ({})", top_consumer.name, top_consumer.pid));
+    }
+}
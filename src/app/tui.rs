@@ -0,0 +1,114 @@
+//! Terminal UI module.
+//!
+//! This module runs the terminal frontend, an alternative to the eframe window
+//! that drives the same monitors.
+
+use std::io;
+use std::time::Duration;
+
+use ratatui::{
+    DefaultTerminal,
+    crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+};
+
+use crate::{
+    app::{
+        app_monitor::AppMonitor,
+        process_monitor::ProcessColumn,
+        tui_view::{TuiState, render_tui},
+        update::receive_snapshots,
+    },
+    config::settings::settings_monitoring,
+};
+
+/// Runs the terminal frontend until the user quits.
+///
+/// The terminal is restored on the way out, also when drawing fails, and the
+/// metrics history is exported when --export was given.
+///
+/// * Parameters
+/// `app_monitor` The app monitor to drive
+///
+/// * Returns
+/// The error met drawing to or reading from the terminal, if any
+pub fn run_tui(mut app_monitor: AppMonitor) -> io::Result<()> {
+    let mut terminal: DefaultTerminal = ratatui::init();
+    let result: io::Result<()> = tui_loop(&mut terminal, &mut app_monitor);
+    ratatui::restore();
+
+    app_monitor
+        .export_monitor
+        .export_on_exit(&app_monitor.cpu_monitor);
+    result
+}
+
+/// Receives snapshots, draws a frame and handles key presses until the user quits.
+///
+/// * Parameters
+/// `terminal` The terminal to draw to
+/// `app_monitor` The app monitor to drive
+///
+/// * Returns
+/// The error met drawing to or reading from the terminal, if any
+fn tui_loop(terminal: &mut DefaultTerminal, app_monitor: &mut AppMonitor) -> io::Result<()> {
+    let mut tui_state: TuiState = TuiState::new();
+
+    loop {
+        receive_snapshots(app_monitor);
+        terminal.draw(|frame| render_tui(frame, app_monitor, &mut tui_state))?;
+
+        // waiting for input doubles as the refresh delay
+        if !event::poll(Duration::from_millis(
+            settings_monitoring().refresh_milliseconds,
+        ))? {
+            continue;
+        }
+        if let Event::Key(key_event) = event::read()?
+            && key_event.kind == KeyEventKind::Press
+            && !handle_key(key_event, app_monitor, &mut tui_state)
+        {
+            return Ok(());
+        }
+    }
+}
+
+/// Applies a key press.
+///
+/// * Parameters
+/// `key_event` The key that was pressed
+/// `app_monitor` The app monitor, whose process sorting the keys change
+/// `tui_state` The state of the terminal frontend
+///
+/// * Returns
+/// false if the key quits the frontend, true otherwise
+fn handle_key(key_event: KeyEvent, app_monitor: &mut AppMonitor, tui_state: &mut TuiState) -> bool {
+    let process_monitor = &mut app_monitor.process_monitor;
+
+    match key_event.code {
+        KeyCode::Char('q') | KeyCode::Esc => return false,
+        KeyCode::Char('c') if key_event.modifiers.contains(KeyModifiers::CONTROL) => {
+            return false;
+        }
+        KeyCode::Up => tui_state.process_offset = tui_state.process_offset.saturating_sub(1),
+        KeyCode::Down => tui_state.process_offset += 1,
+        KeyCode::PageUp => {
+            tui_state.process_offset = tui_state
+                .process_offset
+                .saturating_sub(tui_state.process_rows_shown)
+        }
+        KeyCode::PageDown => tui_state.process_offset += tui_state.process_rows_shown,
+        KeyCode::Home => tui_state.process_offset = 0,
+        KeyCode::Char('s') => {
+            // cycles through the columns in display order
+            let index: usize = ProcessColumn::ALL
+                .iter()
+                .position(|column| *column == process_monitor.sort_column)
+                .unwrap_or(0);
+            let next_index: usize = (index + 1) % ProcessColumn::ALL.len();
+            process_monitor.select_sort_column(ProcessColumn::ALL[next_index]);
+        }
+        KeyCode::Char('r') => process_monitor.select_sort_column(process_monitor.sort_column),
+        _ => {}
+    }
+    true
+}
@@ -0,0 +1,309 @@
+//! Terminal UI view module.
+//!
+//! This module renders the CPU, per-core and process data in the terminal.
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, LineGauge, Paragraph, Row, Sparkline, Table},
+};
+
+use crate::{
+    app::{app_monitor::AppMonitor, process_monitor::ProcessColumn},
+    config::style::FULL_OPACITY,
+    data::format::{format_bytes, format_frequency},
+    graph::style::get_color,
+};
+
+/// Structure holding the state that only the terminal frontend has.
+///
+/// The number of process rows shown is remembered from the last frame, for
+/// scrolling a page at a time.
+pub struct TuiState {
+    pub process_offset: usize,
+    pub process_rows_shown: usize,
+}
+
+impl TuiState {
+    /// Creates a new TuiState scrolled to the top of the process list.
+    ///
+    /// * Returns
+    /// TuiState structure
+    pub fn new() -> TuiState {
+        TuiState {
+            process_offset: 0,
+            process_rows_shown: 0,
+        }
+    }
+}
+
+/// Renders a whole frame: the header, overall CPU usage, per-core bars, the
+/// process list and the key help.
+///
+/// * Parameters
+/// `frame` The frame to draw in
+/// `app_monitor` Reference to the app monitor for data access
+/// `tui_state` The state of the terminal frontend
+pub fn render_tui(frame: &mut Frame, app_monitor: &AppMonitor, tui_state: &mut TuiState) {
+    let core_count: usize = app_monitor
+        .cpu_monitor
+        .per_core_cpu_history
+        .as_ref()
+        .map_or(0, |per_core_cpu_history| per_core_cpu_history.len());
+
+    let [
+        header_area,
+        overall_area,
+        cores_area,
+        processes_area,
+        help_area,
+    ] = Layout::vertical([
+        Constraint::Length(2),
+        Constraint::Length(5),
+        // every core gets a row, but the process list keeps at least 5
+        Constraint::Max(core_count as u16 + 2),
+        Constraint::Min(5),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+
+    render_header(frame, header_area, app_monitor);
+    render_overall_cpu(frame, overall_area, app_monitor);
+    render_cores(frame, cores_area, app_monitor);
+    render_processes(frame, processes_area, app_monitor, tui_state);
+
+    frame.render_widget(
+        Paragraph::new("q quit | Up/Down/PgUp/PgDn scroll | s sort column | r reverse sort")
+            .style(Style::default().add_modifier(Modifier::DIM)),
+        help_area,
+    );
+}
+
+/// Renders the host, operating system, process count and average usage.
+///
+/// * Parameters
+/// `frame` The frame to draw in
+/// `area` Where to draw
+/// `app_monitor` Reference to the app monitor for data access
+fn render_header(frame: &mut Frame, area: Rect, app_monitor: &AppMonitor) {
+    let system_monitor = &app_monitor.system_monitor;
+    let lines: Vec<Line> = vec![
+        Line::from(format!(
+            "CPU Monitor | {} | OS: {} {} {}",
+            system_monitor.host_name,
+            system_monitor.system_name,
+            system_monitor.system_version,
+            system_monitor.system_architecture
+        ))
+        .style(Style::default().add_modifier(Modifier::BOLD)),
+        Line::from(format!(
+            "Processes: {} | Average Usage: {:.1}% | Snapshots taken: {}",
+            app_monitor.process_monitor.processes.len(),
+            app_monitor.cpu_monitor.average_cpu_usage,
+            app_monitor.cpu_monitor.total_snapshots_received
+        )),
+    ];
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+/// Renders the overall CPU usage as a bar above a sparkline of its history.
+///
+/// * Parameters
+/// `frame` The frame to draw in
+/// `area` Where to draw
+/// `app_monitor` Reference to the app monitor for data access
+fn render_overall_cpu(frame: &mut Frame, area: Rect, app_monitor: &AppMonitor) {
+    let cpu_monitor = &app_monitor.cpu_monitor;
+    let block: Block = Block::bordered().title("Overall CPU Usage");
+    let inner: Rect = block.inner(area);
+    frame.render_widget(block, area);
+
+    let [gauge_area, sparkline_area] =
+        Layout::vertical([Constraint::Length(1), Constraint::Min(1)]).areas(inner);
+
+    let usage: f32 = cpu_monitor
+        .overall_cpu_history
+        .back()
+        .copied()
+        .unwrap_or(0.0);
+    frame.render_widget(
+        usage_gauge(
+            format!(
+                "{:>5.1}% (EMA {:>5.1}%)",
+                usage,
+                cpu_monitor.previous_ema.unwrap_or(0.0)
+            ),
+            usage,
+        ),
+        gauge_area,
+    );
+
+    // sparklines only take whole numbers, a tenth of a percent is plenty. The
+    // history is longer than the terminal is wide, so only the newest points fit
+    let skipped_points: usize = cpu_monitor
+        .overall_cpu_history
+        .len()
+        .saturating_sub(sparkline_area.width as usize);
+    let history: Vec<u64> = cpu_monitor
+        .overall_cpu_history
+        .iter()
+        .skip(skipped_points)
+        .map(|usage| (usage * 10.0) as u64)
+        .collect();
+    frame.render_widget(
+        Sparkline::default()
+            .data(&history)
+            .max(1000)
+            .style(Style::default().fg(usage_color(usage))),
+        sparkline_area,
+    );
+}
+
+/// Renders a bar per core with its usage, frequency and temperature.
+///
+/// * Parameters
+/// `frame` The frame to draw in
+/// `area` Where to draw
+/// `app_monitor` Reference to the app monitor for data access
+fn render_cores(frame: &mut Frame, area: Rect, app_monitor: &AppMonitor) {
+    let cpu_monitor = &app_monitor.cpu_monitor;
+    let block: Block = Block::bordered().title("Per Core CPU Usage");
+    let inner: Rect = block.inner(area);
+    frame.render_widget(block, area);
+
+    let Some(per_core_cpu_history) = &cpu_monitor.per_core_cpu_history else {
+        return;
+    };
+    let rows =
+        Layout::vertical(vec![Constraint::Length(1); per_core_cpu_history.len()]).split(inner);
+
+    for (index, (history, row)) in per_core_cpu_history.iter().zip(rows.iter()).enumerate() {
+        let usage: f32 = history.back().copied().unwrap_or(0.0);
+        let mut label: String = format!("Core {:<2} {:>5.1}%", index, usage);
+        if let Some(frequency) = cpu_monitor.per_core_frequency.get(index) {
+            label.push_str(&format!(" {:>9}", format_frequency(*frequency)));
+        }
+        if let Some(temperature) = app_monitor.sensor_monitor.core_temperature(index) {
+            label.push_str(&format!(" {:>3.0}°C", temperature));
+        }
+        frame.render_widget(usage_gauge(label, usage), *row);
+    }
+}
+
+/// Renders the process list, sorted and filtered the same way as the GUI's process
+/// table. The filter in use is shown in the title.
+///
+/// * Parameters
+/// `frame` The frame to draw in
+/// `area` Where to draw
+/// `app_monitor` Reference to the app monitor for data access
+/// `tui_state` The state of the terminal frontend, whose offset is kept in range
+fn render_processes(
+    frame: &mut Frame,
+    area: Rect,
+    app_monitor: &AppMonitor,
+    tui_state: &mut TuiState,
+) {
+    let process_monitor = &app_monitor.process_monitor;
+    let matching_count: usize = process_monitor.matching_processes().count();
+
+    // the borders and the header row take three lines
+    tui_state.process_rows_shown = area.height.saturating_sub(3) as usize;
+    tui_state.process_offset = tui_state
+        .process_offset
+        .min(matching_count.saturating_sub(tui_state.process_rows_shown));
+
+    let header: Row = Row::new(
+        [
+            ProcessColumn::Pid,
+            ProcessColumn::Name,
+            ProcessColumn::User,
+            ProcessColumn::CpuUsage,
+            ProcessColumn::Memory,
+            ProcessColumn::Status,
+        ]
+        .map(|column| {
+            if column != process_monitor.sort_column {
+                column.label().to_string()
+            } else if process_monitor.sort_descending {
+                format!("{} ▼", column.label())
+            } else {
+                format!("{} ▲", column.label())
+            }
+        }),
+    )
+    .style(Style::default().add_modifier(Modifier::BOLD));
+
+    let rows: Vec<Row> = process_monitor
+        .matching_processes()
+        .skip(tui_state.process_offset)
+        .take(tui_state.process_rows_shown)
+        .map(|process| {
+            Row::new([
+                process.pid.to_string(),
+                process.name.clone(),
+                process.user.clone(),
+                format!("{:.1}", process.cpu_usage),
+                format_bytes(process.memory),
+                process.status.clone(),
+            ])
+        })
+        .collect();
+    let mut title: String = format!(
+        "Processes ({}-{} of {})",
+        tui_state.process_offset + rows.len().min(1),
+        tui_state.process_offset + rows.len(),
+        matching_count
+    );
+    if !process_monitor.process_filter.is_empty() {
+        title.push_str(&format!(
+            " matching '{}'",
+            process_monitor.process_filter.expression
+        ));
+    }
+
+    let table: Table = Table::new(
+        rows,
+        [
+            Constraint::Length(8),
+            Constraint::Fill(2),
+            Constraint::Fill(1),
+            Constraint::Length(8),
+            Constraint::Length(11),
+            Constraint::Length(10),
+        ],
+    )
+    .header(header)
+    .block(Block::bordered().title(title));
+    frame.render_widget(table, area);
+}
+
+/// Builds a one line bar for a CPU usage percentage, coloured like the GUI graphs.
+///
+/// * Parameters
+/// `label` The text in front of the bar
+/// `usage` The CPU usage percentage
+///
+/// * Returns
+/// The bar widget
+fn usage_gauge(label: String, usage: f32) -> LineGauge<'static> {
+    LineGauge::default()
+        .label(label)
+        .ratio((usage as f64 / 100.0).clamp(0.0, 1.0))
+        .filled_style(Style::default().fg(usage_color(usage)))
+        .unfilled_style(Style::default().fg(Color::DarkGray))
+}
+
+/// Converts the GUI colour of a CPU usage percentage to a terminal colour.
+///
+/// * Parameters
+/// `usage` The CPU usage percentage
+///
+/// * Returns
+/// The colour
+fn usage_color(usage: f32) -> Color {
+    let color = get_color(usage, FULL_OPACITY);
+    Color::Rgb(color.r(), color.g(), color.b())
+}
@@ -7,14 +7,19 @@ use eframe::egui::Context;
 use crate::{
     app::{
         app_monitor::AppMonitor,
-        process_monitor::ProcessMonitor,
         state::change_system_monitor_app_state,
         system_monitor::SystemMonitor,
         view::{render_ui, request_repaint, try_receive_latest_snapshot},
     },
+    commands::process_command_enum::ProcessCommand,
+    filter::filtered_processes_struct::FilteredProcesses,
+    recording::replay_status_struct::ReplayStatus,
     snapshots::{
-        cpu_snapshot_struct::CpuSnapshot, processes_snapshot_struct::ProcessesSnapshot,
-        system_snapshot_struct::SystemSnapshot,
+        cpu_snapshot_struct::CpuSnapshot, cpu_times_snapshot_struct::CpuTimesSnapshot,
+        disks_snapshot_struct::DisksSnapshot, load_snapshot_struct::LoadSnapshot,
+        memory_snapshot_struct::MemorySnapshot, network_snapshot_struct::NetworkSnapshot,
+        process_environment_struct::ProcessEnvironment, sensors_snapshot_struct::SensorsSnapshot,
+        system_snapshot_struct::SystemSnapshot, threads_snapshot_struct::ThreadsSnapshot,
     },
 };
 
@@ -31,6 +36,20 @@ use crate::{
 /// `app_monitor` Mutable reference to the app monitor
 /// `ctx` The egui context for UI operations
 pub fn update(app_monitor: &mut AppMonitor, ctx: &Context) {
+    receive_snapshots(app_monitor);
+
+    // refreshes the gui
+    request_repaint(ctx);
+    render_ui(ctx, app_monitor);
+}
+
+/// Receives the latest snapshot from every channel and applies it to its monitor.
+///
+/// Doesn't draw anything, so it also drives the monitors when running without a GUI.
+///
+/// * Parameters
+/// `app_monitor` Mutable reference to the app monitor
+pub fn receive_snapshots(app_monitor: &mut AppMonitor) {
     // NOTE: We use the :: syntax because turbofish parses the `<` symbol as a comparison operator otherwise, this is a known issue with if let statements.
     if let Some(system_snapshot) = try_receive_latest_snapshot::<SystemSnapshot>(
         &app_monitor.channels.system_snapshot_receiver,
@@ -39,13 +58,165 @@ pub fn update(app_monitor: &mut AppMonitor, ctx: &Context) {
         app_monitor.system_monitor = system_monitor;
     }
 
-    let potential_processes_snapshot: Option<ProcessesSnapshot> =
-        try_receive_latest_snapshot(&app_monitor.channels.processes_snapshot_receiver);
+    // a new filter can arrive in the same frame as the snapshot it filters again, which
+    // then still counts as a new sample
+    let mut potential_filtered_processes: Option<FilteredProcesses> = None;
+    while let Ok(mut filtered_processes) =
+        app_monitor.channels.filtered_processes_receiver.try_recv()
+    {
+        if let Some(previous) = &potential_filtered_processes {
+            filtered_processes.fresh |= previous.fresh;
+        }
+        potential_filtered_processes = Some(filtered_processes);
+    }
+
+    if let Some(filtered_processes) = potential_filtered_processes {
+        app_monitor
+            .process_monitor
+            .process_monitor_apply_filtered_processes(filtered_processes);
+    }
 
-    if let Some(processes_snapshot) = potential_processes_snapshot {
-        let processes_monitor: ProcessMonitor =
-            ProcessMonitor::build_from_snapshot(processes_snapshot);
-        app_monitor.process_monitor = processes_monitor;
+    if let Some(process_command_outcome) =
+        try_receive_latest_snapshot(&app_monitor.channels.process_command_outcome_receiver)
+    {
+        app_monitor.process_monitor.last_command_outcome = Some(process_command_outcome);
+    }
+
+    // the environment of the process opened in the detail window is read by the worker
+    if let Some(process_key) = app_monitor
+        .process_monitor
+        .process_monitor_take_environment_request()
+    {
+        // a replay refuses process commands, and environments aren't recorded anyway
+        let error: Option<String> = match app_monitor.channels.replay_command_sender {
+            Some(_) => Some(String::from("not recorded")),
+            None => app_monitor
+                .channels
+                .process_command_sender
+                .send(ProcessCommand::ReadEnvironment { process_key })
+                .err()
+                .map(|e| e.to_string()),
+        };
+        if let Some(error) = error {
+            app_monitor
+                .process_monitor
+                .process_monitor_apply_process_environment(ProcessEnvironment {
+                    process_key,
+                    environment: Err(error),
+                });
+        }
+    }
+
+    if let Some(process_environment) =
+        try_receive_latest_snapshot(&app_monitor.channels.process_environment_receiver)
+    {
+        app_monitor
+            .process_monitor
+            .process_monitor_apply_process_environment(process_environment);
+    }
+
+    // the threads listed follow the process selected in the last frame
+    if let Some(watched_process) = app_monitor
+        .process_monitor
+        .process_monitor_update_thread_watch()
+    {
+        let sent: bool = app_monitor
+            .channels
+            .thread_watch_sender
+            .send(watched_process)
+            .is_ok();
+        // only a replay has no threads worker, as threads aren't recorded
+        if !sent && let Some(process_key) = watched_process {
+            app_monitor
+                .process_monitor
+                .process_monitor_apply_threads_snapshot(ThreadsSnapshot {
+                    process_key,
+                    threads: Vec::new(),
+                    error: Some(String::from("threads aren't recorded")),
+                });
+        }
+    }
+
+    // a replay has no processes worker to read details, the recorded ones are shown
+    if let Some(detail_pids) = app_monitor
+        .process_monitor
+        .process_monitor_update_detail_watch()
+    {
+        let _ = app_monitor.channels.detail_watch_sender.send(detail_pids);
+    }
+
+    let potential_threads_snapshot: Option<ThreadsSnapshot> =
+        try_receive_latest_snapshot(&app_monitor.channels.threads_snapshot_receiver);
+
+    if let Some(threads_snapshot) = potential_threads_snapshot {
+        app_monitor
+            .process_monitor
+            .process_monitor_apply_threads_snapshot(threads_snapshot);
+    }
+
+    let potential_memory_snapshot: Option<MemorySnapshot> =
+        try_receive_latest_snapshot(&app_monitor.channels.memory_snapshot_receiver);
+
+    if let Some(memory_snapshot) = potential_memory_snapshot {
+        app_monitor
+            .memory_monitor
+            .memory_monitor_apply_memory_snapshot(memory_snapshot);
+    }
+
+    let potential_network_snapshot: Option<NetworkSnapshot> =
+        try_receive_latest_snapshot(&app_monitor.channels.network_snapshot_receiver);
+
+    if let Some(network_snapshot) = potential_network_snapshot {
+        app_monitor
+            .network_monitor
+            .network_monitor_apply_network_snapshot(network_snapshot);
+    }
+
+    let potential_disks_snapshot: Option<DisksSnapshot> =
+        try_receive_latest_snapshot(&app_monitor.channels.disks_snapshot_receiver);
+
+    if let Some(disks_snapshot) = potential_disks_snapshot {
+        app_monitor
+            .disk_monitor
+            .disk_monitor_apply_disks_snapshot(disks_snapshot);
+    }
+
+    let potential_sensors_snapshot: Option<SensorsSnapshot> =
+        try_receive_latest_snapshot(&app_monitor.channels.sensors_snapshot_receiver);
+
+    if let Some(sensors_snapshot) = potential_sensors_snapshot {
+        app_monitor
+            .sensor_monitor
+            .sensor_monitor_apply_sensors_snapshot(sensors_snapshot);
+    }
+
+    let potential_cpu_times_snapshot: Option<CpuTimesSnapshot> =
+        try_receive_latest_snapshot(&app_monitor.channels.cpu_times_snapshot_receiver);
+
+    if let Some(cpu_times_snapshot) = potential_cpu_times_snapshot {
+        app_monitor
+            .cpu_times_monitor
+            .cpu_times_monitor_apply_cpu_times_snapshot(cpu_times_snapshot);
+    }
+
+    let potential_load_snapshot: Option<LoadSnapshot> =
+        try_receive_latest_snapshot(&app_monitor.channels.load_snapshot_receiver);
+
+    if let Some(load_snapshot) = potential_load_snapshot {
+        app_monitor
+            .load_monitor
+            .load_monitor_apply_load_snapshot(load_snapshot);
+    }
+
+    if let Some(replay_status_receiver) = &app_monitor.channels.replay_status_receiver {
+        let potential_replay_status: Option<ReplayStatus> =
+            try_receive_latest_snapshot(replay_status_receiver);
+
+        if let Some(replay_status) = potential_replay_status {
+            app_monitor
+                .replay_monitor
+                .replay_monitor_apply_replay_status(replay_status);
+        }
     }
 
     let potential_cpu_snapshot: Option<CpuSnapshot> =
@@ -54,8 +225,4 @@ pub fn update(app_monitor: &mut AppMonitor, ctx: &Context) {
     if let Some(cpu_snapshot) = potential_cpu_snapshot {
         change_system_monitor_app_state(cpu_snapshot, app_monitor);
     };
-
-    // refreshes the gui
-    request_repaint(ctx);
-    render_ui(ctx, app_monitor);
 }
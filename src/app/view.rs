@@ -1,24 +1,48 @@
+use std::collections::VecDeque;
+use std::ops::Range;
 use std::time::Duration;
 
 use crate::{
-    app::app_monitor::AppMonitor,
+    app::{
+        alert_view::{render_alert_banner, render_alert_panel},
+        app_monitor::AppMonitor,
+        cpu_graph_view::{handle_cpu_graph_pointer, render_time_window_selector},
+        cpu_times_view::{draw_cpu_times_graph, render_cpu_times_legend},
+        disk_view::render_disk_panel,
+        load_view::render_load_row,
+        memory_view::render_memory_panel,
+        menu_view::render_menu_bar,
+        network_view::render_network_panel,
+        process_detail_view::render_process_detail_window,
+        process_view::render_process_panel,
+        replay_view::render_replay_controls,
+        sensor_view::render_sensor_panel,
+        settings_view::render_settings_window,
+        top_consumers_view::render_top_consumers,
+    },
     config::{
-        app_variables::REFRESH_MILLISECONDS,
         layout::{
-            CELL_HEIGHT_PX, LEFT_CELL_WIDTH_PX, PROGRESS_BAR_HEIGHT_PX, PROGRESS_BAR_ROUNDING_PX,
-            PROGRESS_BAR_SPACING_PX, PROGRESS_BAR_WIDTH_PX, TEXT_SPACING_PX,
+            CELL_HEIGHT_PX, LEFT_CELL_WIDTH_PX, PROCESS_PANEL_WIDTH_PX, PROGRESS_BAR_HEIGHT_PX,
+            PROGRESS_BAR_ROUNDING_PX, PROGRESS_BAR_SPACING_PX, PROGRESS_BAR_WIDTH_PX,
+            TEXT_SPACING_PX,
         },
-        style::HALF_OPACITY,
+        settings::{PanelSettings, settings, settings_monitoring, settings_panels},
+        style::{FREQUENCY_LINE_COLOR, HALF_OPACITY},
     },
+    data::{downsample::downsample_history, format::format_frequency},
     graph::{
-        draw::{build_progress_bar, draw_ui_graph},
+        draw::{
+            build_progress_bar, draw_ema_line, draw_scaled_ui_graph, draw_ui_graph,
+            graph_bucket_count,
+        },
+        geometry::nice_upper_bound,
         style::get_color,
     },
 };
 
 use eframe::egui::{
-    Align, CentralPanel, Color32, Context, Layout, ProgressBar, Response, ScrollArea, Sense,
-    UiBuilder, Vec2, vec2,
+    Align, CentralPanel, CollapsingHeader, Color32, Context, Layout, ProgressBar, Rect, Response,
+    ScrollArea, Sense, SidePanel, TopBottomPanel, Ui, UiBuilder, Vec2, vec2,
 };
 use std::sync::mpsc::Receiver;
 
@@ -49,6 +73,76 @@ pub fn try_receive_latest_snapshot<T>(receiver: &Receiver<T>) -> Option<T> {
 /// `ctx` The egui context
 /// `app_monitor` Mutable reference to the app monitor for data access
 pub fn render_ui(ctx: &Context, app_monitor: &mut AppMonitor) {
+    TopBottomPanel::top("menu_panel").show(ctx, |ui| {
+        render_menu_bar(
+            ui,
+            &app_monitor.cpu_monitor,
+            &mut app_monitor.export_monitor,
+            &mut app_monitor.settings_monitor,
+        );
+    });
+
+    if app_monitor.settings_monitor.window_open {
+        render_settings_window(
+            ctx,
+            &mut app_monitor.settings_monitor,
+            &mut app_monitor.cpu_monitor,
+        );
+    }
+    // read after the settings window so its changes show in the same frame
+    let panels: PanelSettings = settings_panels();
+
+    // the banner only shows while an alert is firing
+    if app_monitor.alert_monitor.firing_rules().next().is_some() {
+        TopBottomPanel::top("alert_banner").show(ctx, |ui| {
+            render_alert_banner(ui, &app_monitor.alert_monitor);
+        });
+    }
+
+    // the playback controls only exist while replaying a recording
+    if let Some(replay_command_sender) = &app_monitor.channels.replay_command_sender {
+        TopBottomPanel::top("replay_panel").show(ctx, |ui| {
+            render_replay_controls(
+                ui,
+                &app_monitor.replay_monitor,
+                &mut app_monitor.cpu_monitor,
+                replay_command_sender,
+            );
+        });
+    }
+
+    // the process table sits to the right of the cpu graphs
+    if panels.processes {
+        SidePanel::right("process_panel")
+            .resizable(true)
+            .default_width(PROCESS_PANEL_WIDTH_PX)
+            .show(ctx, |ui| {
+                // a process can be pinned to any of the cores in the per-core rows
+                let core_count: usize = app_monitor
+                    .cpu_monitor
+                    .per_core_cpu_history
+                    .as_ref()
+                    .map_or(0, Vec::len);
+                render_process_panel(
+                    ui,
+                    &mut app_monitor.process_monitor,
+                    core_count,
+                    &app_monitor.channels.process_command_sender,
+                    &app_monitor.channels.process_filter_sender,
+                );
+            });
+
+        render_process_detail_window(ctx, &mut app_monitor.process_monitor);
+
+        // the filter in use is kept in the settings for the next run
+        let filter_expression: &str = &app_monitor.process_monitor.process_filter.expression;
+        if *filter_expression != settings().processes.filter {
+            app_monitor
+                .settings_monitor
+                .settings_monitor_set_process_filter(filter_expression);
+        }
+    }
+
     // the show method takes a closure and builds the gui
     CentralPanel::default().show(ctx, |ui| {
         ui.group(|ui| {
@@ -69,7 +163,7 @@ pub fn render_ui(ctx: &Context, app_monitor: &mut AppMonitor) {
                         .max_rect(rect)
                         .layout(Layout::left_to_right(Align::Center)),
                     |ui| {
-                        ui.label(format!("{}", app_monitor.system_monitor.host_name));
+                        ui.label(&app_monitor.system_monitor.host_name);
                         ui.add_space(50.0);
                         ui.label(format!(
                             "OS: {} {} {}",
@@ -80,15 +174,22 @@ pub fn render_ui(ctx: &Context, app_monitor: &mut AppMonitor) {
                         ui.add_space(50.0);
                         ui.label(format!(
                             "Processes: {}",
-                            app_monitor.process_monitor.processes
+                            app_monitor.process_monitor.processes.len()
                         ));
                         ui.add_space(50.0);
                         ui.label(format!("Average Usage: {:>5.1}%", app_monitor.cpu_monitor.average_cpu_usage));
                     },
                 )
-            })
+            });
+
+            render_load_row(ui, &app_monitor.load_monitor);
         });
 
+        render_time_window_selector(ui, &mut app_monitor.cpu_monitor.graph_viewport);
+        app_monitor.cpu_monitor.graph_viewport.begin_frame();
+        let visible_range: Range<usize> = app_monitor.cpu_monitor.visible_range();
+        let visible_time_range: Option<(u64, u64)> = app_monitor.cpu_monitor.visible_time_range();
+
         // ===== OVERALL CPU USAGE =====
         ui.group(|ui| {
             ui.horizontal(|ui| {
@@ -121,27 +222,95 @@ pub fn render_ui(ctx: &Context, app_monitor: &mut AppMonitor) {
 
                         ui.add_space(TEXT_SPACING_PX);
                     }
+
+                    if let Some(top_consumers) =
+                        app_monitor.cpu_monitor.top_consumers_history.back()
+                    {
+                        // packed tight so the list fits the height of the graph
+                        ui.vertical(|ui| {
+                            ui.spacing_mut().item_spacing.y = 0.0;
+                            render_top_consumers(ui, top_consumers);
+                        });
+                        ui.add_space(TEXT_SPACING_PX);
+                    }
                 });
 
                 let right_cell: Vec2 = vec2(ui.available_width(), CELL_HEIGHT_PX);
-                let (rect, _) = ui.allocate_exact_size(right_cell, Sense::hover());
+                let (rect, response) = ui.allocate_exact_size(right_cell, Sense::click_and_drag());
 
-                draw_ui_graph(
-                    &rect,
+                match app_monitor
+                    .cpu_times_monitor
+                    .visible_history(None)
+                    .zip(visible_time_range)
+                {
+                    Some((cpu_times_history, time_range)) => {
+                        draw_cpu_times_graph(
+                            &rect,
+                            ui,
+                            cpu_times_history,
+                            app_monitor
+                                .cpu_times_monitor
+                                .visible_range(cpu_times_history, time_range),
+                        );
+                        draw_ema_line(
+                            &rect,
+                            ui,
+                            &app_monitor.cpu_monitor.overall_ema_cpu_history,
+                            visible_range.clone(),
+                        );
+                    }
+                    None => draw_ui_graph(
+                        &rect,
+                        ui,
+                        &app_monitor.cpu_monitor.overall_cpu_history,
+                        Some(&app_monitor.cpu_monitor.overall_ema_cpu_history),
+                        visible_range.clone(),
+                    ),
+                }
+                // the pointer reads the usage history, under either graph
+                handle_cpu_graph_pointer(
                     ui,
+                    &response,
+                    &mut app_monitor.cpu_monitor.graph_viewport,
+                    &app_monitor.cpu_monitor.timestamp_history,
                     &app_monitor.cpu_monitor.overall_cpu_history,
                     Some(&app_monitor.cpu_monitor.overall_ema_cpu_history),
+                    Some(&app_monitor.cpu_monitor.top_consumers_history),
+                    visible_range.clone(),
                 );
             });
         });
 
+        render_cpu_times_legend(ui, &mut app_monitor.cpu_times_monitor);
+
         ui.add_space(TEXT_SPACING_PX);
 
+        // ===== MEMORY USAGE =====
+        if panels.memory {
+            render_memory_panel(ui, &app_monitor.memory_monitor);
+
+            ui.add_space(TEXT_SPACING_PX);
+        }
+
         ScrollArea::vertical().show(ui, |ui| {
             // ===== PER CORE CPU USAGE =====
-            if let Some(per_core_history) = &app_monitor.cpu_monitor.per_core_cpu_history {
+            if panels.per_core {
+                ui.checkbox(
+                    &mut app_monitor.cpu_monitor.show_frequency_graphs,
+                    "Show core frequency graphs",
+                );
+            }
+
+            if panels.per_core
+                && let Some(per_core_history) = &app_monitor.cpu_monitor.per_core_cpu_history
+            {
                 for (index, history) in per_core_history.iter().enumerate() {
                     let usage: &f32 = history.back().unwrap(); // Check if this always works!
+                    let ema_history: Option<&VecDeque<f32>> = app_monitor
+                        .cpu_monitor
+                        .per_core_ema_cpu_history
+                        .as_ref()
+                        .and_then(|histories| histories.get(index));
 
                     ui.group(|ui| {
                         ui.horizontal(|ui| {
@@ -149,9 +318,25 @@ pub fn render_ui(ctx: &Context, app_monitor: &mut AppMonitor) {
                             let (rect, _) = ui.allocate_exact_size(left_cell, Sense::hover());
 
                             ui.scope_builder(UiBuilder::new().max_rect(rect), |ui| {
-                                ui.label(format!("Core {}", index));
+                                // the core temperature is shown where a sensor reports it
+                                let core_label: String =
+                                    match app_monitor.sensor_monitor.core_temperature(index) {
+                                        Some(temperature) => {
+                                            format!("Core {} {:.0}°C", index, temperature)
+                                        }
+                                        None => format!("Core {}", index),
+                                    };
+                                ui.label(core_label);
                                 // value formatted to one decimal place
-                                ui.monospace(format!("{:>5.1}%", *usage));
+                                ui.horizontal(|ui| {
+                                    ui.monospace(format!("{:>5.1}%", *usage));
+                                    render_core_frequency(
+                                        ui,
+                                        &app_monitor.cpu_monitor.per_core_frequency,
+                                        &app_monitor.cpu_monitor.per_core_frequency_limits,
+                                        index,
+                                    );
+                                });
                                 ui.add_space(PROGRESS_BAR_SPACING_PX);
 
                                 let color: Color32 = get_color(*usage, HALF_OPACITY);
@@ -169,25 +354,198 @@ pub fn render_ui(ctx: &Context, app_monitor: &mut AppMonitor) {
                                 ui.add_space(TEXT_SPACING_PX);
                             });
 
-                            let desired_size = vec2(ui.available_width(), CELL_HEIGHT_PX);
-                            let (rect, _) = ui.allocate_exact_size(desired_size, Sense::hover());
+                            // the frequency graph takes half of the row when it is shown
+                            let frequency_history: Option<&VecDeque<f32>> =
+                                match app_monitor.cpu_monitor.show_frequency_graphs {
+                                    true => app_monitor
+                                        .cpu_monitor
+                                        .per_core_frequency_history
+                                        .as_ref()
+                                        .and_then(|histories| histories.get(index)),
+                                    false => None,
+                                };
+                            let graph_width: f32 = match frequency_history {
+                                Some(_) => (ui.available_width() - TEXT_SPACING_PX) / 2.0,
+                                None => ui.available_width(),
+                            };
+
+                            let desired_size = vec2(graph_width, CELL_HEIGHT_PX);
+                            let (rect, response) =
+                                ui.allocate_exact_size(desired_size, Sense::click_and_drag());
 
-                            draw_ui_graph(
-                                &rect,
+                            match app_monitor
+                                .cpu_times_monitor
+                                .visible_history(Some(index))
+                                .zip(visible_time_range)
+                            {
+                                Some((cpu_times_history, time_range)) => {
+                                    draw_cpu_times_graph(
+                                        &rect,
+                                        ui,
+                                        cpu_times_history,
+                                        app_monitor
+                                            .cpu_times_monitor
+                                            .visible_range(cpu_times_history, time_range),
+                                    );
+                                    if let Some(ema_history) = ema_history {
+                                        draw_ema_line(
+                                            &rect,
+                                            ui,
+                                            ema_history,
+                                            visible_range.clone(),
+                                        );
+                                    }
+                                }
+                                None => draw_ui_graph(
+                                    &rect,
+                                    ui,
+                                    history,
+                                    ema_history,
+                                    visible_range.clone(),
+                                ),
+                            }
+                            // the pointer reads the usage history, under either graph
+                            handle_cpu_graph_pointer(
                                 ui,
+                                &response,
+                                &mut app_monitor.cpu_monitor.graph_viewport,
+                                &app_monitor.cpu_monitor.timestamp_history,
                                 history,
-                                Some(&app_monitor.cpu_monitor.overall_ema_cpu_history),
+                                ema_history,
+                                None,
+                                visible_range.clone(),
                             );
+
+                            if let Some(frequency_history) = frequency_history {
+                                ui.add_space(TEXT_SPACING_PX);
+                                let desired_size = vec2(ui.available_width(), CELL_HEIGHT_PX);
+                                let (rect, _) =
+                                    ui.allocate_exact_size(desired_size, Sense::hover());
+
+                                draw_frequency_graph(
+                                    &rect,
+                                    ui,
+                                    frequency_history,
+                                    visible_range.clone(),
+                                    app_monitor.cpu_monitor.per_core_frequency_limits.get(index),
+                                );
+                            }
                         });
                     });
 
                     ui.add_space(TEXT_SPACING_PX);
                 }
             }
+
+            // ===== NETWORK THROUGHPUT =====
+            if panels.network {
+                CollapsingHeader::new("Network")
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        render_network_panel(ui, &app_monitor.network_monitor);
+                    });
+            }
+
+            // ===== DISK USAGE AND I/O =====
+            if panels.disks {
+                CollapsingHeader::new("Disks")
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        render_disk_panel(ui, &app_monitor.disk_monitor);
+                    });
+            }
+
+            // ===== HARDWARE SENSORS =====
+            if panels.sensors {
+                CollapsingHeader::new("Sensors")
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        render_sensor_panel(ui, &app_monitor.sensor_monitor);
+                    });
+            }
+
+            // ===== ALERTS =====
+            if panels.alerts {
+                CollapsingHeader::new("Alerts")
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        render_alert_panel(ui, &app_monitor.alert_monitor);
+                    });
+            }
         });
     });
 }
 
+/// Renders the current frequency of a core.
+///
+/// The frequency scaling limits are shown on hover where they are known.
+///
+/// * Parameters
+/// `ui` The UI to draw in
+/// `per_core_frequency` The current frequency of every core, in MHz
+/// `per_core_frequency_limits` The (minimum, maximum) scaling frequency of every core
+/// `index` The index of the core
+fn render_core_frequency(
+    ui: &mut Ui,
+    per_core_frequency: &[u64],
+    per_core_frequency_limits: &[Option<(u64, u64)>],
+    index: usize,
+) {
+    let Some(frequency) = per_core_frequency.get(index) else {
+        return;
+    };
+
+    let response: Response = ui.small(format_frequency(*frequency));
+    if let Some(Some((minimum, maximum))) = per_core_frequency_limits.get(index) {
+        response.on_hover_text(format!(
+            "Scaling limits: {} - {}",
+            format_frequency(*minimum),
+            format_frequency(*maximum)
+        ));
+    }
+}
+
+/// Draws the rolling graph of a core's frequency.
+///
+/// The top of the graph is the core's maximum scaling frequency where it is known,
+/// so a line well below the top means the core is clocked down.
+///
+/// * Parameters
+/// `rect` The rectangle to draw in
+/// `ui` The UI context
+/// `frequency_history` The frequency history of the core, in MHz
+/// `visible_range` The indices of the points to draw
+/// `frequency_limits` The (minimum, maximum) scaling frequency of the core, if known
+fn draw_frequency_graph(
+    rect: &Rect,
+    ui: &mut Ui,
+    frequency_history: &VecDeque<f32>,
+    visible_range: Range<usize>,
+    frequency_limits: Option<&Option<(u64, u64)>>,
+) {
+    // the same time window as the usage graphs, one averaged point per bucket
+    let frequency_history: VecDeque<f32> =
+        downsample_history(frequency_history, visible_range, graph_bucket_count(rect))
+            .iter()
+            .map(|bucket| bucket.average)
+            .collect();
+    let max_frequency: f32 = frequency_history
+        .iter()
+        .fold(0.0, |max, value| max.max(*value));
+    let upper_bound: f32 = match frequency_limits {
+        Some(Some((_, maximum))) => (*maximum as f32).max(max_frequency),
+        _ => nice_upper_bound(max_frequency),
+    };
+
+    draw_scaled_ui_graph(
+        rect,
+        ui,
+        &[(&frequency_history, FREQUENCY_LINE_COLOR)],
+        upper_bound,
+        &format_frequency(upper_bound as u64),
+    );
+}
+
 /// Requests a repaint of the GUI after a specified interval.
 ///
 /// Ensures the UI updates regularly for real-time monitoring.
@@ -195,6 +553,8 @@ pub fn render_ui(ctx: &Context, app_monitor: &mut AppMonitor) {
 /// * Parameters
 /// `ctx` The egui context
 pub fn request_repaint(ctx: &Context) {
-    // refreshes the gui every REFRESH_MILLISECONDS milliseconds
-    ctx.request_repaint_after(Duration::from_millis(REFRESH_MILLISECONDS));
+    // refreshes the gui at the configured refresh interval
+    ctx.request_repaint_after(Duration::from_millis(
+        settings_monitoring().refresh_milliseconds,
+    ));
 }
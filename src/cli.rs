@@ -0,0 +1,177 @@
+//! Command line module.
+//!
+//! Parses the command line arguments the application was started with.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::alerts::alert_rule_struct::AlertRule;
+
+pub const USAGE: &str = "\
+Usage: tm [OPTIONS]
+
+Options:
+  --config <FILE>   Read the settings from FILE instead of the default
+                    config file (see the README)
+  --record <FILE>   Record the session to FILE while monitoring live
+  --replay <FILE>   Replay a recorded session instead of monitoring live
+  --export <FILE>   Export the metrics history to FILE on exit,
+                    as JSON if FILE ends in .json and CSV otherwise
+  --metrics-address <ADDRESS>
+                    Serve Prometheus metrics at http://ADDRESS/metrics,
+                    for example --metrics-address 127.0.0.1:9898
+  --headless        Run without a window, printing a summary periodically,
+                    until Ctrl-C is pressed or the duration has passed
+  --tui             Show the monitors in the terminal instead of a window
+  --duration <SECONDS>
+                    Stop a headless run after SECONDS
+  --summary-interval <SECONDS>
+                    Print a headless summary every SECONDS [default: 1]
+  --alert <RULE>    Raise an alert when RULE holds, can be given more than once,
+                    for example --alert 'cpu > 90 for 30s' (see the README)
+  --alert-command <COMMAND>
+                    Run COMMAND through the shell when an alert fires or clears
+  --alert-log <FILE>
+                    Append alerts to FILE
+  -h, --help        Print this help";
+
+/// Structure holding the options given on the command line.
+pub struct CliOptions {
+    pub config_path: Option<PathBuf>,
+    pub record_path: Option<PathBuf>,
+    pub replay_path: Option<PathBuf>,
+    pub export_path: Option<PathBuf>,
+    pub metrics_address: Option<String>,
+    pub headless: bool,
+    pub tui: bool,
+    pub duration: Option<Duration>,
+    pub summary_interval: Duration,
+    pub alert_rules: Vec<AlertRule>,
+    pub alert_command: Option<String>,
+    pub alert_log_path: Option<PathBuf>,
+    pub show_help: bool,
+}
+
+impl CliOptions {
+    /// Creates CliOptions for a plain live session.
+    ///
+    /// * Returns
+    /// CliOptions with every option unset
+    pub fn new() -> CliOptions {
+        CliOptions {
+            config_path: None,
+            record_path: None,
+            replay_path: None,
+            export_path: None,
+            metrics_address: None,
+            headless: false,
+            tui: false,
+            duration: None,
+            summary_interval: Duration::from_secs(1),
+            alert_rules: Vec::new(),
+            alert_command: None,
+            alert_log_path: None,
+            show_help: false,
+        }
+    }
+}
+
+/// Parses the command line arguments.
+///
+/// * Parameters
+/// `arguments` The arguments, without the program name
+///
+/// * Returns
+/// The parsed options, or a message describing the first invalid argument
+pub fn parse_arguments(arguments: impl Iterator<Item = String>) -> Result<CliOptions, String> {
+    let mut cli_options: CliOptions = CliOptions::new();
+    let mut arguments = arguments;
+
+    while let Some(argument) = arguments.next() {
+        match argument.as_str() {
+            "--config" => cli_options.config_path = Some(path_value(&argument, arguments.next())?),
+            "--record" => cli_options.record_path = Some(path_value(&argument, arguments.next())?),
+            "--replay" => cli_options.replay_path = Some(path_value(&argument, arguments.next())?),
+            "--export" => cli_options.export_path = Some(path_value(&argument, arguments.next())?),
+            "--metrics-address" => {
+                cli_options.metrics_address = Some(option_value(&argument, arguments.next())?)
+            }
+            "--headless" => cli_options.headless = true,
+            "--tui" => cli_options.tui = true,
+            "--duration" => {
+                cli_options.duration = Some(seconds_value(&argument, arguments.next())?)
+            }
+            "--summary-interval" => {
+                cli_options.summary_interval = seconds_value(&argument, arguments.next())?
+            }
+            "--alert" => {
+                let rule: String = option_value(&argument, arguments.next())?;
+                let alert_rule: AlertRule = AlertRule::parse(&rule)
+                    .map_err(|message| format!("invalid alert rule '{}': {}", rule, message))?;
+                cli_options.alert_rules.push(alert_rule);
+            }
+            "--alert-command" => {
+                cli_options.alert_command = Some(option_value(&argument, arguments.next())?)
+            }
+            "--alert-log" => {
+                cli_options.alert_log_path = Some(path_value(&argument, arguments.next())?)
+            }
+            "-h" | "--help" => cli_options.show_help = true,
+            _ => return Err(format!("unknown argument '{}'", argument)),
+        }
+    }
+
+    if cli_options.record_path.is_some() && cli_options.replay_path.is_some() {
+        return Err(String::from("--record and --replay can't be used together"));
+    }
+    if cli_options.headless && cli_options.tui {
+        return Err(String::from("--headless and --tui can't be used together"));
+    }
+    if cli_options.duration.is_some() && !cli_options.headless {
+        return Err(String::from("--duration only applies with --headless"));
+    }
+    Ok(cli_options)
+}
+
+/// Takes the value following an option that expects a path.
+///
+/// * Parameters
+/// `option` The option, used in the error message
+/// `value` The argument following the option, if any
+///
+/// * Returns
+/// The path, or a message saying it is missing
+fn path_value(option: &str, value: Option<String>) -> Result<PathBuf, String> {
+    value
+        .map(PathBuf::from)
+        .ok_or_else(|| format!("{} expects a file path", option))
+}
+
+/// Takes the value following an option that expects one.
+///
+/// * Parameters
+/// `option` The option, used in the error message
+/// `value` The argument following the option, if any
+///
+/// * Returns
+/// The value, or a message saying it is missing
+fn option_value(option: &str, value: Option<String>) -> Result<String, String> {
+    value.ok_or_else(|| format!("{} expects a value", option))
+}
+
+/// Takes the value following an option that expects a number of seconds.
+///
+/// * Parameters
+/// `option` The option, used in the error message
+/// `value` The argument following the option, if any
+///
+/// * Returns
+/// The duration, or a message saying the value is missing or not a positive number
+fn seconds_value(option: &str, value: Option<String>) -> Result<Duration, String> {
+    let seconds: f64 = option_value(option, value)?
+        .parse()
+        .ok()
+        .filter(|seconds: &f64| seconds.is_finite() && *seconds > 0.0)
+        .ok_or_else(|| format!("{} expects a positive number of seconds", option))?;
+    Ok(Duration::from_secs_f64(seconds))
+}
@@ -0,0 +1,8 @@
+//! Command structures.
+//!
+//! Defines messages sent from the GUI back to the background workers, and the
+//! outcomes the workers report once a command has been carried out.
+
+pub mod process_command_enum;
+pub mod process_command_outcome_struct;
+pub mod replay_command_enum;
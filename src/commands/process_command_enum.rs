@@ -0,0 +1,98 @@
+//! Process command enum.
+//!
+//! Represents an action the GUI asks the processes worker to perform.
+
+use sysinfo::Signal;
+
+use crate::data::{format::format_core_list, process_key_struct::ProcessKey};
+
+/// Enum holding a command targeting a single process.
+/// Note: the cores of SetAffinity are core indices in ascending order.
+/// Note: ReadEnvironment is answered with a ProcessEnvironment rather than an outcome.
+#[derive(Clone)]
+pub enum ProcessCommand {
+    SendSignal { pid: u32, signal: Signal },
+    SetNice { pid: u32, nice: i32 },
+    SetAffinity { pid: u32, cores: Vec<usize> },
+    ReadEnvironment { process_key: ProcessKey },
+}
+
+impl ProcessCommand {
+    /// Returns the pid of the process the command targets.
+    ///
+    /// * Returns
+    /// The target pid
+    pub fn pid(&self) -> u32 {
+        match self {
+            ProcessCommand::SendSignal { pid, .. }
+            | ProcessCommand::SetNice { pid, .. }
+            | ProcessCommand::SetAffinity { pid, .. } => *pid,
+            ProcessCommand::ReadEnvironment { process_key } => process_key.pid,
+        }
+    }
+
+    /// Describes the command for confirmation dialogs and outcome messages.
+    ///
+    /// * Returns
+    /// A short human readable description, e.g. "send SIGTERM to process 42"
+    pub fn describe(&self) -> String {
+        match self {
+            ProcessCommand::SendSignal { pid, signal } => {
+                format!("send {} to process {}", signal_name(*signal), pid)
+            }
+            ProcessCommand::SetNice { pid, nice } => {
+                format!("set the nice value of process {} to {}", pid, nice)
+            }
+            ProcessCommand::SetAffinity { pid, cores } => {
+                format!("pin process {} to cores {}", pid, format_core_list(cores))
+            }
+            ProcessCommand::ReadEnvironment { process_key } => {
+                format!("read the environment of process {}", process_key.pid)
+            }
+        }
+    }
+}
+
+/// Returns the conventional unix name of a signal.
+///
+/// * Parameters
+/// `signal` The signal to name
+///
+/// * Returns
+/// The signal name, e.g. "SIGTERM"
+pub fn signal_name(signal: Signal) -> &'static str {
+    match signal {
+        Signal::Hangup => "SIGHUP",
+        Signal::Interrupt => "SIGINT",
+        Signal::Quit => "SIGQUIT",
+        Signal::Illegal => "SIGILL",
+        Signal::Trap => "SIGTRAP",
+        Signal::Abort => "SIGABRT",
+        Signal::IOT => "SIGIOT",
+        Signal::Bus => "SIGBUS",
+        Signal::FloatingPointException => "SIGFPE",
+        Signal::Kill => "SIGKILL",
+        Signal::User1 => "SIGUSR1",
+        Signal::Segv => "SIGSEGV",
+        Signal::User2 => "SIGUSR2",
+        Signal::Pipe => "SIGPIPE",
+        Signal::Alarm => "SIGALRM",
+        Signal::Term => "SIGTERM",
+        Signal::Child => "SIGCHLD",
+        Signal::Continue => "SIGCONT",
+        Signal::Stop => "SIGSTOP",
+        Signal::TSTP => "SIGTSTP",
+        Signal::TTIN => "SIGTTIN",
+        Signal::TTOU => "SIGTTOU",
+        Signal::Urgent => "SIGURG",
+        Signal::XCPU => "SIGXCPU",
+        Signal::XFSZ => "SIGXFSZ",
+        Signal::VirtualAlarm => "SIGVTALRM",
+        Signal::Profiling => "SIGPROF",
+        Signal::Winch => "SIGWINCH",
+        Signal::IO => "SIGIO",
+        Signal::Poll => "SIGPOLL",
+        Signal::Power => "SIGPWR",
+        Signal::Sys => "SIGSYS",
+    }
+}
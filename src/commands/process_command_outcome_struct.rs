@@ -0,0 +1,26 @@
+//! Process command outcome structure.
+//!
+//! Represents the result of a ProcessCommand carried out by the processes worker.
+
+use crate::commands::process_command_enum::ProcessCommand;
+
+/// Structure holding the result of a process command.
+///
+/// Contains the command that was attempted and an error message if it failed.
+pub struct ProcessCommandOutcome {
+    pub command: ProcessCommand,
+    pub error: Option<String>,
+}
+
+impl ProcessCommandOutcome {
+    /// Describes the outcome for display in the GUI.
+    ///
+    /// * Returns
+    /// A message stating whether the command succeeded, and why not if it failed
+    pub fn message(&self) -> String {
+        match &self.error {
+            Some(error) => format!("Could not {}: {}", self.command.describe(), error),
+            None => format!("Done: {}", self.command.describe()),
+        }
+    }
+}
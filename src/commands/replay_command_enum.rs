@@ -0,0 +1,11 @@
+//! Replay command enum.
+//!
+//! Represents an action the GUI asks the replayer to perform.
+
+/// Enum holding a command controlling the playback of a recording.
+#[derive(Clone, Copy)]
+pub enum ReplayCommand {
+    Play,
+    Pause,
+    Seek { position_milliseconds: u64 },
+}
@@ -1,8 +1,7 @@
 //! Application variables configuration.
 //!
-//! Defines constants used throughout the application for timing, limits, and sizing.
+//! Defines constants used throughout the application for limits and sizing. The tunable
+//! timing and history values live in the settings module.
 
-pub const REFRESH_MILLISECONDS: u64 = 100;
 pub const CORES_UPPER_LIMIT: usize = 20;
-pub const MAX_LINE_GRAPH_POINTS: usize = 10;
-pub const LAST_INDEX: usize = 9;
+pub const TOP_CONSUMER_COUNT: usize = 5;
@@ -0,0 +1,482 @@
+//! Config file module.
+//!
+//! Loads the settings from a TOML file in the user's config directory, checking
+//! every value and falling back to the default for any that is unusable, and saves
+//! the settings changed in the settings window back to it, keeping the rest of the
+//! file as it was.
+
+use std::fmt::Display;
+use std::fs;
+use std::io::ErrorKind;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+
+use toml_edit::{DocumentMut, Item, Table, Value};
+
+use crate::config::settings::{
+    DEFAULT_RED_LINE_THRESHOLD, DEFAULT_YELLOW_LINE_THRESHOLD, HISTORY_POINTS_RANGE,
+    HISTORY_SECONDS_RANGE, LINE_THICKNESS_RANGE, LINE_THRESHOLD_RANGE, MonitoringSettings,
+    REFRESH_MILLISECONDS_RANGE, SAMPLING_MILLISECONDS_RANGE, SMOOTHING_FACTOR_RANGE, Settings,
+    StyleSettings, WINDOW_SIZE_RANGE, WindowSettings,
+};
+
+pub const CONFIG_DIRECTORY_NAME: &str = "tm";
+pub const CONFIG_FILE_NAME: &str = "config.toml";
+
+/// Finds where the config file is looked for when --config isn't given.
+///
+/// On Linux this is $XDG_CONFIG_HOME/tm/config.toml, or ~/.config/tm/config.toml.
+///
+/// * Returns
+/// The path, or None if the system has no config directory
+pub fn config_file_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|config_directory| {
+        config_directory
+            .join(CONFIG_DIRECTORY_NAME)
+            .join(CONFIG_FILE_NAME)
+    })
+}
+
+/// Loads the settings from a config file.
+///
+/// A missing default config file is not a problem, the defaults are used. An
+/// unreadable or malformed file, or one given with --config that doesn't exist,
+/// is reported and the defaults are used. Values out of range are reported and
+/// replaced by their default one at a time. Unknown keys are reported and ignored,
+/// as saving keeps them in the file.
+///
+/// * Parameters
+/// `config_path` The file given with --config, or None for the default location
+///
+/// * Returns
+/// The settings to use, and a message for every problem found
+pub fn load_settings(config_path: Option<&Path>) -> (Settings, Vec<String>) {
+    let path: PathBuf = match config_path.map(Path::to_path_buf).or_else(config_file_path) {
+        Some(path) => path,
+        None => return (Settings::DEFAULT, Vec::new()),
+    };
+
+    let contents: String = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == ErrorKind::NotFound && config_path.is_none() => {
+            return (Settings::DEFAULT, Vec::new());
+        }
+        Err(e) => {
+            let warning: String = format!(
+                "could not read config file {}: {}; using the default settings",
+                path.display(),
+                e
+            );
+            return (Settings::DEFAULT, vec![warning]);
+        }
+    };
+
+    match toml::from_str::<Settings>(&contents) {
+        Ok(mut settings) => {
+            let mut warnings: Vec<String> = find_unknown_keys(&contents)
+                .into_iter()
+                .map(|key| format!("{} is not a known setting; it is ignored", key))
+                .collect();
+            warnings.extend(validate_settings(&mut settings));
+            let warnings: Vec<String> = warnings
+                .into_iter()
+                .map(|warning| format!("{}: {}", path.display(), warning))
+                .collect();
+            (settings, warnings)
+        }
+        Err(e) => {
+            // toml's message already points at the offending line and key, and ends
+            // in a newline
+            let warning: String = format!(
+                "could not parse config file {}: {}, using the default settings",
+                path.display(),
+                e.to_string().trim_end()
+            );
+            (Settings::DEFAULT, vec![warning])
+        }
+    }
+}
+
+/// Finds the keys of a config file that aren't settings.
+///
+/// * Parameters
+/// `contents` The contents of the config file, already known to parse
+///
+/// * Returns
+/// The unknown keys, as dotted paths such as monitoring.future_option
+fn find_unknown_keys(contents: &str) -> Vec<String> {
+    let (Ok(file_table), Ok(toml::Value::Table(known_table))) = (
+        contents.parse::<toml::Table>(),
+        toml::Value::try_from(Settings::DEFAULT),
+    ) else {
+        return Vec::new();
+    };
+    let mut unknown_keys: Vec<String> = Vec::new();
+    collect_unknown_keys(&file_table, &known_table, "", &mut unknown_keys);
+    unknown_keys
+}
+
+/// Adds the keys of a table missing from the matching table of known settings,
+/// looking inside the sections both have.
+///
+/// * Parameters
+/// `table` The table read from the config file
+/// `known_table` The table of the same section in the default settings
+/// `prefix` The dotted path of the section, empty at the top
+/// `unknown_keys` Where to add the unknown keys
+fn collect_unknown_keys(
+    table: &toml::Table,
+    known_table: &toml::Table,
+    prefix: &str,
+    unknown_keys: &mut Vec<String>,
+) {
+    for (key, value) in table {
+        let dotted_key: String = format!("{}{}", prefix, key);
+        match (known_table.get(key), value) {
+            (None, _) => unknown_keys.push(dotted_key),
+            (Some(toml::Value::Table(known_section)), toml::Value::Table(section)) => {
+                collect_unknown_keys(
+                    section,
+                    known_section,
+                    &format!("{}.", dotted_key),
+                    unknown_keys,
+                );
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Writes the settings to a config file, creating its directory if needed.
+///
+/// The settings are merged into the file already there, so its comments, its layout
+/// and keys this version doesn't know about are kept. Only the values that differ
+/// are rewritten.
+///
+/// * Parameters
+/// `config_path` The file given with --config, or None for the default location
+/// `settings` The settings to write
+///
+/// * Returns
+/// The path written to, or a message saying why the settings couldn't be written
+pub fn save_settings(config_path: Option<&Path>, settings: &Settings) -> Result<PathBuf, String> {
+    let path: PathBuf = config_path
+        .map(Path::to_path_buf)
+        .or_else(config_file_path)
+        .ok_or_else(|| String::from("there is no config directory on this system"))?;
+
+    // a file that isn't valid TOML is left alone rather than overwritten
+    let mut document: DocumentMut = match fs::read_to_string(&path) {
+        Ok(contents) => contents
+            .parse::<DocumentMut>()
+            .map_err(|e| format!("{}: {}", path.display(), e.to_string().trim_end()))?,
+        Err(e) if e.kind() == ErrorKind::NotFound => DocumentMut::new(),
+        Err(e) => return Err(format!("{}: {}", path.display(), e)),
+    };
+    let new_document: DocumentMut = toml::to_string_pretty(settings)
+        .map_err(|e| e.to_string())?
+        .parse::<DocumentMut>()
+        .map_err(|e| e.to_string())?;
+    merge_table(document.as_table_mut(), new_document.as_table());
+
+    if let Some(directory) = path.parent() {
+        fs::create_dir_all(directory).map_err(|e| format!("{}: {}", directory.display(), e))?;
+    }
+    fs::write(&path, document.to_string()).map_err(|e| format!("{}: {}", path.display(), e))?;
+    Ok(path)
+}
+
+/// Copies the values of a TOML table into another, keeping the keys only found in
+/// the target and the comments around the values already there.
+///
+/// * Parameters
+/// `target` The table read from the config file
+/// `source` The table holding the settings to write
+fn merge_table(target: &mut Table, source: &Table) {
+    for (key, source_item) in source.iter() {
+        match (target.get_mut(key), source_item) {
+            (Some(Item::Table(target_table)), Item::Table(source_table)) => {
+                merge_table(target_table, source_table);
+            }
+            (Some(Item::Value(target_value)), Item::Value(source_value)) => {
+                // unchanged values keep their spelling, e.g. 800 rather than 800.0
+                if !same_value(target_value, source_value) {
+                    let mut new_value: Value = source_value.clone();
+                    *new_value.decor_mut() = target_value.decor().clone();
+                    *target_value = new_value;
+                }
+            }
+            _ => {
+                target.insert(key, source_item.clone());
+            }
+        }
+    }
+}
+
+/// Compares two TOML values, ignoring the comments and spaces around them and
+/// whether numbers are written as integers or floats.
+///
+/// * Parameters
+/// `a` The first value
+/// `b` The second value
+///
+/// * Returns
+/// true if both hold the same value
+fn same_value(a: &Value, b: &Value) -> bool {
+    let number = |value: &Value| -> Option<f64> {
+        value
+            .as_float()
+            .or_else(|| value.as_integer().map(|integer| integer as f64))
+    };
+    if let (Some(a), Some(b)) = (number(a), number(b)) {
+        return a == b;
+    }
+    let mut a: Value = a.clone();
+    let mut b: Value = b.clone();
+    a.decor_mut().clear();
+    b.decor_mut().clear();
+    a.to_string() == b.to_string()
+}
+
+/// Checks every setting, replacing each unusable one by its default.
+///
+/// * Parameters
+/// `settings` The settings to check
+///
+/// * Returns
+/// A message for every setting that was replaced
+pub fn validate_settings(settings: &mut Settings) -> Vec<String> {
+    let mut warnings: Vec<String> = Vec::new();
+
+    let monitoring: &mut MonitoringSettings = &mut settings.monitoring;
+    let default_monitoring: MonitoringSettings = MonitoringSettings::DEFAULT;
+    check_range(
+        &mut warnings,
+        "monitoring.refresh_milliseconds",
+        &mut monitoring.refresh_milliseconds,
+        default_monitoring.refresh_milliseconds,
+        REFRESH_MILLISECONDS_RANGE,
+    );
+    check_range(
+        &mut warnings,
+        "monitoring.sampling_milliseconds",
+        &mut monitoring.sampling_milliseconds,
+        default_monitoring.sampling_milliseconds,
+        SAMPLING_MILLISECONDS_RANGE,
+    );
+    check_range(
+        &mut warnings,
+        "monitoring.history_points",
+        &mut monitoring.history_points,
+        default_monitoring.history_points,
+        HISTORY_POINTS_RANGE,
+    );
+    check_range(
+        &mut warnings,
+        "monitoring.history_seconds",
+        &mut monitoring.history_seconds,
+        default_monitoring.history_seconds,
+        HISTORY_SECONDS_RANGE,
+    );
+    check_range(
+        &mut warnings,
+        "monitoring.smoothing_factor",
+        &mut monitoring.smoothing_factor,
+        default_monitoring.smoothing_factor,
+        SMOOTHING_FACTOR_RANGE,
+    );
+
+    let style: &mut StyleSettings = &mut settings.style;
+    let default_style: StyleSettings = StyleSettings::DEFAULT;
+    check_range(
+        &mut warnings,
+        "style.yellow_line_threshold",
+        &mut style.yellow_line_threshold,
+        default_style.yellow_line_threshold,
+        LINE_THRESHOLD_RANGE,
+    );
+    check_range(
+        &mut warnings,
+        "style.red_line_threshold",
+        &mut style.red_line_threshold,
+        default_style.red_line_threshold,
+        LINE_THRESHOLD_RANGE,
+    );
+    if style.yellow_line_threshold >= style.red_line_threshold {
+        warnings.push(format!(
+            "style.yellow_line_threshold ({}) must be below style.red_line_threshold ({}); \
+             using the defaults {} and {}",
+            style.yellow_line_threshold,
+            style.red_line_threshold,
+            DEFAULT_YELLOW_LINE_THRESHOLD,
+            DEFAULT_RED_LINE_THRESHOLD
+        ));
+        style.yellow_line_threshold = DEFAULT_YELLOW_LINE_THRESHOLD;
+        style.red_line_threshold = DEFAULT_RED_LINE_THRESHOLD;
+    }
+    check_range(
+        &mut warnings,
+        "style.green_line_thickness",
+        &mut style.green_line_thickness,
+        default_style.green_line_thickness,
+        LINE_THICKNESS_RANGE,
+    );
+    check_range(
+        &mut warnings,
+        "style.yellow_line_thickness",
+        &mut style.yellow_line_thickness,
+        default_style.yellow_line_thickness,
+        LINE_THICKNESS_RANGE,
+    );
+    check_range(
+        &mut warnings,
+        "style.red_line_thickness",
+        &mut style.red_line_thickness,
+        default_style.red_line_thickness,
+        LINE_THICKNESS_RANGE,
+    );
+
+    let window: &mut WindowSettings = &mut settings.window;
+    let default_window: WindowSettings = WindowSettings::DEFAULT;
+    check_range(
+        &mut warnings,
+        "window.width",
+        &mut window.width,
+        default_window.width,
+        WINDOW_SIZE_RANGE,
+    );
+    check_range(
+        &mut warnings,
+        "window.height",
+        &mut window.height,
+        default_window.height,
+        WINDOW_SIZE_RANGE,
+    );
+
+    warnings
+}
+
+/// Replaces a setting by its default if it is outside the allowed range.
+///
+/// * Parameters
+/// `warnings` Where to add the message if the setting is replaced
+/// `key` The setting's key in the config file, used in the message
+/// `value` The setting
+/// `default` The value to use instead
+/// `range` The allowed range
+fn check_range<T: PartialOrd + Display + Copy>(
+    warnings: &mut Vec<String>,
+    key: &str,
+    value: &mut T,
+    default: T,
+    range: RangeInclusive<T>,
+) {
+    // NaN is never contained in a range, so it is replaced too
+    if !range.contains(value) {
+        warnings.push(format!(
+            "{} = {} is out of range, expected {} to {}; using the default {}",
+            key,
+            value,
+            range.start(),
+            range.end(),
+            default
+        ));
+        *value = default;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn saving_keeps_comments_and_unknown_keys() {
+        let path: PathBuf =
+            std::env::temp_dir().join(format!("tm-config-{}.toml", std::process::id()));
+        let contents: &str = "\
+# written by hand
+[monitoring]
+refresh_milliseconds = 100   # how often the window redraws
+sampling_milliseconds = 500
+future_option = \"kept\"
+
+[window]
+width = 800
+";
+        fs::write(&path, contents).unwrap();
+
+        let mut settings: Settings = Settings::DEFAULT;
+        settings.monitoring.refresh_milliseconds = 250;
+        let saved: Result<PathBuf, String> = save_settings(Some(&path), &settings);
+        let written: String = fs::read_to_string(&path).unwrap();
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(saved, Ok(path.clone()));
+        assert!(written.starts_with(
+            "\
+# written by hand
+[monitoring]
+refresh_milliseconds = 250   # how often the window redraws
+sampling_milliseconds = 500
+future_option = \"kept\"
+"
+        ));
+        // an unchanged value keeps its spelling
+        assert!(written.contains("[window]\nwidth = 800\n"));
+        // sections and keys missing from the file are added
+        assert!(written.contains("history_seconds = 3600"));
+        assert!(written.contains("[panels]"));
+    }
+
+    #[test]
+    fn saved_settings_load_back_with_unknown_keys_reported() {
+        let path: PathBuf =
+            std::env::temp_dir().join(format!("tm-config-reload-{}.toml", std::process::id()));
+        let contents: &str = "\
+[monitoring]
+sampling_milliseconds = 500
+future_option = \"kept\"
+
+[future_section]
+enabled = true
+";
+        fs::write(&path, contents).unwrap();
+
+        let mut settings: Settings = Settings::DEFAULT;
+        settings.monitoring.refresh_milliseconds = 250;
+        settings.processes.filter = String::from("cpu>5");
+        let saved: Result<PathBuf, String> = save_settings(Some(&path), &settings);
+        let (loaded, warnings): (Settings, Vec<String>) = load_settings(Some(&path));
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(saved, Ok(path.clone()));
+        assert!(loaded == settings);
+        assert_eq!(
+            warnings,
+            vec![
+                format!(
+                    "{}: future_section is not a known setting; it is ignored",
+                    path.display()
+                ),
+                format!(
+                    "{}: monitoring.future_option is not a known setting; it is ignored",
+                    path.display()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn saving_leaves_a_malformed_file_alone() {
+        let path: PathBuf =
+            std::env::temp_dir().join(format!("tm-config-malformed-{}.toml", std::process::id()));
+        fs::write(&path, "[monitoring\n").unwrap();
+
+        let saved: Result<PathBuf, String> = save_settings(Some(&path), &Settings::DEFAULT);
+        let written: String = fs::read_to_string(&path).unwrap();
+        let _ = fs::remove_file(&path);
+
+        assert!(saved.is_err());
+        assert_eq!(written, "[monitoring\n");
+    }
+}
@@ -2,8 +2,6 @@
 //!
 //! Defines pixel dimensions and spacing constants for the GUI layout.
 
-pub const APP_WIDTH_PX: f32 = 800.0;
-pub const APP_HEIGHT_PX: f32 = 600.0;
 pub const LEFT_CELL_WIDTH_PX: f32 = 20.0;
 pub const CELL_HEIGHT_PX: f32 = 60.0;
 pub const CELL_CORNER_RADIUS_PX: f32 = 4.0;
@@ -13,3 +11,14 @@ pub const PROGRESS_BAR_SPACING_PX: f32 = 20.0;
 pub const PROGRESS_BAR_WIDTH_PX: f32 = 60.0;
 pub const PROGRESS_BAR_HEIGHT_PX: f32 = 25.0;
 pub const PROGRESS_BAR_ROUNDING_PX: f32 = 0.5;
+pub const PROCESS_PANEL_WIDTH_PX: f32 = 560.0;
+pub const PROCESS_COMMAND_MAX_CHARS: usize = 60;
+pub const PROCESS_TREE_INDENT_PX: f32 = 12.0;
+pub const PROCESS_DETAIL_WIDTH_PX: f32 = 560.0;
+pub const PROCESS_ENVIRONMENT_HEIGHT_PX: f32 = 160.0;
+pub const PROCESS_THREADS_HEIGHT_PX: f32 = 160.0;
+pub const TOP_CONSUMER_NAME_MAX_CHARS: usize = 16;
+pub const DEVICE_LEFT_CELL_WIDTH_PX: f32 = 200.0;
+pub const SPARKLINE_WIDTH_PX: f32 = 80.0;
+pub const SPARKLINE_HEIGHT_PX: f32 = 18.0;
+pub const GRAPH_BUCKET_WIDTH_PX: f32 = 2.0;
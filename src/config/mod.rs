@@ -3,5 +3,7 @@
 //! Contains constants and settings for the application's appearance and behavior.
 
 pub mod app_variables;
+pub mod config_file;
 pub mod layout;
+pub mod settings;
 pub mod style;
@@ -0,0 +1,295 @@
+//! Settings.
+//!
+//! Holds the tunable values of the application. They start out as the defaults
+//! below, are replaced by the config file at startup and can be changed at runtime
+//! from the settings window.
+
+use std::ops::RangeInclusive;
+use std::sync::{RwLock, RwLockReadGuard};
+
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_REFRESH_MILLISECONDS: u64 = 100;
+pub const DEFAULT_SAMPLING_MILLISECONDS: u64 = 500;
+pub const DEFAULT_HISTORY_POINTS: usize = 10;
+pub const DEFAULT_HISTORY_SECONDS: u64 = 3600;
+pub const DEFAULT_SMOOTHING_FACTOR: f64 = 0.4;
+
+pub const DEFAULT_YELLOW_LINE_THRESHOLD: f64 = 50.0;
+pub const DEFAULT_RED_LINE_THRESHOLD: f64 = 80.0;
+pub const DEFAULT_GREEN_LINE_THICKNESS: f64 = 2.0;
+pub const DEFAULT_YELLOW_LINE_THICKNESS: f64 = 2.5;
+pub const DEFAULT_RED_LINE_THICKNESS: f64 = 3.0;
+
+pub const DEFAULT_WINDOW_WIDTH_PX: f64 = 800.0;
+pub const DEFAULT_WINDOW_HEIGHT_PX: f64 = 600.0;
+
+// the values accepted for each setting, by the config file and the settings window
+pub const REFRESH_MILLISECONDS_RANGE: RangeInclusive<u64> = 10..=60_000;
+pub const SAMPLING_MILLISECONDS_RANGE: RangeInclusive<u64> = 50..=60_000;
+pub const HISTORY_POINTS_RANGE: RangeInclusive<usize> = 2..=10_000;
+pub const HISTORY_SECONDS_RANGE: RangeInclusive<u64> = 60..=86_400;
+pub const SMOOTHING_FACTOR_RANGE: RangeInclusive<f64> = 0.01..=1.0;
+pub const LINE_THRESHOLD_RANGE: RangeInclusive<f64> = 0.0..=100.0;
+pub const LINE_THICKNESS_RANGE: RangeInclusive<f64> = 0.5..=20.0;
+pub const WINDOW_SIZE_RANGE: RangeInclusive<f64> = 200.0..=10_000.0;
+
+/// The most points kept per CPU history, an hour at 100ms or five hours at the default
+/// sampling interval. Longer histories are cut short so memory stays bounded.
+pub const MAX_CPU_HISTORY_POINTS: usize = 36_000;
+
+/// The settings in use, read through settings() or the accessor of one section.
+static SETTINGS: RwLock<Settings> = RwLock::new(Settings::DEFAULT);
+
+/// Structure holding every tunable value, one field per section of the config file.
+///
+/// Missing sections and keys keep their defaults, unknown keys are ignored.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub monitoring: MonitoringSettings,
+    pub style: StyleSettings,
+    pub panels: PanelSettings,
+    pub window: WindowSettings,
+    pub processes: ProcessSettings,
+}
+
+/// Structure holding how often the GUI refreshes, how often the workers sample and
+/// how the data is kept.
+///
+/// Note: the CPU graphs keep history_seconds of history and show a chosen part of
+/// it, the other graphs keep the last history_points points.
+/// Note: the CPU history is cut to MAX_CPU_HISTORY_POINTS, which a long history at a
+/// short sampling interval would go past.
+/// Note: the smoothing factor is the weight of the newest value in the EMA.
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MonitoringSettings {
+    pub refresh_milliseconds: u64,
+    pub sampling_milliseconds: u64,
+    pub history_points: usize,
+    pub history_seconds: u64,
+    pub smoothing_factor: f64,
+}
+
+/// Structure holding the CPU usage percentages at which graph lines turn yellow
+/// and red, and the width of the line in each colour.
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StyleSettings {
+    pub yellow_line_threshold: f64,
+    pub red_line_threshold: f64,
+    pub green_line_thickness: f64,
+    pub yellow_line_thickness: f64,
+    pub red_line_thickness: f64,
+}
+
+/// Structure holding which panels of the window are shown.
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PanelSettings {
+    pub memory: bool,
+    pub per_core: bool,
+    pub network: bool,
+    pub disks: bool,
+    pub sensors: bool,
+    pub alerts: bool,
+    pub processes: bool,
+}
+
+/// Structure holding the initial size of the window in pixels.
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WindowSettings {
+    pub width: f64,
+    pub height: f64,
+}
+
+/// Structure holding the state of the process table kept between runs.
+///
+/// Note: the filter is the expression typed above the process table, empty when
+/// every process is shown.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProcessSettings {
+    pub filter: String,
+}
+
+impl Settings {
+    /// The settings used when there is no config file.
+    pub const DEFAULT: Settings = Settings {
+        monitoring: MonitoringSettings::DEFAULT,
+        style: StyleSettings::DEFAULT,
+        panels: PanelSettings::DEFAULT,
+        window: WindowSettings::DEFAULT,
+        processes: ProcessSettings::DEFAULT,
+    };
+}
+
+impl MonitoringSettings {
+    /// The monitoring settings used when the config file doesn't set them.
+    pub const DEFAULT: MonitoringSettings = MonitoringSettings {
+        refresh_milliseconds: DEFAULT_REFRESH_MILLISECONDS,
+        sampling_milliseconds: DEFAULT_SAMPLING_MILLISECONDS,
+        history_points: DEFAULT_HISTORY_POINTS,
+        history_seconds: DEFAULT_HISTORY_SECONDS,
+        smoothing_factor: DEFAULT_SMOOTHING_FACTOR,
+    };
+
+    /// Finds how many points the CPU histories keep, enough for history_seconds of
+    /// snapshots at the sampling interval, up to MAX_CPU_HISTORY_POINTS.
+    ///
+    /// * Returns
+    /// The number of points
+    pub fn cpu_history_points(&self) -> usize {
+        let history_points: u64 =
+            (self.history_seconds * 1000).div_ceil(self.sampling_milliseconds.max(1));
+        (history_points as usize).clamp(2, MAX_CPU_HISTORY_POINTS)
+    }
+
+    /// Finds how much time the CPU histories cover once full.
+    ///
+    /// * Returns
+    /// The time covered in milliseconds, less than history_seconds when the
+    /// histories are cut to MAX_CPU_HISTORY_POINTS
+    pub fn cpu_history_milliseconds(&self) -> u64 {
+        (self.history_seconds * 1000)
+            .min(self.cpu_history_points() as u64 * self.sampling_milliseconds)
+    }
+}
+
+impl StyleSettings {
+    /// The style settings used when the config file doesn't set them.
+    pub const DEFAULT: StyleSettings = StyleSettings {
+        yellow_line_threshold: DEFAULT_YELLOW_LINE_THRESHOLD,
+        red_line_threshold: DEFAULT_RED_LINE_THRESHOLD,
+        green_line_thickness: DEFAULT_GREEN_LINE_THICKNESS,
+        yellow_line_thickness: DEFAULT_YELLOW_LINE_THICKNESS,
+        red_line_thickness: DEFAULT_RED_LINE_THICKNESS,
+    };
+}
+
+impl PanelSettings {
+    /// The panel settings used when the config file doesn't set them, every panel shown.
+    pub const DEFAULT: PanelSettings = PanelSettings {
+        memory: true,
+        per_core: true,
+        network: true,
+        disks: true,
+        sensors: true,
+        alerts: true,
+        processes: true,
+    };
+}
+
+impl WindowSettings {
+    /// The window settings used when the config file doesn't set them.
+    pub const DEFAULT: WindowSettings = WindowSettings {
+        width: DEFAULT_WINDOW_WIDTH_PX,
+        height: DEFAULT_WINDOW_HEIGHT_PX,
+    };
+}
+
+impl ProcessSettings {
+    /// The process settings used when the config file doesn't set them, no filter.
+    pub const DEFAULT: ProcessSettings = ProcessSettings {
+        filter: String::new(),
+    };
+}
+
+impl Default for Settings {
+    fn default() -> Settings {
+        Settings::DEFAULT
+    }
+}
+
+impl Default for MonitoringSettings {
+    fn default() -> MonitoringSettings {
+        MonitoringSettings::DEFAULT
+    }
+}
+
+impl Default for StyleSettings {
+    fn default() -> StyleSettings {
+        StyleSettings::DEFAULT
+    }
+}
+
+impl Default for PanelSettings {
+    fn default() -> PanelSettings {
+        PanelSettings::DEFAULT
+    }
+}
+
+impl Default for WindowSettings {
+    fn default() -> WindowSettings {
+        WindowSettings::DEFAULT
+    }
+}
+
+impl Default for ProcessSettings {
+    fn default() -> ProcessSettings {
+        ProcessSettings::DEFAULT
+    }
+}
+
+/// Returns the settings in use.
+///
+/// The whole settings are cloned, filter included, so the graphs and workers read
+/// the section they need through settings_monitoring() or settings_style() instead.
+///
+/// * Returns
+/// A copy of the settings
+pub fn settings() -> Settings {
+    read_settings(Settings::clone)
+}
+
+/// Returns the monitoring settings in use, without cloning the other sections.
+///
+/// * Returns
+/// A copy of the monitoring settings
+pub fn settings_monitoring() -> MonitoringSettings {
+    read_settings(|settings| settings.monitoring)
+}
+
+/// Returns the style settings in use, without cloning the other sections.
+///
+/// * Returns
+/// A copy of the style settings
+pub fn settings_style() -> StyleSettings {
+    read_settings(|settings| settings.style)
+}
+
+/// Returns the panel settings in use, without cloning the other sections.
+///
+/// * Returns
+/// A copy of the panel settings
+pub fn settings_panels() -> PanelSettings {
+    read_settings(|settings| settings.panels)
+}
+
+/// Reads part of the settings in use.
+///
+/// * Parameters
+/// `read` Reads the wanted part out of the settings
+///
+/// * Returns
+/// What `read` returned
+fn read_settings<T>(read: impl FnOnce(&Settings) -> T) -> T {
+    // the settings are plain values, so a panic while holding the lock can't
+    // leave them half written
+    let settings: RwLockReadGuard<Settings> = SETTINGS
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    read(&settings)
+}
+
+/// Replaces the settings in use.
+///
+/// * Parameters
+/// `new_settings` The settings to use from now on
+pub fn set_settings(new_settings: Settings) {
+    *SETTINGS
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = new_settings;
+}
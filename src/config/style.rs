@@ -1,17 +1,41 @@
 //! Style configuration.
 //!
-//! Defines constants for visual styling, including line thicknesses and color thresholds.
+//! Defines constants for visual styling. The colour thresholds and their line thicknesses
+//! live in the settings module.
+
+use eframe::egui::Color32;
 
 pub const DOTTED_LINE_LENGTH_PX: f32 = 2.5;
 pub const DOTTED_LINE_GAP_PX: f32 = 5.0;
 pub const RECT_SHRINK_AMNT: f32 = 4.0;
 
-pub const YELLOW_LINE_THRESHOLD: f32 = 50.0;
-pub const RED_LINE_THRESHOLD: f32 = 80.0;
-
-pub const GREEN_LINE_THICKNESS: f32 = 2.0;
-pub const YELLOW_LINE_THICKNESS: f32 = 2.5;
-pub const RED_LINE_THICKNESS: f32 = 3.0;
-
 pub const EMA_GRAPH_OPACITY: u8 = 200;
+pub const BUCKET_RANGE_OPACITY: u8 = 60;
 pub const HALF_OPACITY: u8 = 128;
+pub const FULL_OPACITY: u8 = 255;
+
+pub const SCALED_LINE_THICKNESS: f32 = 2.0;
+pub const AXIS_LABEL_FONT_SIZE: f32 = 10.0;
+pub const RECEIVED_LINE_COLOR: Color32 = Color32::from_rgb(80, 160, 255);
+pub const TRANSMITTED_LINE_COLOR: Color32 = Color32::from_rgb(255, 160, 60);
+pub const READ_LINE_COLOR: Color32 = Color32::from_rgb(80, 200, 160);
+pub const WRITE_LINE_COLOR: Color32 = Color32::from_rgb(220, 100, 220);
+pub const SENSOR_LINE_COLOR: Color32 = Color32::from_rgb(255, 200, 80);
+pub const CRITICAL_SENSOR_COLOR: Color32 = Color32::RED;
+pub const FREQUENCY_LINE_COLOR: Color32 = Color32::from_rgb(160, 120, 255);
+// fill colours of the cpu time states, in the order of CpuTimesRecord::BUSY_STATES
+pub const CPU_TIME_COLORS: [Color32; 8] = [
+    Color32::from_rgb(80, 160, 255),
+    Color32::from_rgb(120, 200, 255),
+    Color32::from_rgb(255, 90, 90),
+    Color32::from_rgb(255, 200, 60),
+    Color32::from_rgb(200, 120, 255),
+    Color32::from_rgb(255, 140, 220),
+    Color32::from_rgb(150, 150, 150),
+    Color32::from_rgb(90, 210, 120),
+];
+pub const LOAD_LINE_COLOR: Color32 = Color32::from_rgb(80, 160, 255);
+pub const PRESSURE_LINE_COLOR: Color32 = Color32::from_rgb(255, 120, 80);
+pub const PROCESS_LINE_COLOR: Color32 = Color32::from_rgb(120, 200, 255);
+pub const ALERT_BANNER_COLOR: Color32 = Color32::from_rgb(160, 30, 30);
+pub const ALERT_CLEARED_COLOR: Color32 = Color32::from_rgb(90, 210, 120);
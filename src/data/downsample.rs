@@ -0,0 +1,54 @@
+//! Downsampling.
+//!
+//! Reduces part of a long history to at most a given number of buckets, so the
+//! cost of drawing a graph depends on its width rather than on how much is shown.
+
+use std::collections::VecDeque;
+use std::ops::Range;
+
+use crate::data::history_bucket_struct::HistoryBucket;
+
+/// Downsamples a range of a history into buckets of consecutive points.
+///
+/// When there are no more points than buckets every point gets its own bucket,
+/// otherwise the points are shared out as evenly as possible.
+///
+/// * Parameters
+/// `history` The history to downsample
+/// `visible_range` The indices of the points to include, cut to the history's length
+/// `max_buckets` The largest number of buckets to return, at least 1
+///
+/// * Returns
+/// The buckets, oldest first
+pub fn downsample_history(
+    history: &VecDeque<f32>,
+    visible_range: Range<usize>,
+    max_buckets: usize,
+) -> Vec<HistoryBucket> {
+    let end: usize = visible_range.end.min(history.len());
+    let start: usize = visible_range.start.min(end);
+    let point_count: usize = end - start;
+    let bucket_count: usize = point_count.min(max_buckets.max(1));
+    let mut points = history.range(start..end).copied();
+
+    let mut buckets: Vec<HistoryBucket> = Vec::with_capacity(bucket_count);
+    for bucket_index in 0..bucket_count {
+        // bucket i holds the points from i * n / b up to (i + 1) * n / b
+        let bucket_size: usize = (bucket_index + 1) * point_count / bucket_count
+            - bucket_index * point_count / bucket_count;
+
+        let Some(first) = points.next() else {
+            break;
+        };
+        let mut bucket: HistoryBucket = HistoryBucket::new(first);
+        let mut sum: f32 = first;
+        for value in points.by_ref().take(bucket_size - 1) {
+            bucket.minimum = bucket.minimum.min(value);
+            bucket.maximum = bucket.maximum.max(value);
+            sum += value;
+        }
+        bucket.average = sum / bucket_size as f32;
+        buckets.push(bucket);
+    }
+    buckets
+}
@@ -2,7 +2,7 @@
 //!
 //! Provides functions to compute exponential moving averages for CPU usage data.
 
-const SMOOTHING_FACTOR: f32 = 0.4;
+use crate::config::settings::settings_monitoring;
 
 /// Calculates the exponential moving average for overall CPU usage.
 /// 
@@ -27,8 +27,8 @@ pub fn get_cpu_exponential_moving_average(previous_ema: Option<f32>, usage: f32)
 /// `previous_emas` Mutable reference to vector of previous EMA values
 /// `usage` Reference to vector of current per-core usage values
 pub fn get_per_core_exponential_moving_average(
-    previous_emas: &mut Vec<Option<f32>>,
-    usage: &Vec<f32>,
+    previous_emas: &mut [Option<f32>],
+    usage: &[f32],
 ) {
     for (index, ema) in previous_emas.iter_mut().enumerate() {
         *ema = match *ema {
@@ -47,5 +47,6 @@ pub fn get_per_core_exponential_moving_average(
 /// * Returns
 /// The calculated EMA
 pub fn calculate_exponential_moving_average(previous_ema: f32, usage: f32) -> f32 {
-    usage * SMOOTHING_FACTOR + previous_ema * (1 as f32 - SMOOTHING_FACTOR)
+    let smoothing_factor: f32 = settings_monitoring().smoothing_factor as f32;
+    usage * smoothing_factor + previous_ema * (1.0 - smoothing_factor)
 }
@@ -0,0 +1,143 @@
+//! Formatting utilities.
+//!
+//! Provides functions to turn raw byte counts and timestamps into readable strings.
+
+const BYTE_UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Formats a byte count using binary units.
+///
+/// * Parameters
+/// `bytes` The number of bytes
+///
+/// * Returns
+/// The formatted string, e.g. "12.3 MiB"
+pub fn format_bytes(bytes: u64) -> String {
+    let (divisor, unit): (f64, &str) = byte_unit(bytes as f64);
+    if divisor == 1.0 {
+        format!("{} {}", bytes, unit)
+    } else {
+        format!("{:.1} {}", bytes as f64 / divisor, unit)
+    }
+}
+
+/// Formats a transfer rate using binary units.
+///
+/// * Parameters
+/// `bytes_per_second` The rate in bytes per second
+///
+/// * Returns
+/// The formatted string, e.g. "1.5 MiB/s"
+pub fn format_byte_rate(bytes_per_second: f64) -> String {
+    let (divisor, unit): (f64, &str) = byte_unit(bytes_per_second);
+    format!("{:.1} {}/s", bytes_per_second / divisor, unit)
+}
+
+/// Chooses the largest binary unit a byte count can be displayed in.
+///
+/// * Parameters
+/// `bytes` The number of bytes
+///
+/// * Returns
+/// The number of bytes in the unit, and the unit's name
+pub fn byte_unit(bytes: f64) -> (f64, &'static str) {
+    let mut divisor: f64 = 1.0;
+    let mut unit_index: usize = 0;
+    while bytes / divisor >= 1024.0 && unit_index < BYTE_UNITS.len() - 1 {
+        divisor *= 1024.0;
+        unit_index += 1;
+    }
+    (divisor, BYTE_UNITS[unit_index])
+}
+
+/// Formats a CPU frequency.
+///
+/// * Parameters
+/// `megahertz` The frequency in MHz
+///
+/// * Returns
+/// The formatted string, e.g. "3.20 GHz", or "800 MHz" below 1 GHz
+pub fn format_frequency(megahertz: u64) -> String {
+    if megahertz >= 1000 {
+        format!("{:.2} GHz", megahertz as f64 / 1000.0)
+    } else {
+        format!("{} MHz", megahertz)
+    }
+}
+
+/// Formats seconds since the unix epoch as a UTC date and time.
+///
+/// * Parameters
+/// `seconds` Seconds since the unix epoch
+///
+/// * Returns
+/// The formatted string, e.g. "2024-01-31 13:05:09"
+pub fn format_timestamp(seconds: u64) -> String {
+    let days: i64 = (seconds / SECONDS_PER_DAY) as i64;
+    let seconds_of_day: u64 = seconds % SECONDS_PER_DAY;
+
+    // converts days since the epoch into a civil date (Howard Hinnant's algorithm)
+    let shifted_days: i64 = days + 719_468;
+    let era: i64 = shifted_days.div_euclid(146_097);
+    let day_of_era: i64 = shifted_days.rem_euclid(146_097);
+    let year_of_era: i64 =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year: i64 = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index: i64 = (5 * day_of_year + 2) / 153;
+    let day: i64 = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month: i64 = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year: i64 = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+        year,
+        month,
+        day,
+        seconds_of_day / 3_600,
+        (seconds_of_day % 3_600) / 60,
+        seconds_of_day % 60
+    )
+}
+
+/// Formats milliseconds since the unix epoch as a UTC date and time to the millisecond.
+///
+/// * Parameters
+/// `milliseconds` Milliseconds since the unix epoch
+///
+/// * Returns
+/// The formatted string, e.g. "2024-01-31 13:05:09.250"
+pub fn format_timestamp_milliseconds(milliseconds: u64) -> String {
+    format!(
+        "{}.{:03}",
+        format_timestamp(milliseconds / 1000),
+        milliseconds % 1000
+    )
+}
+
+/// Formats a list of core indices, joining consecutive cores into ranges.
+///
+/// * Parameters
+/// `cores` The core indices, in ascending order
+///
+/// * Returns
+/// The formatted string, e.g. "0-3,6"
+pub fn format_core_list(cores: &[usize]) -> String {
+    let mut ranges: Vec<String> = Vec::new();
+    let mut index: usize = 0;
+    while index < cores.len() {
+        let first: usize = cores[index];
+        while index + 1 < cores.len() && cores[index + 1] == cores[index] + 1 {
+            index += 1;
+        }
+        match cores[index] == first {
+            true => ranges.push(first.to_string()),
+            false => ranges.push(format!("{}-{}", first, cores[index])),
+        }
+        index += 1;
+    }
+    ranges.join(",")
+}
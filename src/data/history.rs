@@ -0,0 +1,41 @@
+//! Rolling history utilities.
+//!
+//! Provides functions for maintaining the fixed-length histories plotted by the graphs.
+
+use std::collections::VecDeque;
+
+use crate::config::settings::settings_monitoring;
+
+/// Adds a data point to a history, removing the oldest ones if the maximum is exceeded.
+///
+/// * Parameters
+/// `history` The history to add to
+/// `value` The value to add
+pub fn history_add_point<T>(history: &mut VecDeque<T>, value: T) {
+    history_add_point_with_limit(history, value, settings_monitoring().history_points);
+}
+
+/// Adds a data point to a history, removing the oldest ones beyond a given maximum.
+///
+/// * Parameters
+/// `history` The history to add to
+/// `value` The value to add
+/// `max_points` The number of points to keep
+pub fn history_add_point_with_limit<T>(history: &mut VecDeque<T>, value: T, max_points: usize) {
+    history.push_back(value);
+    history_trim(history, max_points);
+}
+
+/// Removes the oldest data points of a history until it has at most `max_points`.
+///
+/// More than one point is removed when the history length was lowered in the
+/// settings since the history was last added to.
+///
+/// * Parameters
+/// `history` The history to trim
+/// `max_points` The number of points to keep
+pub fn history_trim<T>(history: &mut VecDeque<T>, max_points: usize) {
+    while history.len() > max_points {
+        history.pop_front();
+    }
+}
@@ -0,0 +1,30 @@
+//! History bucket structure.
+//!
+//! Summarizes consecutive points of a history that are drawn as one.
+
+/// Structure holding the smallest, largest and mean value of a run of points.
+///
+/// A bucket of a single point has all three equal to it.
+#[derive(Clone, Copy)]
+pub struct HistoryBucket {
+    pub minimum: f32,
+    pub maximum: f32,
+    pub average: f32,
+}
+
+impl HistoryBucket {
+    /// Creates a bucket holding a single point.
+    ///
+    /// * Parameters
+    /// `value` The point
+    ///
+    /// * Returns
+    /// HistoryBucket structure
+    pub fn new(value: f32) -> HistoryBucket {
+        HistoryBucket {
+            minimum: value,
+            maximum: value,
+            average: value,
+        }
+    }
+}
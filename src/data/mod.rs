@@ -1,5 +1,14 @@
 //! Data processing module.
 //!
-//! Contains utilities for data analysis and smoothing.
+//! Contains utilities for data analysis, smoothing, downsampling and formatting.
 
+pub mod downsample;
 pub mod exponential_moving_average;
+pub mod format;
+pub mod history;
+pub mod history_bucket_struct;
+pub mod process_history_struct;
+pub mod process_key_struct;
+pub mod process_tree;
+pub mod time;
+pub mod top_consumer_struct;
@@ -0,0 +1,84 @@
+//! Process history structure.
+//!
+//! Holds the rolling histories of a single process's resource usage.
+
+use std::collections::VecDeque;
+
+use crate::{
+    data::history::history_add_point_with_limit, snapshots::process_record_struct::ProcessRecord,
+};
+
+/// Structure holding one history per resource a process uses.
+///
+/// Note: memory sizes are in bytes, disk usage in bytes per second.
+/// Note: the thread and open file histories only grow while the counts can be read.
+pub struct ProcessHistory {
+    pub cpu_usage: VecDeque<f32>,
+    pub resident_memory: VecDeque<f32>,
+    pub virtual_memory: VecDeque<f32>,
+    pub thread_count: VecDeque<f32>,
+    pub open_file_count: VecDeque<f32>,
+    pub disk_read_bytes_per_second: VecDeque<f32>,
+    pub disk_written_bytes_per_second: VecDeque<f32>,
+}
+
+impl ProcessHistory {
+    /// Constructor for the ProcessHistory structure.
+    ///
+    /// * Returns
+    /// ProcessHistory with every history empty
+    pub fn new() -> ProcessHistory {
+        ProcessHistory {
+            cpu_usage: VecDeque::new(),
+            resident_memory: VecDeque::new(),
+            virtual_memory: VecDeque::new(),
+            thread_count: VecDeque::new(),
+            open_file_count: VecDeque::new(),
+            disk_read_bytes_per_second: VecDeque::new(),
+            disk_written_bytes_per_second: VecDeque::new(),
+        }
+    }
+
+    /// Adds the values of a process record to the histories.
+    ///
+    /// * Parameters
+    /// `process` The latest record of the process
+    /// `history_points` The number of points to keep in each history
+    pub fn process_history_add_record(&mut self, process: &ProcessRecord, history_points: usize) {
+        history_add_point_with_limit(&mut self.cpu_usage, process.cpu_usage, history_points);
+        history_add_point_with_limit(
+            &mut self.resident_memory,
+            process.memory as f32,
+            history_points,
+        );
+        history_add_point_with_limit(
+            &mut self.virtual_memory,
+            process.virtual_memory as f32,
+            history_points,
+        );
+        if let Some(thread_count) = process.thread_count {
+            history_add_point_with_limit(
+                &mut self.thread_count,
+                thread_count as f32,
+                history_points,
+            );
+        }
+        if let Some(open_file_count) = process.open_file_count {
+            history_add_point_with_limit(
+                &mut self.open_file_count,
+                open_file_count as f32,
+                history_points,
+            );
+        }
+        history_add_point_with_limit(
+            &mut self.disk_read_bytes_per_second,
+            process.disk_read_bytes_per_second as f32,
+            history_points,
+        );
+        history_add_point_with_limit(
+            &mut self.disk_written_bytes_per_second,
+            process.disk_written_bytes_per_second as f32,
+            history_points,
+        );
+    }
+}
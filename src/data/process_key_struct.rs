@@ -0,0 +1,31 @@
+//! Process key structure.
+//!
+//! Identifies a process across snapshots, even once its pid has been reused.
+
+use crate::snapshots::process_record_struct::ProcessRecord;
+
+/// Structure holding what tells one process apart from every other.
+///
+/// A pid can be given to a new process once the old one exits, but the new
+/// process starts later, so the pair of pid and start time stays unique.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ProcessKey {
+    pub pid: u32,
+    pub start_time: u64,
+}
+
+impl ProcessKey {
+    /// Creates the key of a process record.
+    ///
+    /// * Parameters
+    /// `process` The process record
+    ///
+    /// * Returns
+    /// ProcessKey structure
+    pub fn from_record(process: &ProcessRecord) -> ProcessKey {
+        ProcessKey {
+            pid: process.pid,
+            start_time: process.start_time,
+        }
+    }
+}
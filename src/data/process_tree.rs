@@ -0,0 +1,157 @@
+//! Process tree construction.
+//!
+//! Provides functions to arrange a flat list of processes into a parent/child
+//! hierarchy with per-subtree totals.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::snapshots::process_record_struct::ProcessRecord;
+
+/// Structure holding one visible row of the process tree.
+///
+/// Contains the position of the process in the flat list, its depth in the tree
+/// and the totals of the subtree rooted at it.
+pub struct ProcessTreeRow {
+    pub index: usize,
+    pub depth: usize,
+    pub has_children: bool,
+    pub subtree_cpu_usage: f32,
+    pub subtree_memory: u64,
+}
+
+/// Builds the visible rows of the process tree.
+///
+/// Processes whose parent is missing from the list (orphans, or children of
+/// processes that have exited) become roots. Siblings keep the order they have in
+/// `processes`, so a sorted list produces a tree sorted within each parent.
+/// The children of collapsed processes are left out of the rows, but still count
+/// towards the totals of their ancestors.
+///
+/// * Parameters
+/// `processes` The flat list of processes
+/// `collapsed_pids` The pids of processes whose children are hidden
+///
+/// * Returns
+/// The visible rows in display order
+pub fn build_process_tree(
+    processes: &[ProcessRecord],
+    collapsed_pids: &HashSet<u32>,
+) -> Vec<ProcessTreeRow> {
+    let index_by_pid: HashMap<u32, usize> = processes
+        .iter()
+        .enumerate()
+        .map(|(index, process)| (process.pid, index))
+        .collect();
+
+    let mut roots: Vec<usize> = Vec::new();
+    let mut children: Vec<Vec<usize>> = vec![Vec::new(); processes.len()];
+    for (index, process) in processes.iter().enumerate() {
+        match process
+            .parent_pid
+            .filter(|parent_pid| *parent_pid != process.pid)
+            .and_then(|parent_pid| index_by_pid.get(&parent_pid))
+        {
+            Some(parent_index) => children[*parent_index].push(index),
+            None => roots.push(index),
+        }
+    }
+
+    let mut subtree_cpu_usage: Vec<f32> = vec![0.0; processes.len()];
+    let mut subtree_memory: Vec<u64> = vec![0; processes.len()];
+    let mut visited: Vec<bool> = vec![false; processes.len()];
+    for root in &roots {
+        add_subtree_totals(
+            *root,
+            processes,
+            &children,
+            &mut visited,
+            &mut subtree_cpu_usage,
+            &mut subtree_memory,
+        );
+    }
+
+    // processes left unvisited are part of a parent cycle (possible when pids are
+    // reused between refreshes), they are shown as roots rather than dropped
+    for index in 0..processes.len() {
+        if !visited[index] {
+            roots.push(index);
+            add_subtree_totals(
+                index,
+                processes,
+                &children,
+                &mut visited,
+                &mut subtree_cpu_usage,
+                &mut subtree_memory,
+            );
+        }
+    }
+
+    let mut rows: Vec<ProcessTreeRow> = Vec::with_capacity(processes.len());
+    let mut emitted: Vec<bool> = vec![false; processes.len()];
+    // walks the tree depth first, pushing children in reverse to keep their order
+    let mut stack: Vec<(usize, usize)> = roots.iter().rev().map(|root| (*root, 0)).collect();
+    while let Some((index, depth)) = stack.pop() {
+        if emitted[index] {
+            continue;
+        }
+        emitted[index] = true;
+
+        rows.push(ProcessTreeRow {
+            index,
+            depth,
+            has_children: !children[index].is_empty(),
+            subtree_cpu_usage: subtree_cpu_usage[index],
+            subtree_memory: subtree_memory[index],
+        });
+
+        if !collapsed_pids.contains(&processes[index].pid) {
+            for child in children[index].iter().rev() {
+                stack.push((*child, depth + 1));
+            }
+        }
+    }
+
+    rows
+}
+
+/// Sums the cpu usage and memory of every process in a subtree.
+///
+/// Uses an explicit stack so deep process chains can't overflow the call stack.
+///
+/// * Parameters
+/// `root` The index of the subtree's root process
+/// `processes` The flat list of processes
+/// `children` The child indices of every process
+/// `visited` Marks processes that have already been summed
+/// `subtree_cpu_usage` The cpu usage totals, written for every process in the subtree
+/// `subtree_memory` The memory totals, written for every process in the subtree
+fn add_subtree_totals(
+    root: usize,
+    processes: &[ProcessRecord],
+    children: &[Vec<usize>],
+    visited: &mut [bool],
+    subtree_cpu_usage: &mut [f32],
+    subtree_memory: &mut [u64],
+) {
+    let mut order: Vec<usize> = Vec::new();
+    let mut stack: Vec<usize> = vec![root];
+    while let Some(index) = stack.pop() {
+        if visited[index] {
+            continue;
+        }
+        visited[index] = true;
+        order.push(index);
+        stack.extend(children[index].iter().copied());
+    }
+
+    // children always come after their parent in `order`, so walking it backwards
+    // totals every child before its parent
+    for index in order.into_iter().rev() {
+        subtree_cpu_usage[index] += processes[index].cpu_usage;
+        subtree_memory[index] += processes[index].memory;
+        for child in &children[index] {
+            subtree_cpu_usage[index] += subtree_cpu_usage[*child];
+            subtree_memory[index] += subtree_memory[*child];
+        }
+    }
+}
@@ -0,0 +1,16 @@
+//! Time utilities.
+//!
+//! Provides functions for timestamping data.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Returns the current time as milliseconds since the unix epoch.
+///
+/// * Returns
+/// The current time, or 0 if the system clock is set before 1970
+pub fn unix_time_milliseconds() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or(0)
+}
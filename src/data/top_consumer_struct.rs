@@ -0,0 +1,32 @@
+//! Top consumer structure.
+//!
+//! Represents one of the processes using the most CPU at a sample.
+
+use crate::snapshots::process_record_struct::ProcessRecord;
+
+/// Structure holding a process using the most CPU when a CpuSnapshot arrived.
+///
+/// Note: cpu usage is in percent of one core, as in the process table.
+#[derive(Clone)]
+pub struct TopConsumer {
+    pub pid: u32,
+    pub name: String,
+    pub cpu_usage: f32,
+}
+
+impl TopConsumer {
+    /// Creates the top consumer entry of a process record.
+    ///
+    /// * Parameters
+    /// `process` The process record
+    ///
+    /// * Returns
+    /// TopConsumer structure
+    pub fn from_record(process: &ProcessRecord) -> TopConsumer {
+        TopConsumer {
+            pid: process.pid,
+            name: process.name.clone(),
+            cpu_usage: process.cpu_usage,
+        }
+    }
+}
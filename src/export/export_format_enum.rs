@@ -0,0 +1,39 @@
+//! Export format enum.
+//!
+//! Represents the file formats the metrics history can be exported to.
+
+use std::path::Path;
+
+/// Enum holding a file format for exported metrics.
+#[derive(Clone, Copy, PartialEq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    /// Chooses the format from the extension of a path, CSV unless it ends in ".json".
+    ///
+    /// * Parameters
+    /// `path` The path to export to
+    ///
+    /// * Returns
+    /// The format to write
+    pub fn from_path(path: &Path) -> ExportFormat {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some(extension) if extension.eq_ignore_ascii_case("json") => ExportFormat::Json,
+            _ => ExportFormat::Csv,
+        }
+    }
+
+    /// Returns the file extension of the format.
+    ///
+    /// * Returns
+    /// The extension, without the dot
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+        }
+    }
+}
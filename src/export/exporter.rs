@@ -0,0 +1,101 @@
+//! Exporter module.
+//!
+//! Writes a MetricsExport to a file as CSV or JSON.
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use crate::{
+    data::time::unix_time_milliseconds,
+    export::{export_format_enum::ExportFormat, metrics_export_struct::MetricsExport},
+};
+
+/// Writes the metrics to a file, in the format given by its extension.
+///
+/// * Parameters
+/// `metrics_export` The metrics to write
+/// `path` The file to create or overwrite
+///
+/// * Returns
+/// An error if the file couldn't be written
+pub fn write_metrics_export(metrics_export: &MetricsExport, path: &Path) -> io::Result<()> {
+    let mut writer: BufWriter<File> = BufWriter::new(File::create(path)?);
+    match ExportFormat::from_path(path) {
+        ExportFormat::Csv => write_metrics_csv(metrics_export, &mut writer)?,
+        ExportFormat::Json => serde_json::to_writer_pretty(&mut writer, metrics_export)?,
+    }
+    writer.flush()
+}
+
+/// Writes the metrics as CSV, one row per sample.
+///
+/// The per-core columns come last, as a usage and EMA column for each core.
+///
+/// * Parameters
+/// `metrics_export` The metrics to write
+/// `writer` Where to write the CSV
+///
+/// * Returns
+/// An error if writing failed
+fn write_metrics_csv(metrics_export: &MetricsExport, writer: &mut impl Write) -> io::Result<()> {
+    let core_count: usize = metrics_export
+        .samples
+        .iter()
+        .map(|sample| sample.per_core_cpu_usage.len())
+        .max()
+        .unwrap_or(0);
+
+    write!(
+        writer,
+        "timestamp_milliseconds,overall_cpu_usage,overall_ema_cpu_usage,average_cpu_usage,process_count"
+    )?;
+    for core in 0..core_count {
+        write!(
+            writer,
+            ",core_{}_cpu_usage,core_{}_ema_cpu_usage",
+            core, core
+        )?;
+    }
+    writeln!(writer)?;
+
+    for sample in &metrics_export.samples {
+        write!(
+            writer,
+            "{},{:.2},{:.2},{:.2},{}",
+            sample.timestamp_milliseconds,
+            sample.overall_cpu_usage,
+            sample.overall_ema_cpu_usage,
+            sample.average_cpu_usage,
+            sample.process_count
+        )?;
+        for core in 0..core_count {
+            // cores missing from a sample are left as empty cells
+            write!(writer, ",")?;
+            if let Some(usage) = sample.per_core_cpu_usage.get(core) {
+                write!(writer, "{:.2}", usage)?;
+            }
+            write!(writer, ",")?;
+            if let Some(ema) = sample.per_core_ema_cpu_usage.get(core) {
+                write!(writer, "{:.2}", ema)?;
+            }
+        }
+        writeln!(writer)?;
+    }
+    Ok(())
+}
+
+/// Builds a file name in the current directory for an export made now.
+///
+/// * Parameters
+/// `export_format` The format of the export
+///
+/// * Returns
+/// A path such as "cpu-monitor-1760000000000.csv"
+pub fn default_export_path(export_format: ExportFormat) -> PathBuf {
+    PathBuf::from(format!(
+        "cpu-monitor-{}.{}",
+        unix_time_milliseconds(),
+        export_format.extension()
+    ))
+}
@@ -0,0 +1,15 @@
+//! Metrics export structure.
+//!
+//! Represents everything written to an export file.
+
+use serde::Serialize;
+
+use crate::export::metrics_sample_struct::MetricsSample;
+
+/// Structure holding the exported history, oldest sample first.
+#[derive(Serialize)]
+pub struct MetricsExport {
+    pub exported_at_milliseconds: u64,
+    pub average_cpu_usage: f32,
+    pub samples: Vec<MetricsSample>,
+}
@@ -0,0 +1,20 @@
+//! Metrics sample structure.
+//!
+//! Represents the metrics exported for a single CPU snapshot.
+
+use serde::Serialize;
+
+/// Structure holding the CPU usage and process count at one point in time.
+///
+/// Usages are percentages, the average is the running average since the
+/// application started.
+#[derive(Serialize)]
+pub struct MetricsSample {
+    pub timestamp_milliseconds: u64,
+    pub overall_cpu_usage: f32,
+    pub overall_ema_cpu_usage: f32,
+    pub average_cpu_usage: f32,
+    pub process_count: usize,
+    pub per_core_cpu_usage: Vec<f32>,
+    pub per_core_ema_cpu_usage: Vec<f32>,
+}
@@ -0,0 +1,9 @@
+//! Metrics export.
+//!
+//! Writes the history held by the CPU monitor to CSV or JSON files that can be
+//! shared outside the application.
+
+pub mod export_format_enum;
+pub mod exporter;
+pub mod metrics_export_struct;
+pub mod metrics_sample_struct;
@@ -0,0 +1,246 @@
+//! Filter term enum.
+//!
+//! Represents one condition of a process filter expression.
+
+use crate::snapshots::process_record_struct::ProcessRecord;
+
+/// Enum holding a single condition a process has to meet to match a filter.
+///
+/// Note: text, user and state terms are stored lowercased.
+/// Note: memory thresholds are in bytes, CPU thresholds in percent.
+#[derive(Clone, PartialEq)]
+pub enum FilterTerm {
+    Text(String),
+    User(String),
+    State(String),
+    Cpu { above: bool, threshold: f32 },
+    Memory { above: bool, threshold: u64 },
+}
+
+impl FilterTerm {
+    /// Parses a word of a filter expression.
+    ///
+    /// `user:NAME` and `state:STATE` match the owner and state, `cpu>N`, `cpu<N`,
+    /// `mem>SIZE` and `mem<SIZE` compare the usage, and any other word is looked
+    /// for in the name and command line.
+    ///
+    /// * Parameters
+    /// `word` The word, without whitespace
+    ///
+    /// * Returns
+    /// The term, or a message describing what is wrong with it
+    pub fn parse(word: &str) -> Result<FilterTerm, String> {
+        if let Some(user) = word.strip_prefix("user:") {
+            return Ok(FilterTerm::User(
+                non_empty_value("user:", user)?.to_lowercase(),
+            ));
+        }
+        if let Some(state) = word.strip_prefix("state:") {
+            return Ok(FilterTerm::State(
+                non_empty_value("state:", state)?.to_lowercase(),
+            ));
+        }
+        for (prefix, above) in [("cpu>", true), ("cpu<", false)] {
+            if let Some(threshold) = word.strip_prefix(prefix) {
+                let threshold: f32 = threshold
+                    .trim_end_matches('%')
+                    .parse()
+                    .ok()
+                    .filter(|threshold: &f32| threshold.is_finite())
+                    .ok_or_else(|| format!("'{}' expects a percentage, such as cpu>5", prefix))?;
+                return Ok(FilterTerm::Cpu { above, threshold });
+            }
+        }
+        for (prefix, above) in [("mem>", true), ("mem<", false)] {
+            if let Some(threshold) = word.strip_prefix(prefix) {
+                let threshold: u64 = parse_size(threshold)
+                    .ok_or_else(|| format!("'{}' expects a size, such as mem>1G", prefix))?;
+                return Ok(FilterTerm::Memory { above, threshold });
+            }
+        }
+        Ok(FilterTerm::Text(word.to_lowercase()))
+    }
+
+    /// Checks whether a process meets the condition.
+    ///
+    /// * Parameters
+    /// `process` The process record to check
+    ///
+    /// * Returns
+    /// true if the process meets the condition
+    pub fn matches(&self, process: &ProcessRecord) -> bool {
+        match self {
+            FilterTerm::Text(text) => {
+                process.name.to_lowercase().contains(text)
+                    || process.command.to_lowercase().contains(text)
+            }
+            FilterTerm::User(user) => process.user.to_lowercase() == *user,
+            FilterTerm::State(state) => process.status.to_lowercase().contains(state),
+            FilterTerm::Cpu {
+                above: true,
+                threshold,
+            } => process.cpu_usage > *threshold,
+            FilterTerm::Cpu {
+                above: false,
+                threshold,
+            } => process.cpu_usage < *threshold,
+            FilterTerm::Memory {
+                above: true,
+                threshold,
+            } => process.memory > *threshold,
+            FilterTerm::Memory {
+                above: false,
+                threshold,
+            } => process.memory < *threshold,
+        }
+    }
+}
+
+/// Takes the value of a `key:value` term, refusing an empty one.
+///
+/// * Parameters
+/// `key` The key, used in the error message
+/// `value` The text after the key
+///
+/// * Returns
+/// The value, or a message saying it is missing
+fn non_empty_value<'a>(key: &str, value: &'a str) -> Result<&'a str, String> {
+    match value.is_empty() {
+        true => Err(format!("'{}' expects a value, such as {}name", key, key)),
+        false => Ok(value),
+    }
+}
+
+/// Parses a size such as "512", "300M", "1.5G" or "2GiB", in binary units.
+///
+/// * Parameters
+/// `text` The size, in bytes when it has no unit
+///
+/// * Returns
+/// The size in bytes, or None if it isn't a size
+fn parse_size(text: &str) -> Option<u64> {
+    let upper: String = text.to_uppercase();
+    // "IB" only follows a unit, as in "2GiB", while a lone "B" may follow a
+    // number of bytes too, as in "512B".
+    let (number, binary_suffix): (&str, bool) = match upper.strip_suffix("IB") {
+        Some(number) => (number, true),
+        None => (upper.strip_suffix('B').unwrap_or(&upper), false),
+    };
+    let (number, multiplier): (&str, f64) = match number.char_indices().last() {
+        Some((index, 'K')) => (&number[..index], 1024.0),
+        Some((index, 'M')) => (&number[..index], 1024.0 * 1024.0),
+        Some((index, 'G')) => (&number[..index], 1024.0 * 1024.0 * 1024.0),
+        Some((index, 'T')) => (&number[..index], 1024.0 * 1024.0 * 1024.0 * 1024.0),
+        _ if binary_suffix => return None,
+        _ => (number, 1.0),
+    };
+
+    number
+        .parse()
+        .ok()
+        .filter(|number: &f64| number.is_finite() && *number >= 0.0)
+        .map(|number| (number * multiplier) as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GIB: u64 = 1024 * 1024 * 1024;
+
+    fn error(word: &str) -> Option<String> {
+        FilterTerm::parse(word).err()
+    }
+
+    #[test]
+    fn parses_sizes_in_binary_units() {
+        assert_eq!(parse_size("512"), Some(512));
+        assert_eq!(parse_size("512B"), Some(512));
+        assert_eq!(parse_size("4K"), Some(4 * 1024));
+        assert_eq!(parse_size("4kb"), Some(4 * 1024));
+        assert_eq!(parse_size("300M"), Some(300 * 1024 * 1024));
+        assert_eq!(parse_size("1.5G"), Some(GIB + GIB / 2));
+        assert_eq!(parse_size("1GiB"), Some(GIB));
+        assert_eq!(parse_size("1gib"), Some(GIB));
+        assert_eq!(parse_size("2T"), Some(2 * 1024 * GIB));
+    }
+
+    #[test]
+    fn rejects_text_that_is_not_a_size() {
+        for text in ["", "G", "abc", "-1G", "1X", "1iB", "1BB", "1GiiB", "inf"] {
+            assert_eq!(parse_size(text), None, "{:?}", text);
+        }
+    }
+
+    #[test]
+    fn parses_cpu_and_memory_comparisons() {
+        assert!(
+            FilterTerm::parse("cpu>5")
+                == Ok(FilterTerm::Cpu {
+                    above: true,
+                    threshold: 5.0
+                })
+        );
+        assert!(
+            FilterTerm::parse("cpu<12.5%")
+                == Ok(FilterTerm::Cpu {
+                    above: false,
+                    threshold: 12.5
+                })
+        );
+        assert!(
+            FilterTerm::parse("mem>1GiB")
+                == Ok(FilterTerm::Memory {
+                    above: true,
+                    threshold: GIB
+                })
+        );
+        assert!(
+            FilterTerm::parse("mem<300M")
+                == Ok(FilterTerm::Memory {
+                    above: false,
+                    threshold: 300 * 1024 * 1024
+                })
+        );
+    }
+
+    #[test]
+    fn rejects_comparisons_without_a_number() {
+        assert_eq!(
+            error("cpu>"),
+            Some("'cpu>' expects a percentage, such as cpu>5".to_string())
+        );
+        assert_eq!(
+            error("cpu<abc"),
+            Some("'cpu<' expects a percentage, such as cpu>5".to_string())
+        );
+        assert_eq!(
+            error("cpu>inf"),
+            Some("'cpu>' expects a percentage, such as cpu>5".to_string())
+        );
+        assert_eq!(
+            error("mem<lots"),
+            Some("'mem<' expects a size, such as mem>1G".to_string())
+        );
+    }
+
+    #[test]
+    fn parses_user_state_and_text_lowercased() {
+        assert!(FilterTerm::parse("user:Alice") == Ok(FilterTerm::User("alice".to_string())));
+        assert!(FilterTerm::parse("state:Zombie") == Ok(FilterTerm::State("zombie".to_string())));
+        assert!(FilterTerm::parse("FireFox") == Ok(FilterTerm::Text("firefox".to_string())));
+        assert!(FilterTerm::parse("cpu=5") == Ok(FilterTerm::Text("cpu=5".to_string())));
+    }
+
+    #[test]
+    fn rejects_user_and_state_without_a_value() {
+        assert_eq!(
+            error("user:"),
+            Some("'user:' expects a value, such as user:name".to_string())
+        );
+        assert_eq!(
+            error("state:"),
+            Some("'state:' expects a value, such as state:name".to_string())
+        );
+    }
+}
@@ -0,0 +1,17 @@
+//! Filtered processes structure.
+//!
+//! Represents a processes snapshot along with the processes matching the filter.
+
+use std::collections::HashSet;
+
+use crate::snapshots::processes_snapshot_struct::ProcessesSnapshot;
+
+/// Structure holding a processes snapshot and the result of filtering it.
+/// Note: matching_pids is None when the filter is empty, so every process matches.
+/// Note: fresh is false when the last snapshot is filtered again after the filter
+/// changed, so it isn't counted as another sample.
+pub struct FilteredProcesses {
+    pub processes_snapshot: ProcessesSnapshot,
+    pub matching_pids: Option<HashSet<u32>>,
+    pub fresh: bool,
+}
@@ -0,0 +1,9 @@
+//! Process filtering.
+//!
+//! Parses the filter expressions typed above the process table, and evaluates them
+//! against each processes snapshot on a thread of their own.
+
+pub mod filter_term_enum;
+pub mod filtered_processes_struct;
+pub mod process_filter_struct;
+pub mod process_filter_worker;
@@ -0,0 +1,124 @@
+//! Process filter structure.
+//!
+//! Represents a parsed filter expression for the process table.
+
+use crate::{
+    filter::filter_term_enum::FilterTerm, snapshots::process_record_struct::ProcessRecord,
+};
+
+/// Structure holding a process filter.
+///
+/// A process matches when it meets every term, so an empty filter matches every
+/// process.
+#[derive(Clone, Default, PartialEq)]
+pub struct ProcessFilter {
+    pub expression: String,
+    pub terms: Vec<FilterTerm>,
+}
+
+impl ProcessFilter {
+    /// Parses a filter such as "firefox" or "user:alice cpu>5 mem>1G state:zombie".
+    ///
+    /// * Parameters
+    /// `text` The filter expression, words separated by whitespace
+    ///
+    /// * Returns
+    /// The filter, or a message describing the first invalid word
+    pub fn parse(text: &str) -> Result<ProcessFilter, String> {
+        let words: Vec<&str> = text.split_whitespace().collect();
+        let terms: Vec<FilterTerm> = words
+            .iter()
+            .map(|word| FilterTerm::parse(word))
+            .collect::<Result<Vec<FilterTerm>, String>>()?;
+
+        Ok(ProcessFilter {
+            expression: words.join(" "),
+            terms,
+        })
+    }
+
+    /// Checks whether the filter lets every process through.
+    ///
+    /// * Returns
+    /// true if the filter has no terms
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// Checks whether a process meets every term of the filter.
+    ///
+    /// * Parameters
+    /// `process` The process record to check
+    ///
+    /// * Returns
+    /// true if the process matches
+    pub fn matches(&self, process: &ProcessRecord) -> bool {
+        self.terms.iter().all(|term| term.matches(process))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn process(name: &str, user: &str, cpu_usage: f32, memory: u64) -> ProcessRecord {
+        ProcessRecord {
+            pid: 42,
+            parent_pid: Some(1),
+            name: name.to_string(),
+            user: user.to_string(),
+            cpu_usage,
+            memory,
+            status: "Sleeping".to_string(),
+            start_time: 0,
+            command: format!("/usr/bin/{} --flag", name),
+            virtual_memory: memory,
+            thread_count: None,
+            open_file_count: None,
+            disk_read_bytes_per_second: 0.0,
+            disk_written_bytes_per_second: 0.0,
+            nice: None,
+            affinity: None,
+        }
+    }
+
+    #[test]
+    fn parses_every_word_and_normalises_whitespace() {
+        let filter: ProcessFilter = ProcessFilter::parse("  user:alice\tcpu>5   mem<1G ").unwrap();
+
+        assert_eq!(filter.expression, "user:alice cpu>5 mem<1G");
+        assert_eq!(filter.terms.len(), 3);
+    }
+
+    #[test]
+    fn empty_filter_matches_every_process() {
+        let filter: ProcessFilter = ProcessFilter::parse(" \t ").unwrap();
+
+        assert!(filter.is_empty());
+        assert!(filter == ProcessFilter::default());
+        assert!(filter.matches(&process("bash", "root", 0.0, 0)));
+    }
+
+    #[test]
+    fn reports_the_first_invalid_word() {
+        assert_eq!(
+            ProcessFilter::parse("firefox cpu>x mem>y").err(),
+            Some("'cpu>' expects a percentage, such as cpu>5".to_string())
+        );
+    }
+
+    #[test]
+    fn matches_only_processes_meeting_every_term() {
+        let filter: ProcessFilter = ProcessFilter::parse("user:Alice cpu>5 mem>1M").unwrap();
+
+        assert!(filter.matches(&process("firefox", "alice", 10.0, 2 * 1024 * 1024)));
+        assert!(!filter.matches(&process("firefox", "bob", 10.0, 2 * 1024 * 1024)));
+        assert!(!filter.matches(&process("firefox", "alice", 5.0, 2 * 1024 * 1024)));
+        assert!(!filter.matches(&process("firefox", "alice", 10.0, 1024 * 1024)));
+        assert!(
+            ProcessFilter::parse("FLAG")
+                .unwrap()
+                .matches(&process("bash", "root", 0.0, 0))
+        );
+    }
+}
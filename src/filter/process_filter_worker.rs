@@ -0,0 +1,105 @@
+//! Process filter worker.
+//!
+//! Background worker evaluating the process filter against each processes snapshot,
+//! so long process lists are filtered without holding up the GUI.
+
+use std::collections::HashSet;
+use std::sync::mpsc::{Receiver, RecvTimeoutError, Sender};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use crate::{
+    channel::Channel,
+    filter::{filtered_processes_struct::FilteredProcesses, process_filter_struct::ProcessFilter},
+    snapshots::processes_snapshot_struct::ProcessesSnapshot,
+};
+
+/// How long the worker waits for a snapshot before checking for a new filter.
+const FILTER_CHECK_MILLISECONDS: u64 = 50;
+
+/// Starts a background worker that filters the snapshots of a channel.
+///
+/// Every snapshot is forwarded with the pids of the processes matching the latest
+/// filter. When the filter changes the last snapshot is filtered again straight
+/// away, so the process table doesn't wait for the next snapshot, and forwarded as
+/// not fresh.
+///
+/// * Parameters
+/// `receiver` The receiver the snapshots arrive on
+/// `process_filter` The filter to start with
+///
+/// * Returns
+/// The receiver for the filtered snapshots, and the sender for new filters
+pub fn filter_channel(
+    receiver: Receiver<ProcessesSnapshot>,
+    process_filter: ProcessFilter,
+) -> (Receiver<FilteredProcesses>, Sender<ProcessFilter>) {
+    let (forward_sender, forward_receiver) = Channel::<FilteredProcesses>::new().split();
+    let (filter_sender, filter_receiver) = Channel::<ProcessFilter>::new().split();
+
+    let _handle: JoinHandle<()> = thread::spawn(move || {
+        let mut process_filter: ProcessFilter = process_filter;
+        let mut latest_snapshot: Option<ProcessesSnapshot> = None;
+
+        loop {
+            let mut changed: bool = false;
+            let mut fresh: bool = false;
+            while let Ok(new_filter) = filter_receiver.try_recv() {
+                changed |= new_filter != process_filter;
+                process_filter = new_filter;
+            }
+
+            match receiver.recv_timeout(Duration::from_millis(FILTER_CHECK_MILLISECONDS)) {
+                Ok(processes_snapshot) => {
+                    latest_snapshot = Some(processes_snapshot);
+                    changed = true;
+                    fresh = true;
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => return,
+            }
+
+            let Some(processes_snapshot) = &latest_snapshot else {
+                continue;
+            };
+            if !changed {
+                continue;
+            }
+            let filtered_processes: FilteredProcesses = FilteredProcesses {
+                processes_snapshot: processes_snapshot.clone(),
+                matching_pids: matching_pids(processes_snapshot, &process_filter),
+                fresh,
+            };
+            if forward_sender.send(filtered_processes).is_err() {
+                return;
+            }
+        }
+    });
+
+    (forward_receiver, filter_sender)
+}
+
+/// Finds the processes of a snapshot that match a filter.
+///
+/// * Parameters
+/// `processes_snapshot` The snapshot to filter
+/// `process_filter` The filter to apply
+///
+/// * Returns
+/// The pids of the matching processes, or None if the filter is empty
+fn matching_pids(
+    processes_snapshot: &ProcessesSnapshot,
+    process_filter: &ProcessFilter,
+) -> Option<HashSet<u32>> {
+    if process_filter.is_empty() {
+        return None;
+    }
+    Some(
+        processes_snapshot
+            .processes
+            .iter()
+            .filter(|process| process_filter.matches(process))
+            .map(|process| process.pid)
+            .collect(),
+    )
+}
@@ -3,34 +3,227 @@
 //! Provides functions for rendering graphs, progress bars, and UI elements.
 
 use crate::config::{
-    app_variables::LAST_INDEX,
-    layout::{CELL_CORNER_RADIUS_PX, LINE_THICKNESS_ONE_PX},
-    style::{DOTTED_LINE_GAP_PX, DOTTED_LINE_LENGTH_PX, EMA_GRAPH_OPACITY, HALF_OPACITY},
+    layout::{CELL_CORNER_RADIUS_PX, GRAPH_BUCKET_WIDTH_PX, LINE_THICKNESS_ONE_PX},
+    style::{
+        AXIS_LABEL_FONT_SIZE, BUCKET_RANGE_OPACITY, DOTTED_LINE_GAP_PX, DOTTED_LINE_LENGTH_PX,
+        EMA_GRAPH_OPACITY, HALF_OPACITY, RECT_SHRINK_AMNT, SCALED_LINE_THICKNESS,
+    },
 };
+use crate::data::{downsample::downsample_history, history_bucket_struct::HistoryBucket};
 use crate::graph::{
     geometry::make_point,
     style::{find_stroke_width, get_color},
 };
-use eframe::egui::{Color32, Painter, Pos2, ProgressBar, Rangef, Rect, Stroke, StrokeKind, Ui};
+use eframe::egui::{
+    Align2, Color32, FontId, Painter, Pos2, ProgressBar, Rangef, Rect, Shape, Stroke, StrokeKind,
+    Ui, vec2,
+};
 use std::collections::VecDeque;
+use std::ops::Range;
 
 /// Draws a UI graph with background, border, and data lines.
 /// 
-/// Renders a graph rectangle with optional EMA overlay.
+/// Renders a graph rectangle with optional EMA overlay. Only the visible range of
+/// each history is drawn, downsampled to the width of the graph.
 /// 
 /// * Parameters
 /// `rect` The rectangle to draw in
 /// `ui` The UI context
 /// `history` The data history to plot
 /// `ema_history` Optional EMA history to overlay
+/// `visible_range` The indices of the points to draw
 pub fn draw_ui_graph(
     rect: &Rect,
     ui: &mut Ui,
     history: &VecDeque<f32>,
     ema_history: Option<&VecDeque<f32>>,
+    visible_range: Range<usize>,
+) {
+    let painter: Painter = ui.painter_at(*rect);
+    let max_buckets: usize = graph_bucket_count(rect);
+
+    draw_graph_background(rect, ui, &painter);
+
+    let buckets: Vec<HistoryBucket> =
+        downsample_history(history, visible_range.clone(), max_buckets);
+    draw_line_graph(rect, &buckets, &painter, HALF_OPACITY);
+
+    if let Some(ema_history) = ema_history {
+        draw_ema_line(rect, ui, ema_history, visible_range);
+    }
+}
+
+/// Draws an EMA history over a graph that has already been drawn.
+///
+/// * Parameters
+/// `rect` The rectangle of the graph
+/// `ui` The UI context
+/// `ema_history` The EMA history to overlay
+/// `visible_range` The indices of the points to draw
+pub fn draw_ema_line(
+    rect: &Rect,
+    ui: &mut Ui,
+    ema_history: &VecDeque<f32>,
+    visible_range: Range<usize>,
+) {
+    let painter: Painter = ui.painter_at(*rect);
+    let ema_buckets: Vec<HistoryBucket> =
+        downsample_history(ema_history, visible_range, graph_bucket_count(rect));
+    draw_line_graph(rect, &ema_buckets, &painter, EMA_GRAPH_OPACITY);
+}
+
+/// Finds how many buckets a graph's histories are downsampled to.
+///
+/// * Parameters
+/// `rect` The rectangle the graph is drawn in
+///
+/// * Returns
+/// One bucket per GRAPH_BUCKET_WIDTH_PX of width, at least 1
+pub fn graph_bucket_count(rect: &Rect) -> usize {
+    ((rect.width() / GRAPH_BUCKET_WIDTH_PX) as usize).max(1)
+}
+
+/// Draws the time cursor of a graph as a vertical line, with an optional label
+/// beside its top.
+///
+/// * Parameters
+/// `rect` The rectangle of the graph
+/// `ui` The UI context
+/// `x` The x-coordinate of the cursor
+/// `label` The text to show beside the cursor, if any
+pub fn draw_graph_cursor(rect: &Rect, ui: &Ui, x: f32, label: Option<&str>) {
+    let painter: Painter = ui.painter_at(*rect);
+    let color: Color32 = ui.visuals().strong_text_color();
+    painter.vline(x, rect.y_range(), Stroke::new(LINE_THICKNESS_ONE_PX, color));
+
+    // the label goes on whichever side of the cursor has more room
+    if let Some(label) = label {
+        let (label_x, align): (f32, Align2) = match x < rect.center().x {
+            true => (x + RECT_SHRINK_AMNT, Align2::LEFT_TOP),
+            false => (x - RECT_SHRINK_AMNT, Align2::RIGHT_TOP),
+        };
+        painter.text(
+            Pos2::new(label_x, rect.top() + RECT_SHRINK_AMNT),
+            align,
+            label,
+            FontId::proportional(AXIS_LABEL_FONT_SIZE),
+            color,
+        );
+    }
+}
+
+/// Draws a UI graph whose y-axis is scaled to the data instead of 0-100.
+///
+/// Renders a graph rectangle with one fixed-colour line per series, downsampled to
+/// the width of the graph, and labels the top of the graph with the value it
+/// represents.
+///
+/// * Parameters
+/// `rect` The rectangle to draw in
+/// `ui` The UI context
+/// `series` The data histories to plot, each with its line colour
+/// `upper_bound` The value drawn at the top of the graph
+/// `upper_bound_label` The text describing the upper bound, e.g. "2 MiB/s"
+pub fn draw_scaled_ui_graph(
+    rect: &Rect,
+    ui: &mut Ui,
+    series: &[(&VecDeque<f32>, Color32)],
+    upper_bound: f32,
+    upper_bound_label: &str,
+) {
+    let painter: Painter = ui.painter_at(*rect);
+
+    draw_graph_background(rect, ui, &painter);
+
+    for (history, color) in series {
+        draw_plain_line_graph(rect, history, &painter, *color, upper_bound);
+    }
+
+    painter.text(
+        rect.left_top() + vec2(RECT_SHRINK_AMNT, RECT_SHRINK_AMNT),
+        Align2::LEFT_TOP,
+        upper_bound_label,
+        FontId::proportional(AXIS_LABEL_FONT_SIZE),
+        Color32::GRAY,
+    );
+}
+
+/// Draws a UI graph of stacked areas on a 0-100 y-axis.
+///
+/// Each layer is drawn on top of the ones before it, so the top of the last layer
+/// shows the total of all layers.
+///
+/// * Parameters
+/// `rect` The rectangle to draw in
+/// `ui` The UI context
+/// `layers` The histories of each layer, bottom first, each with its fill colour.
+/// Every history must have the same length
+pub fn draw_stacked_ui_graph(rect: &Rect, ui: &mut Ui, layers: &[(VecDeque<f32>, Color32)]) {
+    let painter: Painter = ui.painter_at(*rect);
+    let max_buckets: usize = graph_bucket_count(rect);
+
+    draw_graph_background(rect, ui, &painter);
+
+    let Some(point_count) = layers.first().map(|(history, _)| history.len()) else {
+        return;
+    };
+    // every layer is shared out into the same buckets, so their averages still stack
+    let n: usize = point_count.min(max_buckets);
+    if n < 2 {
+        return;
+    }
+
+    let mut baseline: Vec<f32> = vec![0.0; n];
+    for (history, color) in layers {
+        let buckets: Vec<HistoryBucket> = downsample_history(history, 0..point_count, n);
+        let top: Vec<f32> = baseline
+            .iter()
+            .zip(buckets.iter())
+            .map(|(bottom, bucket)| bottom + bucket.average)
+            .collect();
+
+        // each segment is filled as its own quad, as the whole area is rarely convex
+        for index in 0..n - 1 {
+            let points: Vec<Pos2> = vec![
+                make_point(index, &baseline[index], n, rect, 100.0),
+                make_point(index + 1, &baseline[index + 1], n, rect, 100.0),
+                make_point(index + 1, &top[index + 1], n, rect, 100.0),
+                make_point(index, &top[index], n, rect, 100.0),
+            ];
+            painter.add(Shape::convex_polygon(points, *color, Stroke::NONE));
+        }
+
+        baseline = top;
+    }
+}
+
+/// Draws a small line graph without borders or labels, to sit inline with text.
+///
+/// * Parameters
+/// `rect` The rectangle to draw in
+/// `ui` The UI context
+/// `history` The data history to plot
+/// `upper_bound` The value drawn at the top of the graph
+/// `color` The colour of the line
+pub fn draw_sparkline(
+    rect: &Rect,
+    ui: &mut Ui,
+    history: &VecDeque<f32>,
+    upper_bound: f32,
+    color: Color32,
 ) {
     let painter: Painter = ui.painter_at(*rect);
+    painter.rect_filled(*rect, CELL_CORNER_RADIUS_PX, ui.visuals().extreme_bg_color);
+    draw_plain_line_graph(rect, history, &painter, color, upper_bound);
+}
 
+/// Draws the background, border and 50% dotted line shared by every graph.
+///
+/// * Parameters
+/// `rect` The rectangle to draw in
+/// `ui` The UI context
+/// `painter` The painter to use
+fn draw_graph_background(rect: &Rect, ui: &Ui, painter: &Painter) {
     painter.rect_filled(*rect, CELL_CORNER_RADIUS_PX, ui.visuals().extreme_bg_color);
 
     painter.rect_stroke(
@@ -44,50 +237,101 @@ pub fn draw_ui_graph(
     );
 
     // draws a dotted line at 50% of the height of the rectangle
-    let half: f32 = rect.bottom() - ((rect.bottom() - rect.top()) / 2 as f32);
-    draw_dotted_hline(&rect, half, &painter);
-
-    draw_line_graph(rect, history, &painter, HALF_OPACITY);
-
-    if let Some(ema_history) = ema_history {
-        draw_line_graph(rect, ema_history, &painter, EMA_GRAPH_OPACITY);
-    }
+    let half: f32 = rect.bottom() - ((rect.bottom() - rect.top()) / 2.0);
+    draw_dotted_hline(rect, half, painter);
 }
 
-/// Draws a line graph from the given data history.
+/// Draws a line graph from the given downsampled data history.
+///
+/// The line joins the bucket averages. Where a bucket holds more than one point, a
+/// faint vertical line behind it spans the bucket's minimum to maximum.
 /// 
 /// * Parameters
 /// `rect` The rectangle bounds
-/// `history` The data points to plot
+/// `buckets` The downsampled data points to plot
 /// `painter` The painter to use
 /// `opacity` The opacity for the lines
-pub fn draw_line_graph(rect: &Rect, history: &VecDeque<f32>, painter: &Painter, opacity: u8) {
-    let n: usize = history.len();
-    let points: Vec<Pos2> = history
+pub fn draw_line_graph(rect: &Rect, buckets: &[HistoryBucket], painter: &Painter, opacity: u8) {
+    let n: usize = buckets.len();
+
+    for (index, bucket) in buckets.iter().enumerate() {
+        if bucket.maximum > bucket.minimum {
+            painter.line_segment(
+                [
+                    make_point(index, &bucket.minimum, n, rect, 100.0),
+                    make_point(index, &bucket.maximum, n, rect, 100.0),
+                ],
+                Stroke::new(
+                    LINE_THICKNESS_ONE_PX,
+                    get_color(bucket.maximum, BUCKET_RANGE_OPACITY),
+                ),
+            );
+        }
+    }
+
+    let points: Vec<Pos2> = buckets
         .iter()
         .enumerate()
-        .map(|(index, value)| make_point(index, value, n, rect))
+        .map(|(index, bucket)| make_point(index, &bucket.average, n, rect, 100.0))
         .collect();
 
     for (index, segment) in points.windows(2).enumerate() {
-        let color: Color32;
-        let stroke_width: f32;
-        match index {
-            LAST_INDEX => {
-                let value: f32 = history[index];
-                color = get_color(value, opacity);
-                stroke_width = find_stroke_width(value)
-            }
-            _ => {
-                let value: f32 = (history[index] + history[index + 1]) / 2 as f32;
-                color = get_color(value, opacity);
-                stroke_width = find_stroke_width(value)
-            }
-        }
+        let value: f32 = (buckets[index].average + buckets[index + 1].average) / 2.0;
+        let color: Color32 = get_color(value, opacity);
+        let stroke_width: f32 = find_stroke_width(value);
         painter.line_segment([segment[0], segment[1]], Stroke::new(stroke_width, color));
     }
 }
 
+/// Draws a single-colour line graph scaled to a given maximum.
+///
+/// The history is downsampled to the width of the graph like draw_line_graph, with
+/// the range of each bucket drawn faintly behind the line.
+///
+/// * Parameters
+/// `rect` The rectangle bounds
+/// `history` The data points to plot
+/// `painter` The painter to use
+/// `color` The colour of the line
+/// `max_value` The value drawn at the top of the rectangle
+pub fn draw_plain_line_graph(
+    rect: &Rect,
+    history: &VecDeque<f32>,
+    painter: &Painter,
+    color: Color32,
+    max_value: f32,
+) {
+    let buckets: Vec<HistoryBucket> =
+        downsample_history(history, 0..history.len(), graph_bucket_count(rect));
+    let n: usize = buckets.len();
+
+    let range_color: Color32 = color.gamma_multiply(BUCKET_RANGE_OPACITY as f32 / 255.0);
+    for (index, bucket) in buckets.iter().enumerate() {
+        if bucket.maximum > bucket.minimum {
+            painter.line_segment(
+                [
+                    make_point(index, &bucket.minimum, n, rect, max_value),
+                    make_point(index, &bucket.maximum, n, rect, max_value),
+                ],
+                Stroke::new(LINE_THICKNESS_ONE_PX, range_color),
+            );
+        }
+    }
+
+    let points: Vec<Pos2> = buckets
+        .iter()
+        .enumerate()
+        .map(|(index, bucket)| make_point(index, &bucket.average, n, rect, max_value))
+        .collect();
+
+    for segment in points.windows(2) {
+        painter.line_segment(
+            [segment[0], segment[1]],
+            Stroke::new(SCALED_LINE_THICKNESS, color),
+        );
+    }
+}
+
 /// Draws a horizontal dotted line.
 /// 
 /// * Parameters
@@ -128,7 +372,7 @@ pub fn build_progress_bar(
     rounding: f32,
     color: Color32,
 ) -> ProgressBar {
-    ProgressBar::new(value / 100 as f32)
+    ProgressBar::new(value / 100.0)
         .desired_width(width)
         .desired_height(height)
         .fill(color)
@@ -3,6 +3,7 @@
 //! Provides functions for calculating positions and coordinates for graph rendering.
 
 use crate::config::style::RECT_SHRINK_AMNT;
+use crate::data::format::byte_unit;
 use eframe::egui::{Pos2, Rect};
 
 /// Calculates the position of a data point in the graph.
@@ -11,16 +12,68 @@ use eframe::egui::{Pos2, Rect};
 /// 
 /// * Parameters
 /// `index` The data point index
-/// `value` The data value (0-100)
+/// `value` The data value (0-max_value)
 /// `n` Total number of points
 /// `rect` The bounding rectangle
+/// `max_value` The value drawn at the top of the rectangle, 100 for percentages
 /// 
 /// * Returns
 /// The calculated position
-pub fn make_point(index: usize, value: &f32, n: usize, rect: &Rect) -> Pos2 {
+pub fn make_point(index: usize, value: &f32, n: usize, rect: &Rect, max_value: f32) -> Pos2 {
     let plot_rect = rect.shrink(RECT_SHRINK_AMNT);
     let plot_rect_height: f32 = plot_rect.bottom() - plot_rect.top();
     let x: f32 = plot_rect.left() + (index as f32) / ((n - 1) as f32) * plot_rect.width();
-    let y: f32 = plot_rect.bottom() - (value / 100 as f32) * plot_rect_height;
+    let y: f32 = plot_rect.bottom() - (value / max_value) * plot_rect_height;
     Pos2 { x, y }
 }
+
+/// Finds a round upper bound for an auto-scaled y-axis.
+///
+/// The bound is 1, 2 or 5 times a power of ten, in whatever unit `max_value` is
+/// displayed in, so that the label at the top of the graph reads nicely.
+///
+/// * Parameters
+/// `max_value` The largest value being plotted, already divided into its display unit
+///
+/// * Returns
+/// The smallest round number at or above `max_value`, at least 1
+pub fn nice_upper_bound(max_value: f32) -> f32 {
+    if max_value <= 1.0 || !max_value.is_finite() {
+        return 1.0;
+    }
+    let magnitude: f32 = 10f32.powf(max_value.log10().floor());
+    for step in [1.0, 2.0, 5.0] {
+        if step * magnitude >= max_value {
+            return step * magnitude;
+        }
+    }
+    10.0 * magnitude
+}
+
+/// Finds the y-axis upper bound and its label for a graph of byte amounts.
+///
+/// * Parameters
+/// `max_bytes` The largest amount being plotted
+///
+/// * Returns
+/// The upper bound in bytes, and its label, e.g. "2 MiB"
+pub fn byte_axis(max_bytes: f32) -> (f32, String) {
+    let (divisor, unit): (f64, &str) = byte_unit(max_bytes as f64);
+    let upper_bound: f32 = nice_upper_bound((max_bytes as f64 / divisor) as f32);
+    (
+        (upper_bound as f64 * divisor) as f32,
+        format!("{} {}", upper_bound, unit),
+    )
+}
+
+/// Finds the y-axis upper bound and its label for a graph of byte rates.
+///
+/// * Parameters
+/// `max_bytes_per_second` The largest rate being plotted
+///
+/// * Returns
+/// The upper bound in bytes per second, and its label, e.g. "2 MiB/s"
+pub fn byte_rate_axis(max_bytes_per_second: f32) -> (f32, String) {
+    let (upper_bound, upper_bound_label): (f32, String) = byte_axis(max_bytes_per_second);
+    (upper_bound, format!("{}/s", upper_bound_label))
+}
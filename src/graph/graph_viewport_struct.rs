@@ -0,0 +1,135 @@
+//! Graph viewport structure.
+//!
+//! Tracks which stretch of the CPU history the graphs show, as panned and zoomed by
+//! the user, and the time under the cursor.
+
+use crate::config::settings::settings_monitoring;
+use crate::graph::time_window_enum::TimeWindow;
+
+/// The shortest stretch of history the graphs can be zoomed in to.
+pub const MIN_SPAN_MILLISECONDS: u64 = 2_000;
+/// How much the span shrinks per point scrolled, as the exponent of a scale factor.
+pub const ZOOM_PER_SCROLL_POINT: f64 = 0.005;
+
+/// Structure holding the time range shown by the CPU graphs.
+/// Note: timestamps are in milliseconds since the unix epoch.
+/// Note: without a pinned end the graphs follow the newest snapshot, panning back pins
+/// the end so the view stays still while new snapshots arrive.
+pub struct GraphViewport {
+    pub span_milliseconds: u64,
+    pub pinned_end_timestamp: Option<u64>,
+    pub link_cursors: bool,
+    pub hovered_timestamp: Option<u64>,
+    pub cursor_timestamp: Option<u64>,
+}
+
+impl GraphViewport {
+    /// Constructor for the GraphViewport structure.
+    ///
+    /// * Returns
+    /// GraphViewport following the newest 10 seconds of history
+    pub fn new() -> GraphViewport {
+        GraphViewport {
+            span_milliseconds: TimeWindow::TenSeconds.milliseconds(),
+            pinned_end_timestamp: None,
+            link_cursors: false,
+            hovered_timestamp: None,
+            cursor_timestamp: None,
+        }
+    }
+
+    /// Shows the newest stretch of history of a time window, undoing any pan or zoom.
+    ///
+    /// * Parameters
+    /// `time_window` The time window to show
+    pub fn show_time_window(&mut self, time_window: TimeWindow) {
+        self.span_milliseconds = time_window.milliseconds();
+        self.pinned_end_timestamp = None;
+    }
+
+    /// Checks whether the graphs show exactly the newest stretch of a time window.
+    ///
+    /// * Parameters
+    /// `time_window` The time window to compare with
+    ///
+    /// * Returns
+    /// true if neither panned nor zoomed away from the time window
+    pub fn is_showing(&self, time_window: TimeWindow) -> bool {
+        self.span_milliseconds == time_window.milliseconds() && self.pinned_end_timestamp.is_none()
+    }
+
+    /// Goes back to following the newest snapshot, keeping the zoom.
+    pub fn follow_latest(&mut self) {
+        self.pinned_end_timestamp = None;
+    }
+
+    /// Moves the hovered time of the last frame into the cursor drawn this frame, so
+    /// graphs drawn before the hovered one can show it too.
+    pub fn begin_frame(&mut self) {
+        self.cursor_timestamp = self.hovered_timestamp.take();
+    }
+
+    /// Finds the time range shown.
+    ///
+    /// * Parameters
+    /// `latest` The timestamp of the newest snapshot
+    ///
+    /// * Returns
+    /// The (start, end) timestamps, both inclusive
+    pub fn visible_time_range(&self, latest: u64) -> (u64, u64) {
+        let end: u64 = self.pinned_end_timestamp.unwrap_or(latest).min(latest);
+        (end.saturating_sub(self.span_milliseconds), end)
+    }
+
+    /// Pans the time range, keeping at least part of the history in view.
+    ///
+    /// * Parameters
+    /// `milliseconds` How far to move, positive values going back in time
+    /// `oldest` The timestamp of the oldest snapshot held
+    /// `latest` The timestamp of the newest snapshot
+    pub fn pan(&mut self, milliseconds: f64, oldest: u64, latest: u64) {
+        let (_, end) = self.visible_time_range(latest);
+        self.pin_end(end as f64 - milliseconds, oldest, latest);
+    }
+
+    /// Zooms the time range in or out around a point, which stays under the pointer.
+    ///
+    /// * Parameters
+    /// `factor` How much to scale the span by, below 1 to zoom in
+    /// `pointer_fraction` Where the point is, from 0 at the left edge to 1 at the right
+    /// `oldest` The timestamp of the oldest snapshot held
+    /// `latest` The timestamp of the newest snapshot
+    pub fn zoom(&mut self, factor: f64, pointer_fraction: f64, oldest: u64, latest: u64) {
+        let (_, end) = self.visible_time_range(latest);
+        let max_span: u64 = settings_monitoring()
+            .cpu_history_milliseconds()
+            .max(MIN_SPAN_MILLISECONDS);
+        let span: u64 = ((self.span_milliseconds as f64 * factor) as u64)
+            .clamp(MIN_SPAN_MILLISECONDS, max_span);
+
+        // the time under the pointer is the same distance from the end before and after
+        let pointer_time: f64 =
+            end as f64 - (1.0 - pointer_fraction) * self.span_milliseconds as f64;
+        self.span_milliseconds = span;
+        self.pin_end(
+            pointer_time + (1.0 - pointer_fraction) * span as f64,
+            oldest,
+            latest,
+        );
+    }
+
+    /// Sets the end of the time range, following the newest snapshot again once the
+    /// end reaches it.
+    ///
+    /// * Parameters
+    /// `end` The wanted end timestamp
+    /// `oldest` The timestamp of the oldest snapshot held
+    /// `latest` The timestamp of the newest snapshot
+    fn pin_end(&mut self, end: f64, oldest: u64, latest: u64) {
+        let earliest_end: u64 = (oldest + self.span_milliseconds).min(latest);
+        self.pinned_end_timestamp = match end >= latest as f64 {
+            true => None,
+            false => Some((end.max(0.0) as u64).max(earliest_end)),
+        };
+    }
+}
@@ -4,4 +4,6 @@
 
 pub mod draw;
 pub mod geometry;
+pub mod graph_viewport_struct;
 pub mod style;
+pub mod time_window_enum;
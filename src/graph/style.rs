@@ -2,10 +2,7 @@
 //!
 //! Provides functions for determining colors and line thicknesses based on CPU usage values.
 
-use crate::config::style::{
-    GREEN_LINE_THICKNESS, RED_LINE_THICKNESS, RED_LINE_THRESHOLD, YELLOW_LINE_THICKNESS,
-    YELLOW_LINE_THRESHOLD,
-};
+use crate::config::settings::{StyleSettings, settings_style};
 use eframe::egui::Color32;
 
 /// Determines the color for a given CPU usage value.
@@ -19,12 +16,13 @@ use eframe::egui::Color32;
 /// * Returns
 /// The appropriate Color32
 pub fn get_color(value: f32, opacity: u8) -> Color32 {
-    match value {
-        value if value < YELLOW_LINE_THRESHOLD => {
+    let style: StyleSettings = settings_style();
+    match value as f64 {
+        value if value < style.yellow_line_threshold => {
             Color32::from_rgba_unmultiplied(0, 255, 0, opacity)
         }
 
-        value if value < RED_LINE_THRESHOLD => {
+        value if value < style.red_line_threshold => {
             Color32::from_rgba_unmultiplied(255, 255, 0, opacity)
         }
         // red line otherwise
@@ -40,9 +38,11 @@ pub fn get_color(value: f32, opacity: u8) -> Color32 {
 /// * Returns
 /// The appropriate line thickness
 pub fn find_stroke_width(value: f32) -> f32 {
-    match value {
-        value if value < YELLOW_LINE_THRESHOLD => GREEN_LINE_THICKNESS,
-        value if value < RED_LINE_THRESHOLD => YELLOW_LINE_THICKNESS,
-        _ => RED_LINE_THICKNESS,
-    }
+    let style: StyleSettings = settings_style();
+    let stroke_width: f64 = match value as f64 {
+        value if value < style.yellow_line_threshold => style.green_line_thickness,
+        value if value < style.red_line_threshold => style.yellow_line_thickness,
+        _ => style.red_line_thickness,
+    };
+    stroke_width as f32
 }
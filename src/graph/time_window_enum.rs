@@ -0,0 +1,48 @@
+//! Time window enum.
+//!
+//! Represents how much of the CPU history the graphs show.
+
+/// Enum holding the length of history shown by the CPU graphs.
+#[derive(Clone, Copy, PartialEq)]
+pub enum TimeWindow {
+    TenSeconds,
+    OneMinute,
+    FiveMinutes,
+    OneHour,
+}
+
+impl TimeWindow {
+    /// Every time window, in the order they are offered.
+    pub const ALL: [TimeWindow; 4] = [
+        TimeWindow::TenSeconds,
+        TimeWindow::OneMinute,
+        TimeWindow::FiveMinutes,
+        TimeWindow::OneHour,
+    ];
+
+    /// Returns the text of the time window's button.
+    ///
+    /// * Returns
+    /// The label
+    pub fn label(&self) -> &'static str {
+        match self {
+            TimeWindow::TenSeconds => "10s",
+            TimeWindow::OneMinute => "1m",
+            TimeWindow::FiveMinutes => "5m",
+            TimeWindow::OneHour => "1h",
+        }
+    }
+
+    /// Returns the length of the time window.
+    ///
+    /// * Returns
+    /// The length in milliseconds
+    pub fn milliseconds(&self) -> u64 {
+        match self {
+            TimeWindow::TenSeconds => 10_000,
+            TimeWindow::OneMinute => 60_000,
+            TimeWindow::FiveMinutes => 300_000,
+            TimeWindow::OneHour => 3_600_000,
+        }
+    }
+}
@@ -3,60 +3,397 @@
 //! This module initializes the application, sets up communication channels,
 //! starts background workers for data collection, and launches the GUI.
 
+// doc comments list parameters as "* Parameters" followed by unindented lines.
+#![allow(clippy::doc_lazy_continuation)]
+
+mod alerts;
 mod app;
 mod channel;
+mod cli;
+mod commands;
 mod config;
 mod data;
+mod export;
+mod filter;
 mod graph;
+mod metrics;
+mod recording;
 mod snapshots;
 mod workers;
 
-use app::app_monitor::AppMonitor;
+use app::{
+    alert_monitor::AlertMonitor, app_monitor::AppMonitor, headless::run_headless, tui::run_tui,
+};
 use channel::Channel;
 use snapshots::cpu_snapshot_struct::CpuSnapshot;
 use workers::{send_cpu_snapshot, send_system_snapshot::send_system_snapshot};
 
+use std::env;
+use std::path::Path;
+use std::process;
+use std::sync::mpsc::{Receiver, Sender};
+use std::sync::{Arc, Mutex};
+
 use crate::{
-    app::app_receivers::AppReceivers,
-    config::layout::{APP_HEIGHT_PX, APP_WIDTH_PX},
+    app::{app_receivers::AppReceivers, app_senders::AppSenders},
+    cli::{CliOptions, USAGE, parse_arguments},
+    commands::{
+        process_command_enum::ProcessCommand,
+        process_command_outcome_struct::ProcessCommandOutcome, replay_command_enum::ReplayCommand,
+    },
+    config::{
+        config_file::load_settings,
+        settings::{Settings, set_settings},
+    },
+    data::process_key_struct::ProcessKey,
+    metrics::{
+        metrics_server::{publish_channel, start_metrics_server},
+        metrics_state_struct::MetricsState,
+    },
+    recording::{
+        recorded_snapshot_enum::RecordedSnapshot,
+        recorder::{record_channel, start_recording},
+        recording_entry_struct::RecordingEntry,
+        replay_status_struct::ReplayStatus,
+        replayer::{ReplaySenders, read_recording, send_recorded_snapshots},
+    },
     snapshots::{
-        processes_snapshot_struct::ProcessesSnapshot, system_snapshot_struct::SystemSnapshot,
+        cpu_times_snapshot_struct::CpuTimesSnapshot, disks_snapshot_struct::DisksSnapshot,
+        load_snapshot_struct::LoadSnapshot, memory_snapshot_struct::MemorySnapshot,
+        network_snapshot_struct::NetworkSnapshot, process_environment_struct::ProcessEnvironment,
+        processes_snapshot_struct::ProcessesSnapshot, sensors_snapshot_struct::SensorsSnapshot,
+        system_snapshot_struct::SystemSnapshot, threads_snapshot_struct::ThreadsSnapshot,
+    },
+    workers::{
+        cpu_times::send_cpu_times_snapshot, disks::send_disks_snapshot, load::send_load_snapshot,
+        memory::send_memory_snapshot, network::send_network_snapshot,
+        processes::send_processes_snapshot, sensors::send_sensors_snapshot,
+        threads::send_threads_snapshot,
     },
-    workers::processes::send_processes_snapshot,
 };
 
 /// Main function.
 /// 
-/// Initializes communication channels for CPU, system, and processes data,
-/// starts background workers to collect snapshots, and runs the eframe GUI application.
+/// Parses the command line, loads the config file, starts either the live workers
+/// or the replay of a recording, and runs the eframe GUI application, the terminal
+/// frontend or the headless mode without a window.
 /// 
 /// * Returns
 /// Result indicating success or failure of the application
 fn main() -> eframe::Result<()> {
+    let cli_options: CliOptions = match parse_arguments(env::args().skip(1)) {
+        Ok(cli_options) => cli_options,
+        Err(message) => {
+            eprintln!("Error: {}\n\n{}", message, USAGE);
+            process::exit(2);
+        }
+    };
+    if cli_options.show_help {
+        println!("{}", USAGE);
+        return Ok(());
+    }
+
+    // the settings are in place before anything reads them
+    let (settings, warnings): (Settings, Vec<String>) =
+        load_settings(cli_options.config_path.as_deref());
+    for warning in warnings {
+        eprintln!("Warning: {}", warning);
+    }
+    set_settings(settings.clone());
+
+    let started: Result<(AppReceivers, AppSenders), String> = match &cli_options.replay_path {
+        Some(replay_path) => start_replay(replay_path),
+        None => start_live_workers(cli_options.record_path.as_deref()),
+    };
+    let (mut app_receivers, app_senders) = match started {
+        Ok(channels) => channels,
+        Err(message) => {
+            eprintln!("Error: {}", message);
+            process::exit(1);
+        }
+    };
+
+    if let Some(metrics_address) = &cli_options.metrics_address {
+        app_receivers = match start_metrics(metrics_address, app_receivers) {
+            Ok(app_receivers) => app_receivers,
+            Err(message) => {
+                eprintln!("Error: {}", message);
+                process::exit(1);
+            }
+        };
+    }
+
+    let mut app_monitor: AppMonitor = AppMonitor::new(app_receivers, app_senders);
+    app_monitor.export_monitor.exit_export_path = cli_options.export_path;
+    app_monitor.settings_monitor.config_path = cli_options.config_path;
+    app_monitor.alert_monitor = AlertMonitor::build(
+        cli_options.alert_rules,
+        cli_options.alert_command,
+        cli_options.alert_log_path,
+    );
+
+    if cli_options.headless {
+        run_headless(
+            app_monitor,
+            cli_options.duration,
+            cli_options.summary_interval,
+        );
+        return Ok(());
+    }
+
+    if cli_options.tui {
+        if let Err(e) = run_tui(app_monitor) {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+        return Ok(());
+    }
+
+    let options = eframe::NativeOptions {
+        viewport: eframe::egui::ViewportBuilder::default()
+            .with_inner_size([settings.window.width as f32, settings.window.height as f32]),
+        ..Default::default()
+    };
+
+    eframe::run_native(
+        "CPU Monitor",
+        options,
+        Box::new(|_cc| Ok(Box::new(app_monitor))),
+    )
+}
+
+/// Starts the background workers that monitor the system live.
+///
+/// Initializes communication channels for CPU, CPU times, system, load, processes,
+/// memory, network, disk and sensor data and starts a worker for each. When
+/// recording, the CPU, processes and system snapshots are written to the recording
+/// on their way to the GUI.
+///
+/// * Parameters
+/// `record_path` The file to record the session to, if any
+///
+/// * Returns
+/// The receivers and senders for the GUI, or a message saying why the recording
+/// couldn't be created
+fn start_live_workers(record_path: Option<&Path>) -> Result<(AppReceivers, AppSenders), String> {
+    let entry_sender: Option<Sender<RecordingEntry>> = match record_path {
+        Some(record_path) => Some(start_recording(record_path).map_err(|e| {
+            format!(
+                "could not create recording {}: {}",
+                record_path.display(),
+                e
+            )
+        })?),
+        None => None,
+    };
     let cpu_snapshot_channel: Channel<CpuSnapshot> = Channel::new();
     let (cpu_sender, cpu_receiver) = cpu_snapshot_channel.split();
     send_cpu_snapshot(cpu_sender);
 
+    let cpu_times_snapshot_channel: Channel<CpuTimesSnapshot> = Channel::new();
+    let (cpu_times_sender, cpu_times_receiver) = cpu_times_snapshot_channel.split();
+    send_cpu_times_snapshot(cpu_times_sender);
+
     let system_snapshot_channel: Channel<SystemSnapshot> = Channel::new();
     let (system_sender, system_receiver) = system_snapshot_channel.split();
     send_system_snapshot(system_sender);
 
+    let load_snapshot_channel: Channel<LoadSnapshot> = Channel::new();
+    let (load_sender, load_receiver) = load_snapshot_channel.split();
+    send_load_snapshot(load_sender);
+
     let processes_snapshot_channel: Channel<ProcessesSnapshot> = Channel::new();
     let (processes_sender, processes_receiver) = processes_snapshot_channel.split();
-    send_processes_snapshot(processes_sender);
 
-    let options = eframe::NativeOptions {
-        viewport: eframe::egui::ViewportBuilder::default()
-            .with_inner_size([APP_WIDTH_PX, APP_HEIGHT_PX]),
-        ..Default::default()
+    // commands flow from the GUI to the processes worker, outcomes flow back
+    let process_command_channel: Channel<ProcessCommand> = Channel::new();
+    let (process_command_sender, process_command_receiver) = process_command_channel.split();
+    let process_command_outcome_channel: Channel<ProcessCommandOutcome> = Channel::new();
+    let (process_command_outcome_sender, process_command_outcome_receiver) =
+        process_command_outcome_channel.split();
+    let (process_environment_sender, process_environment_receiver) =
+        Channel::<ProcessEnvironment>::new().split();
+    // the GUI picks the processes whose open files, nice value and affinity are read
+    let (detail_watch_sender, detail_watch_receiver) = Channel::<Vec<u32>>::new().split();
+
+    send_processes_snapshot(
+        processes_sender,
+        process_command_receiver,
+        process_command_outcome_sender,
+        process_environment_sender,
+        detail_watch_receiver,
+    );
+
+    let memory_snapshot_channel: Channel<MemorySnapshot> = Channel::new();
+    let (memory_sender, memory_receiver) = memory_snapshot_channel.split();
+    send_memory_snapshot(memory_sender);
+
+    let network_snapshot_channel: Channel<NetworkSnapshot> = Channel::new();
+    let (network_sender, network_receiver) = network_snapshot_channel.split();
+    send_network_snapshot(network_sender);
+
+    let disks_snapshot_channel: Channel<DisksSnapshot> = Channel::new();
+    let (disks_sender, disks_receiver) = disks_snapshot_channel.split();
+    send_disks_snapshot(disks_sender);
+
+    let sensors_snapshot_channel: Channel<SensorsSnapshot> = Channel::new();
+    let (sensors_sender, sensors_receiver) = sensors_snapshot_channel.split();
+    send_sensors_snapshot(sensors_sender);
+
+    // the GUI picks the process whose threads are listed, the threads flow back
+    let (threads_sender, threads_receiver) = Channel::<ThreadsSnapshot>::new().split();
+    let (thread_watch_sender, thread_watch_receiver) = Channel::<Option<ProcessKey>>::new().split();
+    send_threads_snapshot(threads_sender, thread_watch_receiver);
+
+    let (cpu_receiver, processes_receiver, system_receiver) = match entry_sender {
+        Some(entry_sender) => (
+            record_channel(cpu_receiver, entry_sender.clone(), RecordedSnapshot::Cpu),
+            record_channel(
+                processes_receiver,
+                entry_sender.clone(),
+                RecordedSnapshot::Processes,
+            ),
+            record_channel(system_receiver, entry_sender, RecordedSnapshot::System),
+        ),
+        None => (cpu_receiver, processes_receiver, system_receiver),
     };
 
-    let app_receivers: AppReceivers =
-        AppReceivers::build(cpu_receiver, system_receiver, processes_receiver);
+    let app_receivers: AppReceivers = AppReceivers::build(
+        cpu_receiver,
+        system_receiver,
+        processes_receiver,
+        process_command_outcome_receiver,
+        process_environment_receiver,
+        memory_receiver,
+        network_receiver,
+        disks_receiver,
+        sensors_receiver,
+        cpu_times_receiver,
+        load_receiver,
+        threads_receiver,
+        None,
+    );
+    let app_senders: AppSenders = AppSenders::build(
+        process_command_sender,
+        thread_watch_sender,
+        detail_watch_sender,
+        None,
+    );
 
-    eframe::run_native(
-        "CPU Monitor",
-        options,
-        Box::new(|_cc| Ok(Box::new(AppMonitor::new(app_receivers)))),
-    )
+    Ok((app_receivers, app_senders))
+}
+
+/// Starts the replay of a recorded session.
+///
+/// The recorded CPU, processes and system snapshots are sent on the same kind of
+/// channels the live workers use, the other panels receive nothing.
+///
+/// * Parameters
+/// `replay_path` The recording to replay
+///
+/// * Returns
+/// The receivers and senders for the GUI, or a message saying why the recording
+/// couldn't be read
+fn start_replay(replay_path: &Path) -> Result<(AppReceivers, AppSenders), String> {
+    let entries: Vec<RecordingEntry> = read_recording(replay_path)
+        .map_err(|e| format!("could not read recording {}: {}", replay_path.display(), e))?;
+
+    let (cpu_sender, cpu_receiver) = Channel::<CpuSnapshot>::new().split();
+    let (system_sender, system_receiver) = Channel::<SystemSnapshot>::new().split();
+    let (processes_sender, processes_receiver) = Channel::<ProcessesSnapshot>::new().split();
+    let (process_command_sender, process_command_receiver) =
+        Channel::<ProcessCommand>::new().split();
+    let (process_command_outcome_sender, process_command_outcome_receiver) =
+        Channel::<ProcessCommandOutcome>::new().split();
+
+    // the GUI controls the replay, the replay reports its position back
+    let (replay_command_sender, replay_command_receiver) = Channel::<ReplayCommand>::new().split();
+    let (replay_status_sender, replay_status_receiver) = Channel::<ReplayStatus>::new().split();
+    // threads aren't recorded, so nothing listens for the process to list them of
+    let (thread_watch_sender, _) = Channel::<Option<ProcessKey>>::new().split();
+    // the recorded processes keep whatever details were read while recording
+    let (detail_watch_sender, _) = Channel::<Vec<u32>>::new().split();
+
+    send_recorded_snapshots(
+        entries,
+        ReplaySenders {
+            cpu_snapshot_sender: cpu_sender,
+            processes_snapshot_sender: processes_sender,
+            system_snapshot_sender: system_sender,
+            replay_status_sender,
+        },
+        replay_command_receiver,
+        process_command_receiver,
+        process_command_outcome_sender,
+    );
+
+    let app_receivers: AppReceivers = AppReceivers::build(
+        cpu_receiver,
+        system_receiver,
+        processes_receiver,
+        process_command_outcome_receiver,
+        idle_receiver::<ProcessEnvironment>(),
+        idle_receiver::<MemorySnapshot>(),
+        idle_receiver::<NetworkSnapshot>(),
+        idle_receiver::<DisksSnapshot>(),
+        idle_receiver::<SensorsSnapshot>(),
+        idle_receiver::<CpuTimesSnapshot>(),
+        idle_receiver::<LoadSnapshot>(),
+        idle_receiver::<ThreadsSnapshot>(),
+        Some(replay_status_receiver),
+    );
+    let app_senders: AppSenders = AppSenders::build(
+        process_command_sender,
+        thread_watch_sender,
+        detail_watch_sender,
+        Some(replay_command_sender),
+    );
+
+    Ok((app_receivers, app_senders))
+}
+
+/// Starts the Prometheus metrics endpoint.
+///
+/// The CPU, processes and system snapshots update the served metrics on their way
+/// to the GUI, whether they come from the live workers or a replay.
+///
+/// * Parameters
+/// `metrics_address` The address to serve the metrics on
+/// `app_receivers` The receivers the GUI would otherwise read from
+///
+/// * Returns
+/// The receivers for the GUI, or a message saying why the server couldn't start
+fn start_metrics(
+    metrics_address: &str,
+    app_receivers: AppReceivers,
+) -> Result<AppReceivers, String> {
+    let metrics_state: Arc<Mutex<MetricsState>> = Arc::new(Mutex::new(MetricsState::new()));
+    start_metrics_server(metrics_address, metrics_state.clone())?;
+
+    let mut app_receivers: AppReceivers = app_receivers;
+    app_receivers.cpu_snapshot_receiver = publish_channel(
+        app_receivers.cpu_snapshot_receiver,
+        metrics_state.clone(),
+        MetricsState::apply_cpu_snapshot,
+    );
+    app_receivers.processes_snapshot_receiver = publish_channel(
+        app_receivers.processes_snapshot_receiver,
+        metrics_state.clone(),
+        MetricsState::apply_processes_snapshot,
+    );
+    app_receivers.system_snapshot_receiver = publish_channel(
+        app_receivers.system_snapshot_receiver,
+        metrics_state,
+        MetricsState::apply_system_snapshot,
+    );
+    Ok(app_receivers)
+}
+
+/// Creates the receiver of a channel nothing is ever sent on.
+///
+/// * Returns
+/// A receiver whose sender has already been dropped
+fn idle_receiver<T>() -> Receiver<T> {
+    let (_sender, receiver) = Channel::<T>::new().split();
+    receiver
 }
@@ -0,0 +1,167 @@
+//! Metrics server.
+//!
+//! Serves the metrics state over HTTP and keeps it up to date from the worker
+//! channels.
+
+use crate::{
+    channel::Channel,
+    metrics::{
+        metrics_state_struct::MetricsState,
+        prometheus::{PROMETHEUS_CONTENT_TYPE, render_metrics},
+    },
+};
+use std::net::SocketAddr;
+use std::sync::mpsc::Receiver;
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+/// Starts a background HTTP server answering GET /metrics.
+///
+/// * Parameters
+/// `address` The address to listen on, such as "127.0.0.1:9898"
+/// `metrics_state` The data to serve, shared with the channels feeding it
+///
+/// * Returns
+/// The address the server listens on, which tells the port picked for port 0,
+/// or a message saying why the server couldn't listen on the address
+pub fn start_metrics_server(
+    address: &str,
+    metrics_state: Arc<Mutex<MetricsState>>,
+) -> Result<SocketAddr, String> {
+    let server: Server = Server::http(address)
+        .map_err(|e| format!("could not serve metrics on {}: {}", address, e))?;
+    let listen_address: SocketAddr = server
+        .server_addr()
+        .to_ip()
+        .ok_or_else(|| format!("could not serve metrics on {}: not an IP address", address))?;
+
+    let _handle: JoinHandle<()> = thread::spawn(move || {
+        for request in server.incoming_requests() {
+            if let Err(e) = respond(request, &metrics_state) {
+                eprintln!("Error answering metrics request: {}", e);
+            }
+        }
+    });
+    Ok(listen_address)
+}
+
+/// Answers one request, with the metrics for GET /metrics and an error otherwise.
+///
+/// * Parameters
+/// `request` The request to answer
+/// `metrics_state` The data to serve
+///
+/// * Returns
+/// The error met sending the response, if any
+fn respond(request: Request, metrics_state: &Mutex<MetricsState>) -> std::io::Result<()> {
+    // the query string doesn't select anything, so it is ignored
+    let path: &str = request.url().split('?').next().unwrap_or("");
+
+    if path != "/metrics" {
+        return request.respond(Response::from_string("Not Found\n").with_status_code(404));
+    }
+    if *request.method() != Method::Get && *request.method() != Method::Head {
+        return request
+            .respond(Response::from_string("Method Not Allowed\n").with_status_code(405));
+    }
+
+    let body: String = match metrics_state.lock() {
+        Ok(metrics_state) => render_metrics(&metrics_state),
+        Err(_) => {
+            return request
+                .respond(Response::from_string("Internal Server Error\n").with_status_code(500));
+        }
+    };
+    let content_type: Header = Header::from_bytes("Content-Type", PROMETHEUS_CONTENT_TYPE)
+        .expect("the content type is a valid header");
+    request.respond(Response::from_string(body).with_header(content_type))
+}
+
+/// Stores every snapshot received on a channel in the metrics state, then passes
+/// it on.
+///
+/// Spawns a thread that sits between a worker and its consumer, in the same way
+/// a recording does.
+///
+/// * Parameters
+/// `receiver` The receiver the worker's snapshots arrive on
+/// `metrics_state` The state to update
+/// `apply` Stores a snapshot in the state
+///
+/// * Returns
+/// The receiver the consumer should read the snapshots from instead
+pub fn publish_channel<T: Send + 'static>(
+    receiver: Receiver<T>,
+    metrics_state: Arc<Mutex<MetricsState>>,
+    apply: fn(&mut MetricsState, &T),
+) -> Receiver<T> {
+    let (forward_sender, forward_receiver) = Channel::<T>::new().split();
+    let _handle: JoinHandle<()> = thread::spawn(move || {
+        for snapshot in receiver {
+            if let Ok(mut metrics_state) = metrics_state.lock() {
+                apply(&mut metrics_state, &snapshot);
+            }
+            if forward_sender.send(snapshot).is_err() {
+                return;
+            }
+        }
+    });
+    forward_receiver
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snapshots::cpu_snapshot_struct::CpuSnapshot;
+    use std::io::{Read, Write};
+    use std::net::TcpStream;
+
+    /// Sends a request to the server and returns the whole response.
+    fn send_request(address: SocketAddr, method: &str, path: &str) -> String {
+        let mut stream: TcpStream = TcpStream::connect(address).unwrap();
+        write!(
+            stream,
+            "{} {} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n",
+            method, path
+        )
+        .unwrap();
+        let mut response: String = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+
+    #[test]
+    fn serves_metrics_and_refuses_other_requests() {
+        let mut metrics_state: MetricsState = MetricsState::new();
+        metrics_state.process_count = Some(7);
+        metrics_state.cpu_snapshot = Some(CpuSnapshot::new());
+        let address: SocketAddr =
+            start_metrics_server("127.0.0.1:0", Arc::new(Mutex::new(metrics_state))).unwrap();
+        assert_ne!(address.port(), 0);
+
+        let metrics: String = send_request(address, "GET", "/metrics?format=text");
+        assert!(metrics.starts_with("HTTP/1.1 200"), "{}", metrics);
+        assert!(metrics.contains(PROMETHEUS_CONTENT_TYPE), "{}", metrics);
+        assert!(metrics.contains("\ntm_processes 7\n"), "{}", metrics);
+
+        let head: String = send_request(address, "HEAD", "/metrics");
+        assert!(head.starts_with("HTTP/1.1 200"), "{}", head);
+        assert!(!head.contains("tm_processes"), "{}", head);
+
+        let not_found: String = send_request(address, "GET", "/");
+        assert!(not_found.starts_with("HTTP/1.1 404"), "{}", not_found);
+
+        let not_allowed: String = send_request(address, "POST", "/metrics");
+        assert!(not_allowed.starts_with("HTTP/1.1 405"), "{}", not_allowed);
+    }
+
+    #[test]
+    fn reports_an_address_it_cannot_listen_on() {
+        let error: String =
+            start_metrics_server("not an address", Arc::new(Mutex::new(MetricsState::new())))
+                .unwrap_err();
+
+        assert!(error.starts_with("could not serve metrics on not an address: "));
+    }
+}
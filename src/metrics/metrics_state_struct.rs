@@ -0,0 +1,55 @@
+//! Metrics state structure.
+//!
+//! Represents the latest data served by the metrics endpoint.
+
+use crate::snapshots::{
+    cpu_snapshot_struct::CpuSnapshot, processes_snapshot_struct::ProcessesSnapshot,
+    system_snapshot_struct::SystemSnapshot,
+};
+
+/// Structure holding the latest snapshots seen on the way to the GUI.
+///
+/// Each field is None until its worker has sent a first snapshot.
+pub struct MetricsState {
+    pub cpu_snapshot: Option<CpuSnapshot>,
+    pub process_count: Option<usize>,
+    pub system_snapshot: Option<SystemSnapshot>,
+}
+
+impl MetricsState {
+    /// Creates a new MetricsState without any data.
+    ///
+    /// * Returns
+    /// A MetricsState with every field set to None
+    pub fn new() -> MetricsState {
+        MetricsState {
+            cpu_snapshot: None,
+            process_count: None,
+            system_snapshot: None,
+        }
+    }
+
+    /// Stores the latest CpuSnapshot.
+    ///
+    /// * Parameters
+    /// `cpu_snapshot` The snapshot to store
+    pub fn apply_cpu_snapshot(&mut self, cpu_snapshot: &CpuSnapshot) {
+        self.cpu_snapshot = Some(cpu_snapshot.clone());
+    }
+
+    /// Stores the number of processes in the latest ProcessesSnapshot.
+    ///
+    /// * Parameters
+    /// `processes_snapshot` The snapshot to count the processes of
+    pub fn apply_processes_snapshot(&mut self, processes_snapshot: &ProcessesSnapshot) {
+        self.process_count = Some(processes_snapshot.processes.len());
+    }
+
+    /// Stores the latest SystemSnapshot.
+    ///
+    /// * Parameters
+    /// `system_snapshot` The snapshot to store
+    pub fn apply_system_snapshot(&mut self, system_snapshot: &SystemSnapshot) {
+        self.system_snapshot = Some(system_snapshot.clone());
+    }
+}
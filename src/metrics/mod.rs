@@ -0,0 +1,8 @@
+//! Prometheus metrics endpoint.
+//!
+//! Serves the latest snapshots sent by the workers over HTTP in the Prometheus
+//! text exposition format.
+
+pub mod metrics_server;
+pub mod metrics_state_struct;
+pub mod prometheus;
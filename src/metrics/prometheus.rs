@@ -0,0 +1,181 @@
+//! Prometheus text format.
+//!
+//! Renders the metrics state in the Prometheus text exposition format.
+
+use std::fmt::Write;
+
+use crate::metrics::metrics_state_struct::MetricsState;
+
+/// The content type of the Prometheus text exposition format.
+pub const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Renders every metric that has data.
+///
+/// Metrics whose worker hasn't sent a snapshot yet are left out rather than
+/// reported as zero.
+///
+/// * Parameters
+/// `metrics_state` The latest data
+///
+/// * Returns
+/// The metrics, one sample per line
+pub fn render_metrics(metrics_state: &MetricsState) -> String {
+    let mut output: String = String::new();
+
+    if let Some(cpu_snapshot) = &metrics_state.cpu_snapshot {
+        write_metric_header(
+            &mut output,
+            "tm_cpu_usage_percent",
+            "Overall CPU usage in percent.",
+        );
+        let _ = writeln!(
+            output,
+            "tm_cpu_usage_percent {}",
+            cpu_snapshot.overall_cpu_usage
+        );
+
+        write_metric_header(
+            &mut output,
+            "tm_core_cpu_usage_percent",
+            "CPU usage of each core in percent.",
+        );
+        for (core, usage) in cpu_snapshot.per_core_cpu_usage.iter().enumerate() {
+            let _ = writeln!(
+                output,
+                "tm_core_cpu_usage_percent{{core=\"{}\"}} {}",
+                core, usage
+            );
+        }
+
+        write_metric_header(
+            &mut output,
+            "tm_core_frequency_megahertz",
+            "Current frequency of each core in MHz.",
+        );
+        for (core, frequency) in cpu_snapshot.per_core_frequency.iter().enumerate() {
+            let _ = writeln!(
+                output,
+                "tm_core_frequency_megahertz{{core=\"{}\"}} {}",
+                core, frequency
+            );
+        }
+
+        write_metric_header(&mut output, "tm_cpu_cores", "Number of CPU cores.");
+        let _ = writeln!(
+            output,
+            "tm_cpu_cores {}",
+            cpu_snapshot.per_core_cpu_usage.len()
+        );
+    }
+
+    if let Some(process_count) = metrics_state.process_count {
+        write_metric_header(&mut output, "tm_processes", "Number of running processes.");
+        let _ = writeln!(output, "tm_processes {}", process_count);
+    }
+
+    if let Some(system_snapshot) = &metrics_state.system_snapshot {
+        write_metric_header(
+            &mut output,
+            "tm_host_info",
+            "Host name and operating system, the value is always 1.",
+        );
+        let _ = writeln!(
+            output,
+            "tm_host_info{{host_name=\"{}\",system_name=\"{}\",system_version=\"{}\",system_architecture=\"{}\"}} 1",
+            escape_label_value(&system_snapshot.host_name),
+            escape_label_value(&system_snapshot.system_name),
+            escape_label_value(&system_snapshot.system_version),
+            escape_label_value(&system_snapshot.system_architecture)
+        );
+    }
+
+    output
+}
+
+/// Writes the HELP and TYPE lines of a gauge.
+///
+/// * Parameters
+/// `output` The text to append to
+/// `name` The metric name
+/// `help` The description of the metric
+fn write_metric_header(output: &mut String, name: &str, help: &str) {
+    let _ = writeln!(output, "# HELP {} {}", name, help);
+    let _ = writeln!(output, "# TYPE {} gauge", name);
+}
+
+/// Escapes backslashes, double quotes and newlines in a label value.
+///
+/// * Parameters
+/// `value` The label value
+///
+/// * Returns
+/// The value, safe to place between double quotes
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snapshots::{
+        cpu_snapshot_struct::CpuSnapshot, system_snapshot_struct::SystemSnapshot,
+    };
+
+    #[test]
+    fn renders_nothing_before_the_first_snapshot() {
+        assert_eq!(render_metrics(&MetricsState::new()), "");
+    }
+
+    #[test]
+    fn renders_every_metric_with_data() {
+        let mut cpu_snapshot: CpuSnapshot = CpuSnapshot::new();
+        cpu_snapshot.overall_cpu_usage = 37.5;
+        cpu_snapshot.per_core_cpu_usage = vec![50.0, 25.0];
+        cpu_snapshot.per_core_frequency = vec![3400, 1200];
+        let mut system_snapshot: SystemSnapshot = SystemSnapshot::new();
+        system_snapshot.host_name = String::from("box");
+        system_snapshot.system_name = String::from("Linux");
+        system_snapshot.system_version = String::from("6.8");
+        system_snapshot.system_architecture = String::from("x86_64");
+        let metrics_state: MetricsState = MetricsState {
+            cpu_snapshot: Some(cpu_snapshot),
+            process_count: Some(312),
+            system_snapshot: Some(system_snapshot),
+        };
+
+        assert_eq!(
+            render_metrics(&metrics_state),
+            "\
+# HELP tm_cpu_usage_percent Overall CPU usage in percent.
+# TYPE tm_cpu_usage_percent gauge
+tm_cpu_usage_percent 37.5
+# HELP tm_core_cpu_usage_percent CPU usage of each core in percent.
+# TYPE tm_core_cpu_usage_percent gauge
+tm_core_cpu_usage_percent{core=\"0\"} 50
+tm_core_cpu_usage_percent{core=\"1\"} 25
+# HELP tm_core_frequency_megahertz Current frequency of each core in MHz.
+# TYPE tm_core_frequency_megahertz gauge
+tm_core_frequency_megahertz{core=\"0\"} 3400
+tm_core_frequency_megahertz{core=\"1\"} 1200
+# HELP tm_cpu_cores Number of CPU cores.
+# TYPE tm_cpu_cores gauge
+tm_cpu_cores 2
+# HELP tm_processes Number of running processes.
+# TYPE tm_processes gauge
+tm_processes 312
+# HELP tm_host_info Host name and operating system, the value is always 1.
+# TYPE tm_host_info gauge
+tm_host_info{host_name=\"box\",system_name=\"Linux\",system_version=\"6.8\",system_architecture=\"x86_64\"} 1
+"
+        );
+    }
+
+    #[test]
+    fn escapes_label_values() {
+        assert_eq!(escape_label_value("plain"), "plain");
+        assert_eq!(escape_label_value("a \"b\"\\c\nd"), "a \\\"b\\\"\\\\c\\nd");
+    }
+}
@@ -0,0 +1,11 @@
+//! Session recording.
+//!
+//! Saves the snapshots sent by the workers to a file, and plays such a file back
+//! through the same channels so the GUI can't tell it apart from live data.
+
+pub mod recorded_snapshot_enum;
+pub mod recorder;
+pub mod recording_entry_struct;
+pub mod recording_header_struct;
+pub mod replay_status_struct;
+pub mod replayer;
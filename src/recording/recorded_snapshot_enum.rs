@@ -0,0 +1,17 @@
+//! Recorded snapshot enum.
+//!
+//! Represents one snapshot of any of the kinds that are recorded.
+
+use crate::snapshots::{
+    cpu_snapshot_struct::CpuSnapshot, processes_snapshot_struct::ProcessesSnapshot,
+    system_snapshot_struct::SystemSnapshot,
+};
+use serde::{Deserialize, Serialize};
+
+/// Enum holding a snapshot along with which channel it came from.
+#[derive(Serialize, Deserialize)]
+pub enum RecordedSnapshot {
+    Cpu(CpuSnapshot),
+    Processes(ProcessesSnapshot),
+    System(SystemSnapshot),
+}
@@ -0,0 +1,100 @@
+//! Recorder.
+//!
+//! Writes snapshots to a recording file as they pass from the workers to the GUI.
+
+use crate::{
+    channel::Channel,
+    data::time::unix_time_milliseconds,
+    recording::{
+        recorded_snapshot_enum::RecordedSnapshot, recording_entry_struct::RecordingEntry,
+        recording_header_struct::RecordingHeader,
+    },
+};
+use flate2::{Compression, write::GzEncoder};
+use serde::Serialize;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::sync::mpsc::{Receiver, Sender};
+use std::thread::{self, JoinHandle};
+
+/// Starts a background writer for a new recording file.
+///
+/// The recording is gzip-compressed JSON, one entry per line after a header line.
+/// The writer stops once every sender of entries has been dropped.
+///
+/// * Parameters
+/// `path` Where to create the recording, an existing file is replaced
+///
+/// * Returns
+/// The sender to pass recording entries to the writer, or the error met creating the file
+pub fn start_recording(path: &Path) -> io::Result<Sender<RecordingEntry>> {
+    let mut encoder: GzEncoder<BufWriter<File>> =
+        GzEncoder::new(BufWriter::new(File::create(path)?), Compression::default());
+    write_line(&mut encoder, &RecordingHeader::new())?;
+
+    let (entry_sender, entry_receiver) = Channel::<RecordingEntry>::new().split();
+    let _handle: JoinHandle<()> = thread::spawn(move || {
+        for entry in entry_receiver {
+            if let Err(e) = write_line(&mut encoder, &entry) {
+                eprintln!("Error writing recording: {}", e);
+                return;
+            }
+        }
+        if let Err(e) = encoder.try_finish() {
+            eprintln!("Error finishing recording: {}", e);
+        }
+    });
+
+    Ok(entry_sender)
+}
+
+/// Records every snapshot received on a channel, then passes it on.
+///
+/// Spawns a thread that sits between a worker and the GUI, so neither has to know
+/// the session is being recorded.
+///
+/// * Parameters
+/// `receiver` The receiver the worker's snapshots arrive on
+/// `entry_sender` The sender of the recording writer
+/// `into_recorded` Wraps a snapshot in the matching RecordedSnapshot variant
+///
+/// * Returns
+/// The receiver the GUI should read the snapshots from instead
+pub fn record_channel<T: Clone + Send + 'static>(
+    receiver: Receiver<T>,
+    entry_sender: Sender<RecordingEntry>,
+    into_recorded: fn(T) -> RecordedSnapshot,
+) -> Receiver<T> {
+    let (forward_sender, forward_receiver) = Channel::<T>::new().split();
+    let _handle: JoinHandle<()> = thread::spawn(move || {
+        for snapshot in receiver {
+            // a failed recording only stops the recording, the GUI keeps its snapshots
+            let _ = entry_sender.send(RecordingEntry {
+                timestamp_milliseconds: unix_time_milliseconds(),
+                snapshot: into_recorded(snapshot.clone()),
+            });
+            if forward_sender.send(snapshot).is_err() {
+                return;
+            }
+        }
+    });
+    forward_receiver
+}
+
+/// Writes one value as a line of JSON.
+///
+/// The encoder is flushed after every line, so a recording cut short by the app
+/// closing can still be read up to its last entry.
+///
+/// * Parameters
+/// `encoder` The compressed recording file
+/// `value` The value to write
+///
+/// * Returns
+/// The error met writing, if any
+fn write_line<T: Serialize>(encoder: &mut GzEncoder<BufWriter<File>>, value: &T) -> io::Result<()> {
+    serde_json::to_writer(&mut *encoder, value)?;
+    encoder.write_all(b"\n")?;
+    encoder.flush()
+}
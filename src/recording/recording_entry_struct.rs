@@ -0,0 +1,13 @@
+//! Recording entry structure.
+//!
+//! Represents one line of a recording file.
+
+use crate::recording::recorded_snapshot_enum::RecordedSnapshot;
+use serde::{Deserialize, Serialize};
+
+/// Structure holding a recorded snapshot and when it was received.
+#[derive(Serialize, Deserialize)]
+pub struct RecordingEntry {
+    pub timestamp_milliseconds: u64,
+    pub snapshot: RecordedSnapshot,
+}
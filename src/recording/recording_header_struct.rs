@@ -0,0 +1,29 @@
+//! Recording header structure.
+//!
+//! Represents the first line of a recording file.
+
+use serde::{Deserialize, Serialize};
+
+pub const RECORDING_FORMAT: &str = "tm-recording";
+pub const RECORDING_VERSION: u32 = 1;
+
+/// Structure identifying a file as a recording, and which version of the format
+/// its entries use.
+#[derive(Serialize, Deserialize)]
+pub struct RecordingHeader {
+    pub format: String,
+    pub version: u32,
+}
+
+impl RecordingHeader {
+    /// Creates the header written at the start of new recordings.
+    ///
+    /// * Returns
+    /// A RecordingHeader for the current format version
+    pub fn new() -> RecordingHeader {
+        RecordingHeader {
+            format: String::from(RECORDING_FORMAT),
+            version: RECORDING_VERSION,
+        }
+    }
+}
@@ -0,0 +1,13 @@
+//! Replay status structure.
+//!
+//! Represents how far the replayer has got through a recording.
+
+/// Structure holding the state of a replay.
+///
+/// Positions are measured from the first entry of the recording.
+#[derive(Clone, Copy)]
+pub struct ReplayStatus {
+    pub position_milliseconds: u64,
+    pub duration_milliseconds: u64,
+    pub playing: bool,
+}
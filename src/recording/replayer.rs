@@ -0,0 +1,238 @@
+//! Replayer.
+//!
+//! Plays a recording file back through the channels the workers normally send on.
+
+use crate::{
+    commands::{
+        process_command_enum::ProcessCommand,
+        process_command_outcome_struct::ProcessCommandOutcome, replay_command_enum::ReplayCommand,
+    },
+    recording::{
+        recorded_snapshot_enum::RecordedSnapshot,
+        recording_entry_struct::RecordingEntry,
+        recording_header_struct::{RECORDING_FORMAT, RECORDING_VERSION, RecordingHeader},
+        replay_status_struct::ReplayStatus,
+    },
+    snapshots::{
+        cpu_snapshot_struct::CpuSnapshot, processes_snapshot_struct::ProcessesSnapshot,
+        system_snapshot_struct::SystemSnapshot,
+    },
+};
+use flate2::read::GzDecoder;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+use std::sync::mpsc::{Receiver, RecvTimeoutError, Sender};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+const REPLAY_TICK_MILLISECONDS: u64 = 50;
+
+/// The senders a replay passes recorded snapshots and its status to.
+pub struct ReplaySenders {
+    pub cpu_snapshot_sender: Sender<CpuSnapshot>,
+    pub processes_snapshot_sender: Sender<ProcessesSnapshot>,
+    pub system_snapshot_sender: Sender<SystemSnapshot>,
+    pub replay_status_sender: Sender<ReplayStatus>,
+}
+
+/// Reads every entry of a recording file.
+///
+/// A recording cut short (e.g. by the app being killed) ends in a partial line or
+/// compressed block, the entries before it are kept.
+///
+/// * Parameters
+/// `path` The recording file
+///
+/// * Returns
+/// The entries in the order they were recorded, or the reason the file can't be replayed
+pub fn read_recording(path: &Path) -> io::Result<Vec<RecordingEntry>> {
+    let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidData, message);
+
+    let mut lines = BufReader::new(GzDecoder::new(File::open(path)?)).lines();
+    let header_line: String = lines
+        .next()
+        .ok_or_else(|| invalid(String::from("the file is empty")))??;
+    let header: RecordingHeader = serde_json::from_str(&header_line)
+        .map_err(|_| invalid(String::from("the file is not a recording")))?;
+    if header.format != RECORDING_FORMAT || header.version != RECORDING_VERSION {
+        return Err(invalid(format!(
+            "unsupported recording format {} version {}",
+            header.format, header.version
+        )));
+    }
+
+    let mut entries: Vec<RecordingEntry> = Vec::new();
+    for line in lines {
+        let Ok(line) = line else {
+            break;
+        };
+        let Ok(entry) = serde_json::from_str::<RecordingEntry>(&line) else {
+            break;
+        };
+        entries.push(entry);
+    }
+
+    if entries.is_empty() {
+        return Err(invalid(String::from("the recording holds no snapshots")));
+    }
+    Ok(entries)
+}
+
+/// Starts a background worker that plays a recording back.
+///
+/// Spawns a thread that sends each recorded snapshot once as much time has passed
+/// since the start of the replay as had passed since the start of the recording.
+/// The replay starts playing straight away and pauses at the end.
+///
+/// * Parameters
+/// `entries` The entries of the recording, must not be empty
+/// `replay_senders` The senders to pass snapshots and the replay status to
+/// `replay_command_receiver` Receiver for play, pause and seek commands from the GUI
+/// `process_command_receiver` Receiver for process commands, which are all refused
+/// `process_command_outcome_sender` Sender used to report the refusals
+pub fn send_recorded_snapshots(
+    entries: Vec<RecordingEntry>,
+    replay_senders: ReplaySenders,
+    replay_command_receiver: Receiver<ReplayCommand>,
+    process_command_receiver: Receiver<ProcessCommand>,
+    process_command_outcome_sender: Sender<ProcessCommandOutcome>,
+) {
+    let _handle: JoinHandle<()> = thread::spawn(move || {
+        let start_milliseconds: u64 = entries[0].timestamp_milliseconds;
+        let duration_milliseconds: u64 =
+            entries[entries.len() - 1].timestamp_milliseconds - start_milliseconds;
+        let offset = |entry: &RecordingEntry| entry.timestamp_milliseconds - start_milliseconds;
+
+        let mut position_milliseconds: u64 = 0;
+        let mut next_index: usize = 0;
+        let mut playing: bool = true;
+        let mut last_tick: Instant = Instant::now();
+
+        loop {
+            match replay_command_receiver
+                .recv_timeout(Duration::from_millis(REPLAY_TICK_MILLISECONDS))
+            {
+                Ok(ReplayCommand::Play) => {
+                    // playing from the end starts the recording over
+                    if position_milliseconds >= duration_milliseconds {
+                        position_milliseconds = 0;
+                        next_index = 0;
+                    }
+                    playing = true;
+                }
+                Ok(ReplayCommand::Pause) => playing = false,
+                Ok(ReplayCommand::Seek {
+                    position_milliseconds: target_milliseconds,
+                }) => {
+                    position_milliseconds = target_milliseconds.min(duration_milliseconds);
+                    next_index =
+                        entries.partition_point(|entry| offset(entry) < position_milliseconds);
+                    // the system snapshot is only recorded once, and the process table
+                    // shouldn't sit empty until the next processes snapshot
+                    for resend in [
+                        latest_entry_before(&entries, next_index, |snapshot| {
+                            matches!(snapshot, RecordedSnapshot::System(_))
+                        }),
+                        latest_entry_before(&entries, next_index, |snapshot| {
+                            matches!(snapshot, RecordedSnapshot::Processes(_))
+                        }),
+                    ]
+                    .into_iter()
+                    .flatten()
+                    {
+                        if !send_recorded_snapshot(&resend.snapshot, &replay_senders) {
+                            return;
+                        }
+                    }
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => return,
+            }
+
+            // the recorded processes no longer exist, or are different processes now
+            while let Ok(command) = process_command_receiver.try_recv() {
+                let _ = process_command_outcome_sender.send(ProcessCommandOutcome {
+                    command,
+                    error: Some(String::from("processes can't be changed during a replay")),
+                });
+            }
+
+            if playing {
+                position_milliseconds += last_tick.elapsed().as_millis() as u64;
+                if position_milliseconds >= duration_milliseconds {
+                    position_milliseconds = duration_milliseconds;
+                    playing = false;
+                }
+            }
+            last_tick = Instant::now();
+
+            while next_index < entries.len()
+                && offset(&entries[next_index]) <= position_milliseconds
+            {
+                if !send_recorded_snapshot(&entries[next_index].snapshot, &replay_senders) {
+                    return;
+                }
+                next_index += 1;
+            }
+
+            let replay_status: ReplayStatus = ReplayStatus {
+                position_milliseconds,
+                duration_milliseconds,
+                playing,
+            };
+            if replay_senders
+                .replay_status_sender
+                .send(replay_status)
+                .is_err()
+            {
+                return;
+            }
+        }
+    });
+}
+
+/// Finds the latest entry of a kind before a position in the recording.
+///
+/// * Parameters
+/// `entries` The entries of the recording
+/// `end_index` The index to search before
+/// `is_kind` Whether a snapshot is of the wanted kind
+///
+/// * Returns
+/// Some(&RecordingEntry) if an entry of the kind was found, None otherwise
+fn latest_entry_before(
+    entries: &[RecordingEntry],
+    end_index: usize,
+    is_kind: fn(&RecordedSnapshot) -> bool,
+) -> Option<&RecordingEntry> {
+    entries[..end_index]
+        .iter()
+        .rev()
+        .find(|entry| is_kind(&entry.snapshot))
+}
+
+/// Sends a recorded snapshot on the channel it was recorded from.
+///
+/// * Parameters
+/// `snapshot` The recorded snapshot
+/// `replay_senders` The senders to pass snapshots to
+///
+/// * Returns
+/// false if the GUI has stopped receiving, true otherwise
+fn send_recorded_snapshot(snapshot: &RecordedSnapshot, replay_senders: &ReplaySenders) -> bool {
+    match snapshot {
+        RecordedSnapshot::Cpu(cpu_snapshot) => replay_senders
+            .cpu_snapshot_sender
+            .send(cpu_snapshot.clone())
+            .is_ok(),
+        RecordedSnapshot::Processes(processes_snapshot) => replay_senders
+            .processes_snapshot_sender
+            .send(processes_snapshot.clone())
+            .is_ok(),
+        RecordedSnapshot::System(system_snapshot) => replay_senders
+            .system_snapshot_sender
+            .send(system_snapshot.clone())
+            .is_ok(),
+    }
+}
@@ -0,0 +1,15 @@
+//! Block device record structure.
+//!
+//! Represents the I/O activity of a single block device over one sampling interval.
+
+/// Structure holding I/O information about one block device.
+///
+/// Contains throughput and operation rates measured since the previous sample.
+#[derive(Clone)]
+pub struct BlockDeviceRecord {
+    pub name: String,
+    pub read_bytes_per_second: f64,
+    pub written_bytes_per_second: f64,
+    pub read_operations_per_second: f64,
+    pub write_operations_per_second: f64,
+}
@@ -2,23 +2,36 @@
 //!
 //! Represents a snapshot of CPU usage data at a point in time.
 
+use serde::{Deserialize, Serialize};
+
 /// Structure holding CPU usage information.
 /// 
-/// Contains overall CPU usage and per-core usage percentages.
+/// Contains overall CPU usage and per-core usage percentages, current frequencies
+/// and frequency scaling limits.
+/// Note: frequencies are in MHz, and the (minimum, maximum) limits are only known on Linux.
+/// Note: the timestamp is in milliseconds since the unix epoch.
+#[derive(Clone, Serialize, Deserialize)]
 pub struct CpuSnapshot {
+    #[serde(default)]
+    pub timestamp_milliseconds: u64,
     pub overall_cpu_usage: f32,
     pub per_core_cpu_usage: Vec<f32>,
+    pub per_core_frequency: Vec<u64>,
+    pub per_core_frequency_limits: Vec<Option<(u64, u64)>>,
 }
 
 impl CpuSnapshot {
     /// Creates a new CpuSnapshot with default values.
     /// 
     /// * Returns
-    /// A CpuSnapshot with zero usage and empty per-core vector
-    fn new() -> CpuSnapshot {
+    /// A CpuSnapshot with zero usage and empty per-core vectors
+    pub fn new() -> CpuSnapshot {
         CpuSnapshot {
+            timestamp_milliseconds: 0,
             overall_cpu_usage: 0.0,
             per_core_cpu_usage: Vec::new(),
+            per_core_frequency: Vec::new(),
+            per_core_frequency_limits: Vec::new(),
         }
     }
 }
@@ -0,0 +1,44 @@
+//! CPU times record structure.
+//!
+//! Represents how a CPU spent its time over one sampling interval.
+
+/// Structure holding the share of time a CPU spent in each state.
+///
+/// Every field is a percentage of the sampling interval, and the fields add up to 100.
+/// Note: guest time is not counted again in user and nice time, unlike in /proc/stat.
+#[derive(Clone, Copy, Default)]
+pub struct CpuTimesRecord {
+    pub user: f32,
+    pub nice: f32,
+    pub system: f32,
+    pub idle: f32,
+    pub iowait: f32,
+    pub irq: f32,
+    pub softirq: f32,
+    pub steal: f32,
+    pub guest: f32,
+}
+
+impl CpuTimesRecord {
+    /// Names of the busy states, in the order they are stacked in graphs.
+    pub const BUSY_STATES: [&'static str; 8] = [
+        "user", "nice", "system", "iowait", "irq", "softirq", "steal", "guest",
+    ];
+
+    /// Returns the share of time spent in each busy state.
+    ///
+    /// * Returns
+    /// The percentages in the same order as BUSY_STATES
+    pub fn busy_times(&self) -> [f32; 8] {
+        [
+            self.user,
+            self.nice,
+            self.system,
+            self.iowait,
+            self.irq,
+            self.softirq,
+            self.steal,
+            self.guest,
+        ]
+    }
+}
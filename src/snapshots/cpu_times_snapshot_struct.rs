@@ -0,0 +1,31 @@
+//! CPU times snapshot structure.
+//!
+//! Represents a snapshot of how the CPUs spent their time.
+
+use crate::snapshots::cpu_times_record_struct::CpuTimesRecord;
+
+/// Structure holding CPU time breakdowns.
+///
+/// Contains the breakdown of all CPUs together and of each logical CPU, in the same
+/// order as the per-core CPU usages.
+/// Note: the timestamp is in milliseconds since the unix epoch.
+pub struct CpuTimesSnapshot {
+    pub timestamp_milliseconds: u64,
+    pub overall: CpuTimesRecord,
+    pub per_core: Vec<CpuTimesRecord>,
+}
+
+impl CpuTimesSnapshot {
+    /// Creates a new CpuTimesSnapshot with default values.
+    ///
+    /// * Returns
+    /// A CpuTimesSnapshot with no timestamp, an all-zero overall breakdown and no
+    /// per-core breakdowns
+    pub fn new() -> CpuTimesSnapshot {
+        CpuTimesSnapshot {
+            timestamp_milliseconds: 0,
+            overall: CpuTimesRecord::default(),
+            per_core: Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,29 @@
+//! Disks snapshot structure.
+//!
+//! Represents a snapshot of filesystem usage and block device I/O.
+
+use crate::snapshots::{
+    block_device_record_struct::BlockDeviceRecord, filesystem_record_struct::FilesystemRecord,
+};
+
+/// Structure holding disk information.
+///
+/// Contains one record per mounted filesystem and one per block device.
+/// Note: block device I/O is only reported on Linux.
+pub struct DisksSnapshot {
+    pub filesystems: Vec<FilesystemRecord>,
+    pub block_devices: Vec<BlockDeviceRecord>,
+}
+
+impl DisksSnapshot {
+    /// Creates a new DisksSnapshot with default values.
+    ///
+    /// * Returns
+    /// A DisksSnapshot with no filesystems or block devices
+    pub fn new() -> DisksSnapshot {
+        DisksSnapshot {
+            filesystems: Vec::new(),
+            block_devices: Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,15 @@
+//! Filesystem record structure.
+//!
+//! Represents the capacity and usage of a single mounted filesystem.
+
+/// Structure holding information about one mounted filesystem.
+///
+/// Contains the device, where it is mounted, its type and space in bytes.
+#[derive(Clone)]
+pub struct FilesystemRecord {
+    pub device_name: String,
+    pub mount_point: String,
+    pub filesystem_type: String,
+    pub total_space: u64,
+    pub available_space: u64,
+}
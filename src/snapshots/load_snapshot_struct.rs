@@ -0,0 +1,36 @@
+//! Load snapshot structure.
+//!
+//! Represents a snapshot of the system load average and resource pressure.
+
+use crate::snapshots::pressure_record_struct::PressureRecord;
+
+/// Structure holding system load information.
+///
+/// Contains the 1, 5 and 15 minute load averages and the pressure on the cpu,
+/// memory and io.
+/// Note: pressure is only reported on Linux kernels built with PSI, None elsewhere.
+pub struct LoadSnapshot {
+    pub load_average_one: f64,
+    pub load_average_five: f64,
+    pub load_average_fifteen: f64,
+    pub cpu_pressure: Option<PressureRecord>,
+    pub memory_pressure: Option<PressureRecord>,
+    pub io_pressure: Option<PressureRecord>,
+}
+
+impl LoadSnapshot {
+    /// Creates a new LoadSnapshot with default values.
+    ///
+    /// * Returns
+    /// A LoadSnapshot with zero load and no pressure information
+    pub fn new() -> LoadSnapshot {
+        LoadSnapshot {
+            load_average_one: 0.0,
+            load_average_five: 0.0,
+            load_average_fifteen: 0.0,
+            cpu_pressure: None,
+            memory_pressure: None,
+            io_pressure: None,
+        }
+    }
+}
@@ -0,0 +1,33 @@
+//! Memory snapshot structure.
+//!
+//! Represents a snapshot of RAM and swap usage at a point in time.
+
+/// Structure holding memory usage information in bytes.
+///
+/// Contains RAM totals and usage, and swap totals and usage.
+/// Note: cached memory is only reported on Linux.
+pub struct MemorySnapshot {
+    pub total_memory: u64,
+    pub used_memory: u64,
+    pub available_memory: u64,
+    pub cached_memory: Option<u64>,
+    pub total_swap: u64,
+    pub used_swap: u64,
+}
+
+impl MemorySnapshot {
+    /// Creates a new MemorySnapshot with default values.
+    ///
+    /// * Returns
+    /// A MemorySnapshot with every value set to zero
+    pub fn new() -> MemorySnapshot {
+        MemorySnapshot {
+            total_memory: 0,
+            used_memory: 0,
+            available_memory: 0,
+            cached_memory: None,
+            total_swap: 0,
+            used_swap: 0,
+        }
+    }
+}
@@ -2,6 +2,24 @@
 //!
 //! Defines structs for holding system data snapshots captured from workers.
 
+pub mod block_device_record_struct;
 pub mod cpu_snapshot_struct;
+pub mod cpu_times_record_struct;
+pub mod cpu_times_snapshot_struct;
+pub mod disks_snapshot_struct;
+pub mod filesystem_record_struct;
+pub mod load_snapshot_struct;
+pub mod memory_snapshot_struct;
+pub mod network_interface_record_struct;
+pub mod network_snapshot_struct;
+pub mod pressure_averages_struct;
+pub mod pressure_record_struct;
+pub mod process_environment_struct;
+pub mod process_record_struct;
 pub mod processes_snapshot_struct;
+pub mod sensor_kind_enum;
+pub mod sensor_record_struct;
+pub mod sensors_snapshot_struct;
 pub mod system_snapshot_struct;
+pub mod thread_record_struct;
+pub mod threads_snapshot_struct;
@@ -0,0 +1,18 @@
+//! Network interface record structure.
+//!
+//! Represents the traffic of a single network interface over one sampling interval.
+
+/// Structure holding traffic information about one network interface.
+///
+/// Contains transfer rates measured since the previous sample, and error totals
+/// since the interface came up.
+#[derive(Clone)]
+pub struct NetworkInterfaceRecord {
+    pub name: String,
+    pub received_bytes_per_second: f64,
+    pub transmitted_bytes_per_second: f64,
+    pub received_packets_per_second: f64,
+    pub transmitted_packets_per_second: f64,
+    pub total_receive_errors: u64,
+    pub total_transmit_errors: u64,
+}
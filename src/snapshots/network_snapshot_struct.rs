@@ -0,0 +1,24 @@
+//! Network snapshot structure.
+//!
+//! Represents a snapshot of network traffic on every interface.
+
+use crate::snapshots::network_interface_record_struct::NetworkInterfaceRecord;
+
+/// Structure holding network traffic information.
+///
+/// Contains one record per network interface.
+pub struct NetworkSnapshot {
+    pub interfaces: Vec<NetworkInterfaceRecord>,
+}
+
+impl NetworkSnapshot {
+    /// Creates a new NetworkSnapshot with default values.
+    ///
+    /// * Returns
+    /// A NetworkSnapshot with no interfaces
+    pub fn new() -> NetworkSnapshot {
+        NetworkSnapshot {
+            interfaces: Vec::new(),
+        }
+    }
+}
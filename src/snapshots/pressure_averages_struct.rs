@@ -0,0 +1,13 @@
+//! Pressure averages structure.
+//!
+//! Represents one line of a Linux pressure stall information (PSI) file.
+
+/// Structure holding the share of time tasks were stalled on a resource.
+///
+/// Every field is a percentage, averaged over the last 10, 60 and 300 seconds.
+#[derive(Clone, Copy)]
+pub struct PressureAverages {
+    pub avg10: f32,
+    pub avg60: f32,
+    pub avg300: f32,
+}
@@ -0,0 +1,16 @@
+//! Pressure record structure.
+//!
+//! Represents the pressure stall information (PSI) of one resource.
+
+use crate::snapshots::pressure_averages_struct::PressureAverages;
+
+/// Structure holding the pressure on one resource (cpu, memory or io).
+///
+/// "some" is the time at least one task was stalled, "full" the time every
+/// non-idle task was stalled at once.
+/// Note: older kernels don't report "full" for the cpu.
+#[derive(Clone, Copy)]
+pub struct PressureRecord {
+    pub some: PressureAverages,
+    pub full: Option<PressureAverages>,
+}
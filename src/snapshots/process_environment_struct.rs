@@ -0,0 +1,14 @@
+//! Process environment structure.
+//!
+//! Represents the environment variables of the process in the detail window.
+
+use crate::data::process_key_struct::ProcessKey;
+
+/// Structure holding the environment of one process, read when the GUI asks for it.
+///
+/// Note: the variables are "NAME=value" strings, the error explains why they
+/// couldn't be read.
+pub struct ProcessEnvironment {
+    pub process_key: ProcessKey,
+    pub environment: Result<Vec<String>, String>,
+}
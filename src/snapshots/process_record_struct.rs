@@ -0,0 +1,43 @@
+//! Process record structure.
+//!
+//! Represents the state of a single process at a point in time.
+
+use serde::{Deserialize, Serialize};
+
+/// Structure holding information about one process.
+///
+/// Contains identifiers, ownership, resource usage and launch details.
+/// Note: memory is the resident set size, both memory sizes are in bytes.
+/// Note: thread and open file counts, the nice value and the affinity are None where
+/// the platform or permissions don't allow reading them. The open file count, the
+/// nice value and the affinity are also None unless the GUI asked for them, for the
+/// selected process and the one in the detail window.
+/// Note: the affinity lists the indices of the cores the process may run on.
+/// Note: the fields after command default when replaying a recording made before
+/// they were added.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ProcessRecord {
+    pub pid: u32,
+    pub parent_pid: Option<u32>,
+    pub name: String,
+    pub user: String,
+    pub cpu_usage: f32,
+    pub memory: u64,
+    pub status: String,
+    pub start_time: u64,
+    pub command: String,
+    #[serde(default)]
+    pub virtual_memory: u64,
+    #[serde(default)]
+    pub thread_count: Option<usize>,
+    #[serde(default)]
+    pub open_file_count: Option<usize>,
+    #[serde(default)]
+    pub disk_read_bytes_per_second: f64,
+    #[serde(default)]
+    pub disk_written_bytes_per_second: f64,
+    #[serde(default)]
+    pub nice: Option<i32>,
+    #[serde(default)]
+    pub affinity: Option<Vec<usize>>,
+}
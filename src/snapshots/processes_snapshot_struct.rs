@@ -1,20 +1,26 @@
 //! Processes snapshot structure.
 //!
-//! Represents a snapshot of process count data.
+//! Represents a snapshot of every process running on the system.
 
-/// Structure holding process count information.
-/// 
-/// Contains the number of running processes.
+use crate::snapshots::process_record_struct::ProcessRecord;
+use serde::{Deserialize, Serialize};
+
+/// Structure holding process information.
+///
+/// Contains one record per running process.
+#[derive(Clone, Serialize, Deserialize)]
 pub struct ProcessesSnapshot {
-    pub processes: usize,
+    pub processes: Vec<ProcessRecord>,
 }
 
 impl ProcessesSnapshot {
     /// Creates a new ProcessesSnapshot with default values.
-    /// 
+    ///
     /// * Returns
-    /// A ProcessesSnapshot with zero processes
-    fn new() -> ProcessesSnapshot {
-        ProcessesSnapshot { processes: 0 }
+    /// A ProcessesSnapshot with no processes
+    pub fn new() -> ProcessesSnapshot {
+        ProcessesSnapshot {
+            processes: Vec::new(),
+        }
     }
 }
@@ -0,0 +1,42 @@
+//! Sensor kind enum.
+//!
+//! Represents what a hardware sensor measures.
+
+/// Enum holding the kind of quantity a sensor reports.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SensorKind {
+    Temperature,
+    Fan,
+    Voltage,
+    Power,
+}
+
+impl SensorKind {
+    /// Returns the unit readings of this kind are reported in.
+    ///
+    /// * Returns
+    /// The unit symbol, e.g. "°C"
+    pub fn unit(&self) -> &'static str {
+        match self {
+            SensorKind::Temperature => "°C",
+            SensorKind::Fan => "RPM",
+            SensorKind::Voltage => "V",
+            SensorKind::Power => "W",
+        }
+    }
+
+    /// Formats a reading of this kind with its unit.
+    ///
+    /// * Parameters
+    /// `value` The reading
+    ///
+    /// * Returns
+    /// The formatted reading, e.g. "45.0 °C" or "1200 RPM"
+    pub fn format_value(&self, value: f32) -> String {
+        match self {
+            SensorKind::Temperature | SensorKind::Power => format!("{:.1} {}", value, self.unit()),
+            SensorKind::Fan => format!("{:.0} {}", value, self.unit()),
+            SensorKind::Voltage => format!("{:.2} {}", value, self.unit()),
+        }
+    }
+}
@@ -0,0 +1,31 @@
+//! Sensor record structure.
+//!
+//! Represents a single reading of one hardware sensor.
+
+use crate::snapshots::sensor_kind_enum::SensorKind;
+
+/// Structure holding one hardware sensor reading.
+///
+/// Contains the chip the sensor belongs to, its label, and the reading in the
+/// unit of its kind.
+/// Note: the id is unique between sensors and stays the same between samples.
+#[derive(Clone)]
+pub struct SensorRecord {
+    pub id: String,
+    pub chip: String,
+    pub label: String,
+    pub kind: SensorKind,
+    pub value: f32,
+    pub critical: Option<f32>,
+}
+
+impl SensorRecord {
+    /// Checks whether the reading has reached the sensor's critical threshold.
+    ///
+    /// * Returns
+    /// true if the sensor has a critical threshold and the reading is at or above it
+    pub fn is_critical(&self) -> bool {
+        self.critical
+            .is_some_and(|critical| critical > 0.0 && self.value >= critical)
+    }
+}
@@ -0,0 +1,28 @@
+//! Sensors snapshot structure.
+//!
+//! Represents a snapshot of hardware sensor readings.
+
+use crate::snapshots::sensor_record_struct::SensorRecord;
+
+/// Structure holding hardware sensor information.
+///
+/// Contains every sensor reading, and the temperature of each logical CPU's
+/// physical core in the same order as the per-core CPU usages.
+/// Note: core temperatures are None where no sensor reports them.
+pub struct SensorsSnapshot {
+    pub sensors: Vec<SensorRecord>,
+    pub core_temperatures: Vec<Option<f32>>,
+}
+
+impl SensorsSnapshot {
+    /// Creates a new SensorsSnapshot with default values.
+    ///
+    /// * Returns
+    /// A SensorsSnapshot with no readings
+    pub fn new() -> SensorsSnapshot {
+        SensorsSnapshot {
+            sensors: Vec::new(),
+            core_temperatures: Vec::new(),
+        }
+    }
+}
@@ -2,9 +2,12 @@
 //!
 //! Represents a snapshot of system information data.
 
+use serde::{Deserialize, Serialize};
+
 /// Structure holding system information.
 /// 
 /// Contains system name, version, architecture, and host name.
+#[derive(Clone, Serialize, Deserialize)]
 pub struct SystemSnapshot {
     pub system_name: String,
     pub system_version: String,
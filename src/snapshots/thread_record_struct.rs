@@ -0,0 +1,16 @@
+//! Thread record structure.
+//!
+//! Represents the state of a single thread of a process at a point in time.
+
+/// Structure holding information about one thread.
+///
+/// Note: cpu usage is in percent of one core, None at the first sample of a thread
+/// as it is worked out from two samples.
+/// Note: last core is the index of the core the thread last ran on.
+pub struct ThreadRecord {
+    pub tid: u32,
+    pub name: String,
+    pub status: String,
+    pub cpu_usage: Option<f32>,
+    pub last_core: Option<usize>,
+}
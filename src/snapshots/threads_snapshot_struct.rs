@@ -0,0 +1,14 @@
+//! Threads snapshot structure.
+//!
+//! Represents a snapshot of the threads of the process selected in the GUI.
+
+use crate::{data::process_key_struct::ProcessKey, snapshots::thread_record_struct::ThreadRecord};
+
+/// Structure holding the threads of one process.
+///
+/// Note: error explains why the threads couldn't be listed, the threads are empty then.
+pub struct ThreadsSnapshot {
+    pub process_key: ProcessKey,
+    pub threads: Vec<ThreadRecord>,
+    pub error: Option<String>,
+}
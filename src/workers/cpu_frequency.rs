@@ -0,0 +1,59 @@
+//! CPU frequency limits.
+//!
+//! Reads the frequency scaling limits of each logical CPU, which sysinfo doesn't report.
+
+#[cfg(target_os = "linux")]
+use std::path::Path;
+
+#[cfg(target_os = "linux")]
+const CPU_ROOT: &str = "/sys/devices/system/cpu";
+
+/// Reads the frequency scaling limits of every logical CPU.
+///
+/// The limits are the ones the cpufreq governor is currently allowed to pick
+/// between, so a lowered maximum shows when the CPU is being throttled by policy.
+///
+/// * Parameters
+/// `cpu_count` The number of logical CPUs
+///
+/// * Returns
+/// The (minimum, maximum) frequency of each CPU in MHz, None where unavailable
+#[cfg(target_os = "linux")]
+pub fn read_frequency_limits(cpu_count: usize) -> Vec<Option<(u64, u64)>> {
+    read_frequency_limits_from(Path::new(CPU_ROOT), cpu_count)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn read_frequency_limits(cpu_count: usize) -> Vec<Option<(u64, u64)>> {
+    vec![None; cpu_count]
+}
+
+/// Reads the frequency scaling limits of every logical CPU under a sysfs cpu directory.
+///
+/// * Parameters
+/// `cpu_root` The cpu directory, /sys/devices/system/cpu outside of tests
+/// `cpu_count` The number of logical CPUs
+///
+/// * Returns
+/// The (minimum, maximum) frequency of each CPU in MHz, None where unavailable
+#[cfg(target_os = "linux")]
+pub fn read_frequency_limits_from(cpu_root: &Path, cpu_count: usize) -> Vec<Option<(u64, u64)>> {
+    // cpufreq reports frequencies in kHz
+    let read_mhz = |path: &Path| -> Option<u64> {
+        let contents: String = std::fs::read_to_string(path).ok()?;
+        contents
+            .trim()
+            .parse::<u64>()
+            .ok()
+            .map(|frequency| frequency / 1000)
+    };
+
+    (0..cpu_count)
+        .map(|index| {
+            let cpufreq_path = cpu_root.join(format!("cpu{}", index)).join("cpufreq");
+            let minimum: u64 = read_mhz(&cpufreq_path.join("scaling_min_freq"))?;
+            let maximum: u64 = read_mhz(&cpufreq_path.join("scaling_max_freq"))?;
+            Some((minimum, maximum))
+        })
+        .collect()
+}
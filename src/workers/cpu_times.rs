@@ -0,0 +1,409 @@
+//! CPU times worker.
+//!
+//! Background worker for collecting the user/system/iowait/... breakdown of CPU time.
+
+// the parser is only driven by the Linux worker
+#![cfg_attr(not(target_os = "linux"), allow(dead_code))]
+
+use crate::snapshots::{
+    cpu_times_record_struct::CpuTimesRecord, cpu_times_snapshot_struct::CpuTimesSnapshot,
+};
+use std::sync::mpsc::Sender;
+
+/// Cumulative time counters of one CPU, as read from /proc/stat.
+///
+/// The counters are in clock ticks since boot.
+#[derive(Clone, Copy, Default)]
+pub struct CpuTimeCounters {
+    pub user: u64,
+    pub nice: u64,
+    pub system: u64,
+    pub idle: u64,
+    pub iowait: u64,
+    pub irq: u64,
+    pub softirq: u64,
+    pub steal: u64,
+    pub guest: u64,
+    pub guest_nice: u64,
+}
+
+/// Time counters of every CPU, as read from /proc/stat.
+pub struct ProcStatCounters {
+    pub overall: CpuTimeCounters,
+    pub per_core: Vec<CpuTimeCounters>,
+}
+
+/// Starts a background worker to collect CPU times snapshots.
+///
+/// Spawns a thread that periodically sends CpuTimesSnapshot data via the channel.
+/// Each snapshot holds the breakdown of the time passed since the previous sample.
+///
+/// * Parameters
+/// `sender` The channel sender for CpuTimesSnapshot data
+#[cfg(target_os = "linux")]
+pub fn send_cpu_times_snapshot(sender: Sender<CpuTimesSnapshot>) {
+    use crate::{data::time::unix_time_milliseconds, workers::sampling::sleep_sampling_interval};
+    use std::thread::{self, JoinHandle};
+
+    let read_counters = || -> Option<ProcStatCounters> {
+        parse_proc_stat(&std::fs::read_to_string("/proc/stat").ok()?)
+    };
+
+    let _handle: JoinHandle<()> = thread::spawn(move || {
+        let Some(mut previous_counters) = read_counters() else {
+            eprintln!("Error reading /proc/stat, CPU times are unavailable");
+            return;
+        };
+        loop {
+            sleep_sampling_interval();
+
+            let Some(counters) = read_counters() else {
+                continue;
+            };
+            let mut cpu_times_snapshot: CpuTimesSnapshot =
+                build_cpu_times_snapshot(&previous_counters, &counters);
+            cpu_times_snapshot.timestamp_milliseconds = unix_time_milliseconds();
+            previous_counters = counters;
+
+            if let Err(e) = sender.send(cpu_times_snapshot) {
+                eprintln!("Error sending CPU times snapshot: {}", e);
+                return;
+            }
+        }
+    });
+}
+
+// the breakdown comes from /proc/stat, so elsewhere no snapshots are ever sent
+// and the graphs keep showing plain utilisation
+#[cfg(not(target_os = "linux"))]
+pub fn send_cpu_times_snapshot(_sender: Sender<CpuTimesSnapshot>) {}
+
+/// Builds a CpuTimesSnapshot from two samples of /proc/stat.
+///
+/// * Parameters
+/// `previous` The counters at the previous sample
+/// `current` The counters at this sample
+///
+/// * Returns
+/// The breakdown of the time between the two samples. Per-core breakdowns are left
+/// out when the number of CPUs changed between the samples
+pub fn build_cpu_times_snapshot(
+    previous: &ProcStatCounters,
+    current: &ProcStatCounters,
+) -> CpuTimesSnapshot {
+    let mut cpu_times_snapshot: CpuTimesSnapshot = CpuTimesSnapshot::new();
+    cpu_times_snapshot.overall = cpu_times_between(&previous.overall, &current.overall);
+    if previous.per_core.len() == current.per_core.len() {
+        cpu_times_snapshot.per_core = previous
+            .per_core
+            .iter()
+            .zip(current.per_core.iter())
+            .map(|(previous, current)| cpu_times_between(previous, current))
+            .collect();
+    }
+    cpu_times_snapshot
+}
+
+/// Works out how a CPU spent the time between two samples of its counters.
+///
+/// /proc/stat counts guest time in user time (and guest_nice in nice) as well, so it
+/// is taken out of them to keep every tick in exactly one state.
+///
+/// * Parameters
+/// `previous` The counters at the previous sample
+/// `current` The counters at this sample
+///
+/// * Returns
+/// The percentage of the interval spent in each state, all zero if no time passed
+pub fn cpu_times_between(previous: &CpuTimeCounters, current: &CpuTimeCounters) -> CpuTimesRecord {
+    // counters can go backwards when a CPU is hotplugged, so differences saturate at zero
+    let delta = |previous: u64, current: u64| current.saturating_sub(previous) as f64;
+
+    let guest: f64 = delta(previous.guest, current.guest);
+    let guest_nice: f64 = delta(previous.guest_nice, current.guest_nice);
+    let user: f64 = (delta(previous.user, current.user) - guest).max(0.0);
+    let nice: f64 = (delta(previous.nice, current.nice) - guest_nice).max(0.0);
+    let system: f64 = delta(previous.system, current.system);
+    let idle: f64 = delta(previous.idle, current.idle);
+    let iowait: f64 = delta(previous.iowait, current.iowait);
+    let irq: f64 = delta(previous.irq, current.irq);
+    let softirq: f64 = delta(previous.softirq, current.softirq);
+    let steal: f64 = delta(previous.steal, current.steal);
+    let guest: f64 = guest + guest_nice;
+
+    let total: f64 = user + nice + system + idle + iowait + irq + softirq + steal + guest;
+    if total == 0.0 {
+        return CpuTimesRecord::default();
+    }
+    let percentage = |ticks: f64| (ticks / total * 100.0) as f32;
+
+    CpuTimesRecord {
+        user: percentage(user),
+        nice: percentage(nice),
+        system: percentage(system),
+        idle: percentage(idle),
+        iowait: percentage(iowait),
+        irq: percentage(irq),
+        softirq: percentage(softirq),
+        steal: percentage(steal),
+        guest: percentage(guest),
+    }
+}
+
+/// Parses the CPU lines of /proc/stat.
+///
+/// Older kernels report fewer columns, the missing counters are read as zero.
+///
+/// * Parameters
+/// `proc_stat` The contents of /proc/stat
+///
+/// * Returns
+/// Some(ProcStatCounters) with the per-core counters in file order, or None if
+/// the overall "cpu" line is missing or malformed
+pub fn parse_proc_stat(proc_stat: &str) -> Option<ProcStatCounters> {
+    let mut overall: Option<CpuTimeCounters> = None;
+    let mut per_core: Vec<CpuTimeCounters> = Vec::new();
+
+    for line in proc_stat.lines() {
+        let mut fields = line.split_whitespace();
+        let Some(name) = fields.next() else {
+            continue;
+        };
+        let Some(core) = name.strip_prefix("cpu") else {
+            continue;
+        };
+
+        let values: Vec<u64> = fields
+            .map(|field| field.parse::<u64>())
+            .collect::<Result<Vec<u64>, _>>()
+            .ok()?;
+        // user, nice, system and idle have been reported by every kernel
+        if values.len() < 4 {
+            return None;
+        }
+        let value = |index: usize| values.get(index).copied().unwrap_or(0);
+        let counters: CpuTimeCounters = CpuTimeCounters {
+            user: value(0),
+            nice: value(1),
+            system: value(2),
+            idle: value(3),
+            iowait: value(4),
+            irq: value(5),
+            softirq: value(6),
+            steal: value(7),
+            guest: value(8),
+            guest_nice: value(9),
+        };
+
+        if core.is_empty() {
+            overall = Some(counters);
+        } else {
+            per_core.push(counters);
+        }
+    }
+
+    Some(ProcStatCounters {
+        overall: overall?,
+        per_core,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// /proc/stat of a two core machine on a current kernel, cut short after btime.
+    const PROC_STAT: &str = "\
+cpu  4705 356 584 3699176 23060 0 277 12 90 7
+cpu0 1393 280 301 1849420 11571 0 204 5 40 3
+cpu1 3312 76 283 1849756 11489 0 73 7 50 4
+intr 1462898 9 0 0 0 0 0 0 0 1 0 0 0 0 0 0 0 0 0
+ctxt 2123781
+btime 1700000000
+processes 12345
+procs_running 1
+procs_blocked 0
+softirq 501283 0 160520 26 1405 92 0 6 167412 0 165822
+";
+
+    fn counters(counters: &CpuTimeCounters) -> [u64; 10] {
+        [
+            counters.user,
+            counters.nice,
+            counters.system,
+            counters.idle,
+            counters.iowait,
+            counters.irq,
+            counters.softirq,
+            counters.steal,
+            counters.guest,
+            counters.guest_nice,
+        ]
+    }
+
+    fn counters_from(values: [u64; 10]) -> CpuTimeCounters {
+        CpuTimeCounters {
+            user: values[0],
+            nice: values[1],
+            system: values[2],
+            idle: values[3],
+            iowait: values[4],
+            irq: values[5],
+            softirq: values[6],
+            steal: values[7],
+            guest: values[8],
+            guest_nice: values[9],
+        }
+    }
+
+    fn rounded_times(record: &CpuTimesRecord) -> [f32; 9] {
+        [
+            record.user,
+            record.nice,
+            record.system,
+            record.idle,
+            record.iowait,
+            record.irq,
+            record.softirq,
+            record.steal,
+            record.guest,
+        ]
+        .map(|percentage| (percentage * 100.0).round() / 100.0)
+    }
+
+    #[test]
+    fn parses_overall_and_per_core_lines() {
+        let proc_stat_counters: ProcStatCounters = parse_proc_stat(PROC_STAT).unwrap();
+
+        assert_eq!(
+            counters(&proc_stat_counters.overall),
+            [4705, 356, 584, 3699176, 23060, 0, 277, 12, 90, 7]
+        );
+        let per_core: Vec<[u64; 10]> = proc_stat_counters.per_core.iter().map(counters).collect();
+        assert_eq!(
+            per_core,
+            vec![
+                [1393, 280, 301, 1849420, 11571, 0, 204, 5, 40, 3],
+                [3312, 76, 283, 1849756, 11489, 0, 73, 7, 50, 4],
+            ]
+        );
+    }
+
+    #[test]
+    fn missing_columns_are_read_as_zero() {
+        // 2.6.11 to 2.6.23 have no guest columns, 2.6.0 to 2.6.10 no steal either,
+        // and 2.4 only has the first four
+        let proc_stat: &str = "\
+cpu  100 2 30 900 4 5 6 7
+cpu0 50 1 15 450 2 3 3
+cpu1 50 1 15 450
+ctxt 42
+";
+        let proc_stat_counters: ProcStatCounters = parse_proc_stat(proc_stat).unwrap();
+
+        assert_eq!(
+            counters(&proc_stat_counters.overall),
+            [100, 2, 30, 900, 4, 5, 6, 7, 0, 0]
+        );
+        assert_eq!(
+            counters(&proc_stat_counters.per_core[0]),
+            [50, 1, 15, 450, 2, 3, 3, 0, 0, 0]
+        );
+        assert_eq!(
+            counters(&proc_stat_counters.per_core[1]),
+            [50, 1, 15, 450, 0, 0, 0, 0, 0, 0]
+        );
+    }
+
+    #[test]
+    fn malformed_cpu_lines_are_rejected() {
+        // no overall line
+        assert!(parse_proc_stat("cpu0 1 2 3 4\nctxt 42\n").is_none());
+        assert!(parse_proc_stat("").is_none());
+        // a counter that isn't a number
+        assert!(parse_proc_stat("cpu  1 2 x 4\n").is_none());
+        assert!(parse_proc_stat("cpu  1 2 3 4\ncpu0 1 2 3 -4\n").is_none());
+        // fewer than the four columns every kernel reports
+        assert!(parse_proc_stat("cpu  1 2 3\n").is_none());
+    }
+
+    #[test]
+    fn guest_time_is_taken_out_of_user_and_nice() {
+        let previous: CpuTimeCounters = counters_from([1000, 500, 0, 0, 0, 0, 0, 0, 200, 100]);
+        // 100 user ticks of which 40 ran a guest, 50 nice ticks of which 10 ran a niced guest
+        let current: CpuTimeCounters = counters_from([1100, 550, 50, 100, 0, 0, 0, 0, 240, 110]);
+
+        let cpu_times_record: CpuTimesRecord = cpu_times_between(&previous, &current);
+
+        // 300 ticks: user 60, nice 40, system 50, idle 100 and guest 50
+        assert_eq!(
+            rounded_times(&cpu_times_record),
+            [20.0, 13.33, 16.67, 33.33, 0.0, 0.0, 0.0, 0.0, 16.67]
+        );
+    }
+
+    #[test]
+    fn guest_time_above_user_time_does_not_go_negative() {
+        let previous: CpuTimeCounters = CpuTimeCounters::default();
+        let current: CpuTimeCounters = counters_from([10, 0, 0, 70, 0, 0, 0, 0, 30, 0]);
+
+        let cpu_times_record: CpuTimesRecord = cpu_times_between(&previous, &current);
+
+        assert_eq!(
+            rounded_times(&cpu_times_record),
+            [0.0, 0.0, 0.0, 70.0, 0.0, 0.0, 0.0, 0.0, 30.0]
+        );
+    }
+
+    #[test]
+    fn no_time_passed_gives_all_zero() {
+        let counters: CpuTimeCounters =
+            counters_from([4705, 356, 584, 3699176, 23060, 0, 277, 12, 90, 7]);
+
+        let cpu_times_record: CpuTimesRecord = cpu_times_between(&counters, &counters);
+
+        assert_eq!(rounded_times(&cpu_times_record), [0.0; 9]);
+    }
+
+    #[test]
+    fn counters_going_backwards_count_as_zero() {
+        // a hotplugged CPU starts counting again from zero
+        let previous: CpuTimeCounters = counters_from([5000, 0, 2000, 90000, 300, 0, 0, 0, 0, 0]);
+        let current: CpuTimeCounters = counters_from([30, 0, 10, 60, 300, 0, 0, 0, 0, 0]);
+        assert_eq!(
+            rounded_times(&cpu_times_between(&previous, &current)),
+            [0.0; 9]
+        );
+
+        // only the counters that went forward are counted
+        let current: CpuTimeCounters = counters_from([5075, 0, 10, 90025, 300, 0, 0, 0, 0, 0]);
+        assert_eq!(
+            rounded_times(&cpu_times_between(&previous, &current)),
+            [75.0, 0.0, 0.0, 25.0, 0.0, 0.0, 0.0, 0.0, 0.0]
+        );
+    }
+
+    #[test]
+    fn per_core_breakdowns_are_dropped_when_the_core_count_changes() {
+        let previous: ProcStatCounters = parse_proc_stat(PROC_STAT).unwrap();
+        let same_cores: ProcStatCounters = parse_proc_stat(PROC_STAT).unwrap();
+        let fewer_cores: ProcStatCounters =
+            parse_proc_stat("cpu  4805 356 584 3699276 23060 0 277 12 90 7\ncpu0 1 2 3 4\n")
+                .unwrap();
+
+        assert_eq!(
+            build_cpu_times_snapshot(&previous, &same_cores)
+                .per_core
+                .len(),
+            2
+        );
+
+        let cpu_times_snapshot: CpuTimesSnapshot =
+            build_cpu_times_snapshot(&previous, &fewer_cores);
+        assert!(cpu_times_snapshot.per_core.is_empty());
+        assert_eq!(
+            rounded_times(&cpu_times_snapshot.overall),
+            [50.0, 0.0, 0.0, 50.0, 0.0, 0.0, 0.0, 0.0, 0.0]
+        );
+    }
+}
@@ -0,0 +1,174 @@
+//! Disks worker.
+//!
+//! Background worker for collecting filesystem usage and block device I/O data.
+
+use crate::snapshots::{
+    block_device_record_struct::BlockDeviceRecord, disks_snapshot_struct::DisksSnapshot,
+    filesystem_record_struct::FilesystemRecord,
+};
+use crate::workers::sampling::sleep_sampling_interval;
+use std::collections::HashMap;
+use std::sync::mpsc::Sender;
+use std::thread::{self, JoinHandle};
+use std::time::Instant;
+use sysinfo::Disks;
+
+/// /proc/diskstats always counts sectors of 512 bytes, whatever the device's sector size.
+const DISKSTATS_SECTOR_BYTES: u64 = 512;
+
+/// Cumulative I/O counters of one block device, as read from /proc/diskstats.
+#[derive(Clone, Copy)]
+pub struct DiskCounters {
+    pub reads_completed: u64,
+    pub sectors_read: u64,
+    pub writes_completed: u64,
+    pub sectors_written: u64,
+}
+
+/// Starts a background worker to collect disk snapshots.
+///
+/// Spawns a thread that periodically sends DisksSnapshot data via the channel.
+/// Block device rates are computed from the counters' change since the previous sample.
+///
+/// * Parameters
+/// `sender` The channel sender for DisksSnapshot data
+pub fn send_disks_snapshot(sender: Sender<DisksSnapshot>) {
+    let mut disks: Disks = Disks::new_with_refreshed_list();
+    let _handle: JoinHandle<()> = thread::spawn(move || {
+        let mut previous_counters: HashMap<String, DiskCounters> = read_disk_counters();
+        let mut last_sample: Instant = Instant::now();
+        loop {
+            sleep_sampling_interval();
+
+            // refreshing the list also picks up filesystems that were mounted or unmounted
+            disks.refresh_list();
+            let counters: HashMap<String, DiskCounters> = read_disk_counters();
+            let elapsed_seconds: f64 = last_sample.elapsed().as_secs_f64();
+            last_sample = Instant::now();
+
+            let mut disks_snapshot: DisksSnapshot = DisksSnapshot::new();
+            for disk in disks.list() {
+                disks_snapshot.filesystems.push(FilesystemRecord {
+                    device_name: disk.name().to_string_lossy().into_owned(),
+                    mount_point: disk.mount_point().to_string_lossy().into_owned(),
+                    filesystem_type: disk.file_system().to_string_lossy().into_owned(),
+                    total_space: disk.total_space(),
+                    available_space: disk.available_space(),
+                });
+            }
+
+            for (name, current) in &counters {
+                // devices that appeared since the previous sample have no rate yet
+                let Some(previous) = previous_counters.get(name) else {
+                    continue;
+                };
+                disks_snapshot.block_devices.push(build_block_device_record(
+                    name,
+                    previous,
+                    current,
+                    elapsed_seconds,
+                ));
+            }
+            disks_snapshot
+                .block_devices
+                .sort_by(|a, b| a.name.cmp(&b.name));
+            previous_counters = counters;
+
+            if let Err(e) = sender.send(disks_snapshot) {
+                eprintln!("Error sending disks snapshot: {}", e);
+                return;
+            }
+        }
+    });
+}
+
+/// Builds a BlockDeviceRecord from two samples of a device's counters.
+///
+/// * Parameters
+/// `name` The name of the block device
+/// `previous` The counters at the previous sample
+/// `current` The counters at this sample
+/// `elapsed_seconds` The time between the two samples
+///
+/// * Returns
+/// A BlockDeviceRecord with the rates between the two samples
+fn build_block_device_record(
+    name: &str,
+    previous: &DiskCounters,
+    current: &DiskCounters,
+    elapsed_seconds: f64,
+) -> BlockDeviceRecord {
+    // counters can wrap or reset when a device is re-attached, so differences saturate at zero
+    let rate =
+        |previous: u64, current: u64| current.saturating_sub(previous) as f64 / elapsed_seconds;
+    BlockDeviceRecord {
+        name: name.to_string(),
+        read_bytes_per_second: rate(previous.sectors_read, current.sectors_read)
+            * DISKSTATS_SECTOR_BYTES as f64,
+        written_bytes_per_second: rate(previous.sectors_written, current.sectors_written)
+            * DISKSTATS_SECTOR_BYTES as f64,
+        read_operations_per_second: rate(previous.reads_completed, current.reads_completed),
+        write_operations_per_second: rate(previous.writes_completed, current.writes_completed),
+    }
+}
+
+/// Reads the I/O counters of every block device.
+///
+/// * Returns
+/// The counters keyed by device name, empty if they could not be read
+#[cfg(target_os = "linux")]
+fn read_disk_counters() -> HashMap<String, DiskCounters> {
+    match std::fs::read_to_string("/proc/diskstats") {
+        Ok(diskstats) => parse_diskstats(&diskstats),
+        Err(_) => HashMap::new(),
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_disk_counters() -> HashMap<String, DiskCounters> {
+    HashMap::new()
+}
+
+/// Parses the contents of /proc/diskstats.
+///
+/// Devices that have never done any I/O (e.g. unused loop devices) are skipped.
+///
+/// * Parameters
+/// `diskstats` The contents of /proc/diskstats
+///
+/// * Returns
+/// The counters keyed by device name
+pub fn parse_diskstats(diskstats: &str) -> HashMap<String, DiskCounters> {
+    let mut counters: HashMap<String, DiskCounters> = HashMap::new();
+    for line in diskstats.lines() {
+        // fields: major minor name reads reads_merged sectors_read ms_reading writes
+        // writes_merged sectors_written ...
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() < 10 {
+            continue;
+        }
+        let parse = |index: usize| fields[index].parse::<u64>().ok();
+        let (
+            Some(reads_completed),
+            Some(sectors_read),
+            Some(writes_completed),
+            Some(sectors_written),
+        ) = (parse(3), parse(5), parse(7), parse(9))
+        else {
+            continue;
+        };
+        if reads_completed == 0 && writes_completed == 0 {
+            continue;
+        }
+        counters.insert(
+            fields[2].to_string(),
+            DiskCounters {
+                reads_completed,
+                sectors_read,
+                writes_completed,
+                sectors_written,
+            },
+        );
+    }
+    counters
+}
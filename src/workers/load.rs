@@ -0,0 +1,218 @@
+//! Load worker.
+//!
+//! Background worker for collecting load averages and pressure stall information.
+
+use crate::snapshots::{
+    load_snapshot_struct::LoadSnapshot, pressure_averages_struct::PressureAverages,
+    pressure_record_struct::PressureRecord,
+};
+use crate::workers::sampling::sleep_sampling_interval;
+use std::sync::mpsc::Sender;
+use std::thread::{self, JoinHandle};
+use sysinfo::{LoadAvg, System};
+
+/// Starts a background worker to collect load snapshots.
+///
+/// Spawns a thread that periodically sends LoadSnapshot data via the channel.
+///
+/// * Parameters
+/// `sender` The channel sender for LoadSnapshot data
+pub fn send_load_snapshot(sender: Sender<LoadSnapshot>) {
+    let _handle: JoinHandle<()> = thread::spawn(move || {
+        loop {
+            let load_average: LoadAvg = System::load_average();
+
+            let mut load_snapshot: LoadSnapshot = LoadSnapshot::new();
+            load_snapshot.load_average_one = load_average.one;
+            load_snapshot.load_average_five = load_average.five;
+            load_snapshot.load_average_fifteen = load_average.fifteen;
+            load_snapshot.cpu_pressure = read_pressure("cpu");
+            load_snapshot.memory_pressure = read_pressure("memory");
+            load_snapshot.io_pressure = read_pressure("io");
+
+            if let Err(e) = sender.send(load_snapshot) {
+                eprintln!("Error sending load snapshot: {}", e);
+                return;
+            }
+
+            sleep_sampling_interval();
+        }
+    });
+}
+
+/// Reads the pressure stall information of a resource.
+///
+/// * Parameters
+/// `resource` The resource's file name under /proc/pressure, e.g. "cpu"
+///
+/// * Returns
+/// Some(PressureRecord) on Linux if the file could be read and parsed, None otherwise
+#[cfg(target_os = "linux")]
+fn read_pressure(resource: &str) -> Option<PressureRecord> {
+    let pressure: String = std::fs::read_to_string(format!("/proc/pressure/{}", resource)).ok()?;
+    parse_pressure(&pressure)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_pressure(_resource: &str) -> Option<PressureRecord> {
+    None
+}
+
+/// Parses the contents of a /proc/pressure file.
+///
+/// The file has a "some" line and, on most kernels, a "full" line, each of the form
+/// "some avg10=1.23 avg60=0.50 avg300=0.10 total=12345".
+///
+/// * Parameters
+/// `pressure` The contents of the file
+///
+/// * Returns
+/// Some(PressureRecord) if the "some" line could be parsed, None otherwise
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+pub fn parse_pressure(pressure: &str) -> Option<PressureRecord> {
+    let mut some: Option<PressureAverages> = None;
+    let mut full: Option<PressureAverages> = None;
+
+    for line in pressure.lines() {
+        let mut fields = line.split_whitespace();
+        match fields.next() {
+            Some("some") => some = parse_pressure_averages(fields),
+            Some("full") => full = parse_pressure_averages(fields),
+            _ => {}
+        }
+    }
+
+    Some(PressureRecord { some: some?, full })
+}
+
+/// Parses the key=value fields of one pressure line.
+///
+/// * Parameters
+/// `fields` The fields following "some" or "full"
+///
+/// * Returns
+/// Some(PressureAverages) if all three averages were found, None otherwise
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_pressure_averages<'a>(fields: impl Iterator<Item = &'a str>) -> Option<PressureAverages> {
+    let mut avg10: Option<f32> = None;
+    let mut avg60: Option<f32> = None;
+    let mut avg300: Option<f32> = None;
+
+    for field in fields {
+        let Some((key, value)) = field.split_once('=') else {
+            continue;
+        };
+        let value: Option<f32> = value.parse::<f32>().ok();
+        match key {
+            "avg10" => avg10 = value,
+            "avg60" => avg60 = value,
+            "avg300" => avg300 = value,
+            _ => {}
+        }
+    }
+
+    Some(PressureAverages {
+        avg10: avg10?,
+        avg60: avg60?,
+        avg300: avg300?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// /proc/pressure/cpu before Linux 5.13, which has no "full" line.
+    const CPU_PRESSURE_WITHOUT_FULL: &str =
+        "some avg10=3.41 avg60=2.47 avg300=2.43 total=186720681\n";
+    /// /proc/pressure/cpu since Linux 5.13, with a "full" line that stays at zero
+    /// outside of cgroups.
+    const CPU_PRESSURE: &str = "\
+some avg10=0.52 avg60=1.07 avg300=0.89 total=23460312
+full avg10=0.00 avg60=0.00 avg300=0.00 total=0
+";
+    const MEMORY_PRESSURE: &str = "\
+some avg10=12.50 avg60=4.21 avg300=1.02 total=9312846
+full avg10=7.80 avg60=2.33 avg300=0.51 total=5120340
+";
+    const IO_PRESSURE: &str = "\
+some avg10=0.00 avg60=0.15 avg300=0.36 total=104771942
+full avg10=0.00 avg60=0.11 avg300=0.29 total=96208474
+";
+
+    fn averages(averages: &PressureAverages) -> [f32; 3] {
+        [averages.avg10, averages.avg60, averages.avg300]
+    }
+
+    fn parsed(pressure: &str) -> Option<([f32; 3], Option<[f32; 3]>)> {
+        let pressure_record: PressureRecord = parse_pressure(pressure)?;
+        Some((
+            averages(&pressure_record.some),
+            pressure_record.full.as_ref().map(averages),
+        ))
+    }
+
+    #[test]
+    fn parses_cpu_pressure_without_full_line() {
+        assert_eq!(
+            parsed(CPU_PRESSURE_WITHOUT_FULL),
+            Some(([3.41, 2.47, 2.43], None))
+        );
+    }
+
+    #[test]
+    fn parses_cpu_pressure_with_full_line() {
+        assert_eq!(
+            parsed(CPU_PRESSURE),
+            Some(([0.52, 1.07, 0.89], Some([0.0, 0.0, 0.0])))
+        );
+    }
+
+    #[test]
+    fn parses_memory_and_io_pressure() {
+        assert_eq!(
+            parsed(MEMORY_PRESSURE),
+            Some(([12.5, 4.21, 1.02], Some([7.8, 2.33, 0.51])))
+        );
+        assert_eq!(
+            parsed(IO_PRESSURE),
+            Some(([0.0, 0.15, 0.36], Some([0.0, 0.11, 0.29])))
+        );
+    }
+
+    #[test]
+    fn fields_in_any_order_are_parsed() {
+        assert_eq!(
+            parsed("some total=1 avg300=3.00 avg10=1.00 avg60=2.00\n"),
+            Some(([1.0, 2.0, 3.0], None))
+        );
+    }
+
+    #[test]
+    fn malformed_some_line_is_rejected() {
+        assert_eq!(parsed(""), None);
+        // only a "full" line
+        assert_eq!(
+            parsed("full avg10=0.00 avg60=0.00 avg300=0.00 total=0\n"),
+            None
+        );
+        // a missing average
+        assert_eq!(parsed("some avg10=0.52 avg300=0.89 total=23460312\n"), None);
+        // an average that isn't a number
+        assert_eq!(
+            parsed("some avg10=abc avg60=1.07 avg300=0.89 total=0\n"),
+            None
+        );
+        // fields without a value
+        assert_eq!(parsed("some avg10 avg60 avg300\n"), None);
+    }
+
+    #[test]
+    fn malformed_full_line_is_left_out() {
+        let pressure: &str = "\
+some avg10=12.50 avg60=4.21 avg300=1.02 total=9312846
+full avg10=7.80 avg60= avg300=0.51 total=5120340
+";
+        assert_eq!(parsed(pressure), Some(([12.5, 4.21, 1.02], None)));
+    }
+}
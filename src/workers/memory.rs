@@ -0,0 +1,77 @@
+//! Memory worker.
+//!
+//! Background worker for collecting RAM and swap usage data.
+
+use crate::snapshots::memory_snapshot_struct::MemorySnapshot;
+use crate::workers::sampling::sleep_sampling_interval;
+use std::sync::mpsc::Sender;
+use std::thread::{self, JoinHandle};
+use sysinfo::System;
+
+/// Starts a background worker to collect memory snapshots.
+///
+/// Spawns a thread that periodically sends MemorySnapshot data via the channel.
+///
+/// * Parameters
+/// `sender` The channel sender for MemorySnapshot data
+pub fn send_memory_snapshot(sender: Sender<MemorySnapshot>) {
+    let mut sys: System = System::new();
+    let _handle: JoinHandle<()> = thread::spawn(move || {
+        loop {
+            sys.refresh_memory();
+
+            let mut memory_snapshot: MemorySnapshot = MemorySnapshot::new();
+            memory_snapshot.total_memory = sys.total_memory();
+            memory_snapshot.used_memory = sys.used_memory();
+            memory_snapshot.available_memory = sys.available_memory();
+            memory_snapshot.cached_memory = read_cached_memory();
+            memory_snapshot.total_swap = sys.total_swap();
+            memory_snapshot.used_swap = sys.used_swap();
+
+            if let Err(e) = sender.send(memory_snapshot) {
+                eprintln!("Error sending memory snapshot: {}", e);
+                return;
+            }
+
+            sleep_sampling_interval();
+        }
+    });
+}
+
+/// Reads the amount of memory used by the page cache.
+///
+/// sysinfo doesn't report cached memory, so it is read from /proc/meminfo.
+///
+/// * Returns
+/// Some(bytes) on Linux if /proc/meminfo could be read, None otherwise
+#[cfg(target_os = "linux")]
+fn read_cached_memory() -> Option<u64> {
+    let meminfo: String = std::fs::read_to_string("/proc/meminfo").ok()?;
+    parse_meminfo_field(&meminfo, "Cached")
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_cached_memory() -> Option<u64> {
+    None
+}
+
+/// Finds a field in the contents of /proc/meminfo.
+///
+/// * Parameters
+/// `meminfo` The contents of /proc/meminfo
+/// `field` The name of the field, e.g. "Cached"
+///
+/// * Returns
+/// Some(bytes) if the field was found, None otherwise
+#[cfg(target_os = "linux")]
+fn parse_meminfo_field(meminfo: &str, field: &str) -> Option<u64> {
+    meminfo.lines().find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        if name != field {
+            return None;
+        }
+        // values are given in kibibytes, e.g. "Cached:  1234 kB"
+        let kibibytes: u64 = value.split_whitespace().next()?.parse().ok()?;
+        Some(kibibytes * 1024)
+    })
+}
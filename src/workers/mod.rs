@@ -2,18 +2,29 @@
 //!
 //! Contains functions that run in separate threads to collect system data.
 
+pub mod cpu_frequency;
+pub mod cpu_times;
+pub mod disks;
+pub mod load;
+pub mod memory;
+pub mod network;
 pub mod processes;
+pub mod sampling;
 pub mod send_system_snapshot;
+pub mod sensors;
+pub mod threads;
 
+use crate::data::time::unix_time_milliseconds;
 use crate::snapshots::cpu_snapshot_struct::CpuSnapshot;
+use crate::workers::{cpu_frequency::read_frequency_limits, sampling::sleep_sampling_interval};
 use std::sync::mpsc::Sender;
 use std::thread::{self, JoinHandle};
-use std::time::Duration;
 use sysinfo::System;
 
 /// Starts a background worker to collect CPU usage snapshots.
 /// 
 /// Spawns a thread that periodically sends CpuSnapshot data via the channel.
+/// Each snapshot holds the usage and current frequency of every core.
 /// 
 /// * Parameters
 /// `sender` The channel sender for CpuSnapshot data
@@ -23,23 +34,23 @@ pub fn send_cpu_snapshot(sender: Sender<CpuSnapshot>) {
     let _handle: JoinHandle<()> = thread::spawn(move || {
         loop {
             sys.refresh_cpu_usage();
-            let overall_cpu_usage: f32 = sys.global_cpu_info().cpu_usage();
-            let mut per_core_cpu_usage: Vec<f32> = Vec::new();
+            sys.refresh_cpu_frequency();
+            let mut cpu_snapshot: CpuSnapshot = CpuSnapshot::new();
+            cpu_snapshot.timestamp_milliseconds = unix_time_milliseconds();
+            cpu_snapshot.overall_cpu_usage = sys.global_cpu_info().cpu_usage();
             for cpu in sys.cpus() {
                 let cpu_usage: f32 = cpu.cpu_usage();
-                per_core_cpu_usage.push(cpu_usage);
+                cpu_snapshot.per_core_cpu_usage.push(cpu_usage);
+                cpu_snapshot.per_core_frequency.push(cpu.frequency());
             }
-            let cpu_snapshot: CpuSnapshot = CpuSnapshot {
-                overall_cpu_usage: overall_cpu_usage,
-                per_core_cpu_usage: per_core_cpu_usage,
-            };
+            cpu_snapshot.per_core_frequency_limits = read_frequency_limits(sys.cpus().len());
             if let Err(e) = sender.send(cpu_snapshot) {
                 eprintln!("Error: {}", e);
                 return;
             }
             // Sleeping to let time for the system to run for long
             // enough to have useful information.
-            std::thread::sleep(Duration::from_millis(500));
+            sleep_sampling_interval();
         }
     });
 }
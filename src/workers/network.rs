@@ -0,0 +1,57 @@
+//! Network worker.
+//!
+//! Background worker for collecting per-interface network traffic data.
+
+use crate::snapshots::{
+    network_interface_record_struct::NetworkInterfaceRecord,
+    network_snapshot_struct::NetworkSnapshot,
+};
+use crate::workers::sampling::sleep_sampling_interval;
+use std::sync::mpsc::Sender;
+use std::thread::{self, JoinHandle};
+use std::time::Instant;
+use sysinfo::Networks;
+
+/// Starts a background worker to collect network snapshots.
+///
+/// Spawns a thread that periodically sends NetworkSnapshot data via the channel.
+/// Rates are computed from the counters' change since the previous sample.
+///
+/// * Parameters
+/// `sender` The channel sender for NetworkSnapshot data
+pub fn send_network_snapshot(sender: Sender<NetworkSnapshot>) {
+    let mut networks: Networks = Networks::new_with_refreshed_list();
+    let _handle: JoinHandle<()> = thread::spawn(move || {
+        let mut last_sample: Instant = Instant::now();
+        loop {
+            sleep_sampling_interval();
+
+            // refreshing the list also picks up interfaces that were added or removed
+            networks.refresh_list();
+            let elapsed_seconds: f64 = last_sample.elapsed().as_secs_f64();
+            last_sample = Instant::now();
+
+            let mut network_snapshot: NetworkSnapshot = NetworkSnapshot::new();
+            for (name, data) in networks.list() {
+                network_snapshot.interfaces.push(NetworkInterfaceRecord {
+                    name: name.clone(),
+                    received_bytes_per_second: data.received() as f64 / elapsed_seconds,
+                    transmitted_bytes_per_second: data.transmitted() as f64 / elapsed_seconds,
+                    received_packets_per_second: data.packets_received() as f64 / elapsed_seconds,
+                    transmitted_packets_per_second: data.packets_transmitted() as f64
+                        / elapsed_seconds,
+                    total_receive_errors: data.total_errors_on_received(),
+                    total_transmit_errors: data.total_errors_on_transmitted(),
+                });
+            }
+            network_snapshot
+                .interfaces
+                .sort_by(|a, b| a.name.cmp(&b.name));
+
+            if let Err(e) = sender.send(network_snapshot) {
+                eprintln!("Error sending network snapshot: {}", e);
+                return;
+            }
+        }
+    });
+}
@@ -1,34 +1,497 @@
 //! Processes worker.
 //!
-//! Background worker for collecting process count data.
+//! Background worker for collecting per-process data and acting on processes.
 
-use crate::snapshots::processes_snapshot_struct::ProcessesSnapshot;
-use std::sync::mpsc::Sender;
+use crate::{
+    commands::{
+        process_command_enum::ProcessCommand, process_command_outcome_struct::ProcessCommandOutcome,
+    },
+    data::process_key_struct::ProcessKey,
+    snapshots::{
+        process_environment_struct::ProcessEnvironment, process_record_struct::ProcessRecord,
+        processes_snapshot_struct::ProcessesSnapshot,
+    },
+    workers::sampling::next_sampling_wait,
+};
+use std::io;
+use std::sync::mpsc::{Receiver, RecvTimeoutError, Sender};
 use std::thread::{self, JoinHandle};
-use std::time::Duration;
-use sysinfo::System;
+use std::time::{Duration, Instant};
+use sysinfo::{
+    DiskUsage, Pid, Process, ProcessRefreshKind, Signal, System, ThreadKind, UpdateKind, Users,
+};
 
-/// Starts a background worker to collect process count snapshots.
-/// 
+/// Starts a background worker to collect process snapshots.
+///
 /// Spawns a thread that periodically sends ProcessesSnapshot data via the channel.
-/// 
+/// Between snapshots the thread carries out ProcessCommands sent from the GUI and
+/// reports each outcome back, or the environment read for a ReadEnvironment.
+/// The open files, nice value and affinity are only read for the processes the GUI
+/// shows them for, the selected one and the one in the detail window.
+///
 /// * Parameters
 /// `process_sender` The channel sender for ProcessesSnapshot data
-pub fn send_processes_snapshot(process_sender: Sender<ProcessesSnapshot>) {
+/// `command_receiver` The channel receiver for ProcessCommands from the GUI
+/// `outcome_sender` The channel sender for ProcessCommandOutcome data
+/// `environment_sender` The channel sender for ProcessEnvironment data
+/// `detail_watch_receiver` The channel receiver for the pids to read details of
+pub fn send_processes_snapshot(
+    process_sender: Sender<ProcessesSnapshot>,
+    command_receiver: Receiver<ProcessCommand>,
+    outcome_sender: Sender<ProcessCommandOutcome>,
+    environment_sender: Sender<ProcessEnvironment>,
+    detail_watch_receiver: Receiver<Vec<u32>>,
+) {
     let mut sys: System = System::new_all();
+    let users: Users = Users::new_with_refreshed_list();
     let _handle: JoinHandle<()> = thread::spawn(move || {
+        let mut last_refresh: Instant = Instant::now();
+        let mut detail_pids: Vec<u32> = Vec::new();
         loop {
+            // only the latest pids matter
+            while let Ok(pids) = detail_watch_receiver.try_recv() {
+                detail_pids = pids;
+            }
             sys.refresh_processes();
-            let process_count: usize = System::processes(&sys).len();
+            // disk usage is reported since the previous refresh
+            let refresh_interval: Duration = last_refresh.elapsed();
+            last_refresh = Instant::now();
+            let mut processes_snapshot: ProcessesSnapshot = ProcessesSnapshot::new();
+
+            for process in sys.processes().values() {
+                // on Linux, sysinfo also lists every thread of a process as its own entry
+                if let Some(ThreadKind::Userland) = process.thread_kind() {
+                    continue;
+                }
+                let read_details: bool = detail_pids.contains(&process.pid().as_u32());
+                processes_snapshot.processes.push(build_process_record(
+                    process,
+                    &users,
+                    refresh_interval,
+                    read_details,
+                ));
+            }
 
-            if let Err(e) = process_sender.send(ProcessesSnapshot {
-                processes: process_count,
-            }) {
-                eprintln!("Error sending process count: {}", e);
+            if let Err(e) = process_sender.send(processes_snapshot) {
+                eprintln!("Error sending processes snapshot: {}", e);
                 return;
             }
 
-            thread::sleep(Duration::from_millis(500));
+            // waits for commands instead of sleeping so they are carried out straight away
+            let waiting_since: Instant = Instant::now();
+            while let Some(timeout) = next_sampling_wait(waiting_since) {
+                match command_receiver.recv_timeout(timeout) {
+                    Ok(ProcessCommand::ReadEnvironment { process_key }) => {
+                        let process_environment: ProcessEnvironment = ProcessEnvironment {
+                            process_key,
+                            environment: read_process_environment(process_key),
+                        };
+                        if let Err(e) = environment_sender.send(process_environment) {
+                            eprintln!("Error sending process environment: {}", e);
+                            return;
+                        }
+                    }
+                    Ok(command) => {
+                        let outcome: ProcessCommandOutcome = run_process_command(&mut sys, command);
+                        if let Err(e) = outcome_sender.send(outcome) {
+                            eprintln!("Error sending process command outcome: {}", e);
+                            return;
+                        }
+                    }
+                    Err(RecvTimeoutError::Timeout) => {}
+                    Err(RecvTimeoutError::Disconnected) => return,
+                }
+            }
         }
     });
 }
+
+/// Carries out a process command.
+///
+/// * Parameters
+/// `sys` The system used to look up and act on processes
+/// `command` The command to carry out
+///
+/// * Returns
+/// The outcome of the command
+fn run_process_command(sys: &mut System, command: ProcessCommand) -> ProcessCommandOutcome {
+    let result: Result<(), String> = match &command {
+        ProcessCommand::SendSignal { pid, signal } => send_signal(sys, *pid, *signal),
+        ProcessCommand::SetNice { pid, nice } => set_nice(sys, *pid, *nice),
+        ProcessCommand::SetAffinity { pid, cores } => set_affinity(sys, *pid, cores),
+        // the worker sends the environment itself, so this only tells whether it's readable
+        ProcessCommand::ReadEnvironment { process_key } => {
+            read_process_environment(*process_key).map(|_| ())
+        }
+    };
+    ProcessCommandOutcome {
+        command,
+        error: result.err(),
+    }
+}
+
+/// Sends a signal to a process.
+///
+/// * Parameters
+/// `sys` The system used to look up the process
+/// `pid` The pid of the process
+/// `signal` The signal to send
+///
+/// * Returns
+/// Ok if the signal was delivered, otherwise a message explaining why not
+fn send_signal(sys: &mut System, pid: u32, signal: Signal) -> Result<(), String> {
+    let process: &Process = find_live_process(sys, pid)?;
+
+    match process.kill_with(signal) {
+        Some(true) => Ok(()),
+        Some(false) => Err(describe_os_error(io::Error::last_os_error())),
+        None => Err(String::from("the signal is not supported on this platform")),
+    }
+}
+
+/// Changes the nice value of a process.
+///
+/// On Linux the nice value belongs to each thread, so every thread is changed.
+///
+/// * Parameters
+/// `sys` The system used to look up the process
+/// `pid` The pid of the process
+/// `nice` The new nice value, from -20 to 19
+///
+/// * Returns
+/// Ok if the nice value was changed, otherwise a message explaining why not
+#[cfg(unix)]
+fn set_nice(sys: &mut System, pid: u32, nice: i32) -> Result<(), String> {
+    find_live_process(sys, pid)?;
+
+    apply_to_threads(pid, |tid| {
+        // SAFETY: setpriority only reads its arguments
+        match unsafe { libc::setpriority(libc::PRIO_PROCESS, tid as libc::id_t, nice) } {
+            0 => Ok(()),
+            _ => Err(io::Error::last_os_error()),
+        }
+    })
+}
+
+#[cfg(not(unix))]
+fn set_nice(_sys: &mut System, _pid: u32, _nice: i32) -> Result<(), String> {
+    Err(String::from(
+        "nice values are not supported on this platform",
+    ))
+}
+
+/// Restricts a process to a set of cores.
+///
+/// The affinity belongs to each thread, so every thread is restricted.
+///
+/// * Parameters
+/// `sys` The system used to look up the process
+/// `pid` The pid of the process
+/// `cores` The indices of the cores the process may run on
+///
+/// * Returns
+/// Ok if the affinity was changed, otherwise a message explaining why not
+#[cfg(target_os = "linux")]
+fn set_affinity(sys: &mut System, pid: u32, cores: &[usize]) -> Result<(), String> {
+    find_live_process(sys, pid)?;
+    if let Some(core) = cores
+        .iter()
+        .find(|core| **core >= libc::CPU_SETSIZE as usize)
+    {
+        return Err(format!(
+            "core {} is beyond the cores the kernel can pin to",
+            core
+        ));
+    }
+
+    // SAFETY: an all-zero cpu_set_t is the empty set and CPU_SET only writes inside the set
+    let cpu_set: libc::cpu_set_t = unsafe {
+        let mut cpu_set: libc::cpu_set_t = std::mem::zeroed();
+        for core in cores {
+            libc::CPU_SET(*core, &mut cpu_set);
+        }
+        cpu_set
+    };
+    apply_to_threads(pid, |tid| {
+        // SAFETY: sched_setaffinity only reads the set it is given
+        let result: libc::c_int = unsafe {
+            libc::sched_setaffinity(
+                tid as libc::pid_t,
+                std::mem::size_of::<libc::cpu_set_t>(),
+                &cpu_set,
+            )
+        };
+        match result {
+            0 => Ok(()),
+            _ => Err(io::Error::last_os_error()),
+        }
+    })
+}
+
+#[cfg(not(target_os = "linux"))]
+fn set_affinity(_sys: &mut System, _pid: u32, _cores: &[usize]) -> Result<(), String> {
+    Err(String::from(
+        "CPU affinity is not supported on this platform",
+    ))
+}
+
+/// Carries out a change on every thread of a process, listed from /proc/<pid>/task.
+///
+/// Threads that exit before they are changed are skipped.
+///
+/// * Parameters
+/// `pid` The pid of the process
+/// `change` The change to make to one thread, given its tid
+///
+/// * Returns
+/// Ok if every thread was changed, otherwise a message saying how many were and
+/// why each other thread wasn't
+#[cfg(target_os = "linux")]
+fn apply_to_threads(pid: u32, change: impl Fn(u32) -> Result<(), io::Error>) -> Result<(), String> {
+    let entries = std::fs::read_dir(format!("/proc/{}/task", pid)).map_err(describe_os_error)?;
+    let tids: Vec<u32> = entries
+        .flatten()
+        .filter_map(|entry| entry.file_name().to_str()?.parse().ok())
+        .collect();
+
+    let mut changed_count: usize = 0;
+    let mut failures: Vec<(u32, String)> = Vec::new();
+    for tid in tids {
+        match change(tid) {
+            Ok(()) => changed_count += 1,
+            Err(e) if e.raw_os_error() == Some(libc::ESRCH) => {}
+            Err(e) => failures.push((tid, describe_os_error(e))),
+        }
+    }
+
+    // one error shared by every thread reads better on its own
+    if changed_count == 0 && failures.iter().all(|(_, error)| *error == failures[0].1) {
+        return match failures.into_iter().next() {
+            Some((_, error)) => Err(error),
+            None => Err(String::from("the process has already exited")),
+        };
+    }
+    if failures.is_empty() {
+        return Ok(());
+    }
+    let thread_errors: Vec<String> = failures
+        .iter()
+        .map(|(tid, error)| format!("thread {}: {}", tid, error))
+        .collect();
+    Err(format!(
+        "changed {} of {} threads, {}",
+        changed_count,
+        changed_count + failures.len(),
+        thread_errors.join(", ")
+    ))
+}
+
+/// Carries out a change on a process, as it is process-wide outside Linux.
+///
+/// * Parameters
+/// `pid` The pid of the process
+/// `change` The change to make to the process, given its pid
+///
+/// * Returns
+/// Ok if the process was changed, otherwise a message explaining why not
+#[cfg(all(unix, not(target_os = "linux")))]
+fn apply_to_threads(pid: u32, change: impl Fn(u32) -> Result<(), io::Error>) -> Result<(), String> {
+    change(pid).map_err(describe_os_error)
+}
+
+/// Looks up a process again before acting on it, as the process list may be up to
+/// one refresh old.
+///
+/// * Parameters
+/// `sys` The system used to look up the process
+/// `pid` The pid of the process
+///
+/// * Returns
+/// The process, or a message saying it has exited
+fn find_live_process(sys: &mut System, pid: u32) -> Result<&Process, String> {
+    let pid: Pid = Pid::from_u32(pid);
+    if !sys.refresh_process(pid) {
+        return Err(String::from("the process has already exited"));
+    }
+    sys.process(pid)
+        .ok_or_else(|| String::from("the process has already exited"))
+}
+
+/// Turns an operating system error into a message for the GUI.
+///
+/// * Parameters
+/// `error` The error reported by the operating system
+///
+/// * Returns
+/// A message explaining the error
+fn describe_os_error(error: io::Error) -> String {
+    match error.raw_os_error() {
+        Some(libc::EPERM) | Some(libc::EACCES) => String::from("permission denied"),
+        Some(libc::ESRCH) => String::from("the process has already exited"),
+        _ => error.to_string(),
+    }
+}
+
+/// Builds a ProcessRecord from a sysinfo process.
+///
+/// * Parameters
+/// `process` The process to read from
+/// `users` The user list used to resolve the owner's name
+/// `refresh_interval` The time since the previous refresh, to turn disk usage into rates
+/// `read_details` Whether to read the open files, nice value and affinity, which
+/// take a few more files per process
+///
+/// * Returns
+/// A ProcessRecord describing the process
+fn build_process_record(
+    process: &Process,
+    users: &Users,
+    refresh_interval: Duration,
+    read_details: bool,
+) -> ProcessRecord {
+    let user: String = match process.user_id() {
+        Some(user_id) => match users.get_user_by_id(user_id) {
+            Some(user) => user.name().to_string(),
+            None => user_id.to_string(),
+        },
+        None => String::from("N/A"),
+    };
+    let disk_usage: DiskUsage = process.disk_usage();
+    let seconds: f64 = refresh_interval.as_secs_f64().max(f64::EPSILON);
+
+    ProcessRecord {
+        pid: process.pid().as_u32(),
+        parent_pid: process.parent().map(|parent| parent.as_u32()),
+        name: process.name().to_string(),
+        user,
+        cpu_usage: process.cpu_usage(),
+        memory: process.memory(),
+        status: process.status().to_string(),
+        start_time: process.start_time(),
+        command: process.cmd().join(" "),
+        virtual_memory: process.virtual_memory(),
+        thread_count: process.tasks().map(|tasks| tasks.len()),
+        open_file_count: read_details
+            .then(|| count_open_files(process.pid()))
+            .flatten(),
+        disk_read_bytes_per_second: disk_usage.read_bytes as f64 / seconds,
+        disk_written_bytes_per_second: disk_usage.written_bytes as f64 / seconds,
+        nice: read_details.then(|| read_nice(process.pid())).flatten(),
+        affinity: read_details.then(|| read_affinity(process.pid())).flatten(),
+    }
+}
+
+/// Counts the files a process has open.
+///
+/// * Parameters
+/// `pid` The pid of the process
+///
+/// * Returns
+/// The number of open file descriptors, None if they can't be listed, such as for
+/// another user's process
+#[cfg(target_os = "linux")]
+fn count_open_files(pid: Pid) -> Option<usize> {
+    std::fs::read_dir(format!("/proc/{}/fd", pid))
+        .ok()
+        .map(|entries| entries.count())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn count_open_files(_pid: Pid) -> Option<usize> {
+    None
+}
+
+/// Reads the nice value of a process.
+///
+/// sysinfo doesn't report it, so it is read from /proc/<pid>/stat.
+///
+/// * Parameters
+/// `pid` The pid of the process
+///
+/// * Returns
+/// Some(nice) on Linux if the stat file could be read, None otherwise
+#[cfg(target_os = "linux")]
+fn read_nice(pid: Pid) -> Option<i32> {
+    let stat: String = std::fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+    // the name in brackets may hold spaces, so the fields are counted after it.
+    // The state is the 3rd field and the nice value the 19th
+    let (_, fields) = stat.rsplit_once(')')?;
+    fields.split_whitespace().nth(16)?.parse().ok()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_nice(_pid: Pid) -> Option<i32> {
+    None
+}
+
+/// Reads the cores a process may run on.
+///
+/// * Parameters
+/// `pid` The pid of the process
+///
+/// * Returns
+/// Some(core indices) on Linux if the status file could be read, None otherwise
+#[cfg(target_os = "linux")]
+fn read_affinity(pid: Pid) -> Option<Vec<usize>> {
+    let status: String = std::fs::read_to_string(format!("/proc/{}/status", pid)).ok()?;
+    status.lines().find_map(|line| {
+        let core_list: &str = line.strip_prefix("Cpus_allowed_list:")?;
+        parse_core_list(core_list.trim())
+    })
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_affinity(_pid: Pid) -> Option<Vec<usize>> {
+    None
+}
+
+/// Parses a list of core indices as written by the kernel.
+///
+/// * Parameters
+/// `core_list` The list, e.g. "0-3,6"
+///
+/// * Returns
+/// Some(core indices) in ascending order, None if the list is malformed
+#[cfg(target_os = "linux")]
+pub fn parse_core_list(core_list: &str) -> Option<Vec<usize>> {
+    let mut cores: Vec<usize> = Vec::new();
+    for range in core_list.split(',') {
+        match range.split_once('-') {
+            Some((first, last)) => cores.extend(first.parse::<usize>().ok()?..=last.parse().ok()?),
+            None => cores.push(range.parse().ok()?),
+        }
+    }
+    Some(cores)
+}
+
+/// Reads the environment variables of a process.
+///
+/// Only asked for when a process detail window is opened or refreshed, as reading
+/// the environment of every process at each snapshot would be wasteful.
+///
+/// * Parameters
+/// `process_key` The pid and start time of the process
+///
+/// * Returns
+/// The variables as "NAME=value" strings, or a message explaining why they couldn't
+/// be read
+fn read_process_environment(process_key: ProcessKey) -> Result<Vec<String>, String> {
+    let mut sys: System = System::new();
+    let pid: Pid = Pid::from_u32(process_key.pid);
+    let refresh_kind: ProcessRefreshKind =
+        ProcessRefreshKind::new().with_environ(UpdateKind::Always);
+    if !sys.refresh_process_specifics(pid, refresh_kind) {
+        return Err(String::from("the process has already exited"));
+    }
+
+    match sys.process(pid) {
+        // a different start time means the pid now belongs to another process
+        Some(process) if process.start_time() == process_key.start_time => {
+            match process.environ().is_empty() {
+                true => Err(String::from("empty, or not readable without permission")),
+                false => Ok(process.environ().to_vec()),
+            }
+        }
+        _ => Err(String::from("the process has already exited")),
+    }
+}
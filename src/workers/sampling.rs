@@ -0,0 +1,37 @@
+//! Sampling interval.
+//!
+//! Lets the workers wait for the sampling interval in the settings. The interval
+//! is read again every SAMPLING_CHECK_MILLISECONDS while waiting, so a change made
+//! in the settings window applies without waiting out the old interval.
+
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::config::settings::settings_monitoring;
+
+const SAMPLING_CHECK_MILLISECONDS: u64 = 100;
+
+/// Finds how long a worker should wait before checking whether its next sample is due.
+///
+/// * Parameters
+/// `waiting_since` When the worker started waiting
+///
+/// * Returns
+/// The time to wait, or None if the next sample is due
+pub fn next_sampling_wait(waiting_since: Instant) -> Option<Duration> {
+    let sampling_interval: Duration =
+        Duration::from_millis(settings_monitoring().sampling_milliseconds);
+    let remaining: Duration = sampling_interval.saturating_sub(waiting_since.elapsed());
+    match remaining.is_zero() {
+        true => None,
+        false => Some(remaining.min(Duration::from_millis(SAMPLING_CHECK_MILLISECONDS))),
+    }
+}
+
+/// Sleeps until the next sample is due.
+pub fn sleep_sampling_interval() {
+    let waiting_since: Instant = Instant::now();
+    while let Some(wait) = next_sampling_wait(waiting_since) {
+        thread::sleep(wait);
+    }
+}
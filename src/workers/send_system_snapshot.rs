@@ -35,7 +35,6 @@ pub fn send_system_snapshot(sender: Sender<SystemSnapshot>) {
 
         if let Err(e) = sender.send(system_snapshot) {
             eprintln!("Error: {}", e);
-            return;
         }
     });
 }
@@ -0,0 +1,553 @@
+//! Sensors worker.
+//!
+//! Background worker for collecting temperature, fan, voltage and power readings.
+
+use crate::{
+    snapshots::sensors_snapshot_struct::SensorsSnapshot, workers::sampling::sleep_sampling_interval,
+};
+use std::sync::mpsc::Sender;
+use std::thread::{self, JoinHandle};
+
+#[cfg(target_os = "linux")]
+use crate::{
+    snapshots::{sensor_kind_enum::SensorKind, sensor_record_struct::SensorRecord},
+    workers::processes::parse_core_list,
+};
+#[cfg(target_os = "linux")]
+use std::collections::HashMap;
+#[cfg(target_os = "linux")]
+use std::path::Path;
+
+#[cfg(target_os = "linux")]
+const HWMON_ROOT: &str = "/sys/class/hwmon";
+#[cfg(target_os = "linux")]
+const CPU_ROOT: &str = "/sys/devices/system/cpu";
+
+/// Starts a background worker to collect sensor snapshots.
+///
+/// Spawns a thread that periodically sends SensorsSnapshot data via the channel.
+/// On Linux the readings come straight from hwmon, which also exposes fans, voltages
+/// and power, elsewhere only the temperatures reported by sysinfo are available.
+///
+/// * Parameters
+/// `sender` The channel sender for SensorsSnapshot data
+#[cfg(target_os = "linux")]
+pub fn send_sensors_snapshot(sender: Sender<SensorsSnapshot>) {
+    let _handle: JoinHandle<()> = thread::spawn(move || {
+        // cpus don't move between cores while running, so the topology is read once
+        let cpu_topology: Vec<Option<(u32, u32)>> = read_cpu_topology(Path::new(CPU_ROOT));
+        loop {
+            let mut sensors_snapshot: SensorsSnapshot = SensorsSnapshot::new();
+            sensors_snapshot.sensors = read_hwmon_sensors(Path::new(HWMON_ROOT));
+            sensors_snapshot.core_temperatures =
+                match_core_temperatures(&sensors_snapshot.sensors, &cpu_topology);
+
+            if let Err(e) = sender.send(sensors_snapshot) {
+                eprintln!("Error sending sensors snapshot: {}", e);
+                return;
+            }
+
+            sleep_sampling_interval();
+        }
+    });
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn send_sensors_snapshot(sender: Sender<SensorsSnapshot>) {
+    use crate::snapshots::{sensor_kind_enum::SensorKind, sensor_record_struct::SensorRecord};
+    use sysinfo::Components;
+
+    let mut components: Components = Components::new_with_refreshed_list();
+    let _handle: JoinHandle<()> = thread::spawn(move || {
+        loop {
+            components.refresh();
+
+            let mut sensors_snapshot: SensorsSnapshot = SensorsSnapshot::new();
+            for (index, component) in components.iter().enumerate() {
+                sensors_snapshot.sensors.push(SensorRecord {
+                    id: format!("{}/{}", index, component.label()),
+                    chip: String::new(),
+                    label: component.label().to_string(),
+                    kind: SensorKind::Temperature,
+                    value: component.temperature(),
+                    critical: component.critical(),
+                });
+            }
+
+            if let Err(e) = sender.send(sensors_snapshot) {
+                eprintln!("Error sending sensors snapshot: {}", e);
+                return;
+            }
+
+            sleep_sampling_interval();
+        }
+    });
+}
+
+/// Reads every sensor exposed under a hwmon directory.
+///
+/// Each chip is a subdirectory holding a `name` file and one `<kind><n>_input` file
+/// per sensor, with optional `_label` and `_crit` files next to it. Readings are
+/// converted from hwmon's millidegrees, millivolts and microwatts.
+///
+/// * Parameters
+/// `hwmon_root` The hwmon directory, /sys/class/hwmon outside of tests
+///
+/// * Returns
+/// The readings ordered by chip, then kind, then sensor number
+#[cfg(target_os = "linux")]
+pub fn read_hwmon_sensors(hwmon_root: &Path) -> Vec<SensorRecord> {
+    let Ok(chip_entries) = std::fs::read_dir(hwmon_root) else {
+        return Vec::new();
+    };
+
+    let mut sensors: Vec<(u32, SensorRecord)> = Vec::new();
+    for chip_entry in chip_entries.flatten() {
+        let chip_path = chip_entry.path();
+        let chip_directory: String = chip_entry.file_name().to_string_lossy().into_owned();
+        let chip: String =
+            read_trimmed(&chip_path.join("name")).unwrap_or_else(|| chip_directory.clone());
+
+        let Ok(sensor_entries) = std::fs::read_dir(&chip_path) else {
+            continue;
+        };
+        for sensor_entry in sensor_entries.flatten() {
+            let file_name: String = sensor_entry.file_name().to_string_lossy().into_owned();
+            let Some((kind, sensor, number)) = parse_sensor_input_name(&file_name) else {
+                continue;
+            };
+            let Some(raw_value) = read_number(&sensor_entry.path()) else {
+                continue;
+            };
+
+            let scale: f64 = match kind {
+                SensorKind::Temperature | SensorKind::Voltage => 1_000.0,
+                SensorKind::Fan => 1.0,
+                SensorKind::Power => 1_000_000.0,
+            };
+            let critical: Option<f32> = match kind {
+                SensorKind::Temperature => read_number(&chip_path.join(format!("{}_crit", sensor)))
+                    .map(|raw_critical| (raw_critical / scale) as f32),
+                _ => None,
+            };
+
+            sensors.push((
+                number,
+                SensorRecord {
+                    id: format!("{}/{}", chip_directory, sensor),
+                    chip: chip.clone(),
+                    label: read_trimmed(&chip_path.join(format!("{}_label", sensor)))
+                        .unwrap_or_else(|| sensor.to_string()),
+                    kind,
+                    value: (raw_value / scale) as f32,
+                    critical,
+                },
+            ));
+        }
+    }
+
+    // chips of the same kind (e.g. one coretemp per package) are kept apart by their directory
+    sensors.sort_by(|(a_number, a), (b_number, b)| {
+        let a_key = (&a.chip, a.id.split('/').next(), a.kind, a_number);
+        let b_key = (&b.chip, b.id.split('/').next(), b.kind, b_number);
+        a_key.cmp(&b_key)
+    });
+    sensors.into_iter().map(|(_, sensor)| sensor).collect()
+}
+
+/// Splits the name of a hwmon input file into its parts.
+///
+/// * Parameters
+/// `file_name` The file name, e.g. "temp2_input"
+///
+/// * Returns
+/// The sensor kind, the sensor name ("temp2") and its number (2), or None if the
+/// file isn't a sensor input
+#[cfg(target_os = "linux")]
+fn parse_sensor_input_name(file_name: &str) -> Option<(SensorKind, &str, u32)> {
+    let sensor: &str = file_name.strip_suffix("_input")?;
+    for (prefix, kind) in [
+        ("temp", SensorKind::Temperature),
+        ("fan", SensorKind::Fan),
+        ("in", SensorKind::Voltage),
+        ("power", SensorKind::Power),
+    ] {
+        if let Some(number) = sensor
+            .strip_prefix(prefix)
+            .and_then(|number| number.parse::<u32>().ok())
+        {
+            return Some((kind, sensor, number));
+        }
+    }
+    None
+}
+
+/// Reads the physical package and core of every online logical CPU.
+///
+/// Offline CPUs are left out, as sysinfo only lists the online ones and the core
+/// temperatures are looked up by its CPU indices.
+///
+/// * Parameters
+/// `cpu_root` The cpu directory, /sys/devices/system/cpu outside of tests
+///
+/// * Returns
+/// The (package id, core id) of each online CPU in logical order, None for CPUs
+/// whose topology isn't available
+#[cfg(target_os = "linux")]
+pub fn read_cpu_topology(cpu_root: &Path) -> Vec<Option<(u32, u32)>> {
+    let online_cpus: Option<Vec<usize>> = std::fs::read_to_string(cpu_root.join("online"))
+        .ok()
+        .and_then(|cpu_list| parse_core_list(cpu_list.trim()));
+    // without the online list, offline CPUs are told apart by their missing topology
+    let cpu_numbers: Vec<usize> = match online_cpus {
+        Some(online_cpus) => online_cpus,
+        None => {
+            let Ok(cpu_entries) = std::fs::read_dir(cpu_root) else {
+                return Vec::new();
+            };
+            let mut cpu_numbers: Vec<usize> = cpu_entries
+                .flatten()
+                .filter(|cpu_entry| cpu_entry.path().join("topology").is_dir())
+                .filter_map(|cpu_entry| {
+                    cpu_entry
+                        .file_name()
+                        .to_string_lossy()
+                        .strip_prefix("cpu")
+                        .and_then(|number| number.parse::<usize>().ok())
+                })
+                .collect();
+            cpu_numbers.sort_unstable();
+            cpu_numbers
+        }
+    };
+
+    cpu_numbers
+        .into_iter()
+        .map(|cpu_number| {
+            let topology_path = cpu_root.join(format!("cpu{}", cpu_number)).join("topology");
+            let package_id: f64 = read_number(&topology_path.join("physical_package_id"))?;
+            let core_id: f64 = read_number(&topology_path.join("core_id"))?;
+            Some((package_id as u32, core_id as u32))
+        })
+        .collect()
+}
+
+/// Finds the temperature of each logical CPU's physical core.
+///
+/// Intel's coretemp driver labels its sensors "Core <core id>", with one chip per
+/// package that also has a "Package id <package id>" sensor. Drivers without
+/// per-core sensors (e.g. AMD's k10temp) leave every core without a temperature.
+///
+/// * Parameters
+/// `sensors` The sensor readings
+/// `cpu_topology` The (package id, core id) of each logical CPU
+///
+/// * Returns
+/// The core temperature of each logical CPU, in logical order
+#[cfg(target_os = "linux")]
+pub fn match_core_temperatures(
+    sensors: &[SensorRecord],
+    cpu_topology: &[Option<(u32, u32)>],
+) -> Vec<Option<f32>> {
+    let chip_directory = |sensor: &SensorRecord| -> String {
+        sensor.id.split('/').next().unwrap_or_default().to_string()
+    };
+
+    let mut package_ids: HashMap<String, u32> = HashMap::new();
+    for sensor in sensors {
+        if let Some(package_id) = sensor
+            .label
+            .strip_prefix("Package id ")
+            .and_then(|package_id| package_id.parse::<u32>().ok())
+        {
+            package_ids.insert(chip_directory(sensor), package_id);
+        }
+    }
+
+    let mut core_temperatures: HashMap<(u32, u32), f32> = HashMap::new();
+    for sensor in sensors {
+        if sensor.kind != SensorKind::Temperature {
+            continue;
+        }
+        if let Some(core_id) = sensor
+            .label
+            .strip_prefix("Core ")
+            .and_then(|core_id| core_id.parse::<u32>().ok())
+        {
+            let package_id: u32 = package_ids
+                .get(&chip_directory(sensor))
+                .copied()
+                .unwrap_or(0);
+            core_temperatures.insert((package_id, core_id), sensor.value);
+        }
+    }
+
+    cpu_topology
+        .iter()
+        .map(|topology| topology.and_then(|core| core_temperatures.get(&core).copied()))
+        .collect()
+}
+
+/// Reads a sysfs file and trims the trailing newline.
+///
+/// * Parameters
+/// `path` The file to read
+///
+/// * Returns
+/// Some(contents) if the file could be read and isn't empty, None otherwise
+#[cfg(target_os = "linux")]
+fn read_trimmed(path: &Path) -> Option<String> {
+    let contents: String = std::fs::read_to_string(path).ok()?;
+    let trimmed: &str = contents.trim();
+    (!trimmed.is_empty()).then(|| trimmed.to_string())
+}
+
+/// Reads a sysfs file holding a single number.
+///
+/// * Parameters
+/// `path` The file to read
+///
+/// * Returns
+/// Some(number) if the file could be read and parsed, None otherwise (sensors that
+/// are present but not connected often fail to read)
+#[cfg(target_os = "linux")]
+fn read_number(path: &Path) -> Option<f64> {
+    read_trimmed(path)?.parse::<f64>().ok()
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    /// A directory under the system temp directory, removed when dropped.
+    struct FakeTree {
+        root: PathBuf,
+    }
+
+    impl FakeTree {
+        fn new(name: &str) -> FakeTree {
+            let root: PathBuf =
+                std::env::temp_dir().join(format!("tm-sensors-{}-{}", name, std::process::id()));
+            let _ = std::fs::remove_dir_all(&root);
+            std::fs::create_dir_all(&root).unwrap();
+            FakeTree { root }
+        }
+
+        fn write(&self, path: &str, contents: &str) {
+            let path: PathBuf = self.root.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, contents).unwrap();
+        }
+    }
+
+    impl Drop for FakeTree {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.root);
+        }
+    }
+
+    type SensorFields = (String, String, String, &'static str, f32, Option<f32>);
+
+    fn sensor_fields(sensors: &[SensorRecord]) -> Vec<SensorFields> {
+        sensors
+            .iter()
+            .map(|sensor| {
+                (
+                    sensor.id.clone(),
+                    sensor.chip.clone(),
+                    sensor.label.clone(),
+                    sensor.kind.unit(),
+                    sensor.value,
+                    sensor.critical,
+                )
+            })
+            .collect()
+    }
+
+    fn write_coretemp(tree: &FakeTree, chip: &str, package_id: u32, core_temperatures: &[f32]) {
+        tree.write(&format!("{}/name", chip), "coretemp\n");
+        tree.write(
+            &format!("{}/temp1_label", chip),
+            &format!("Package id {}\n", package_id),
+        );
+        tree.write(&format!("{}/temp1_input", chip), "52000\n");
+        tree.write(&format!("{}/temp1_crit", chip), "100000\n");
+        for (core_id, temperature) in core_temperatures.iter().enumerate() {
+            let sensor: String = format!("{}/temp{}", chip, core_id + 2);
+            tree.write(&format!("{}_label", sensor), &format!("Core {}\n", core_id));
+            tree.write(
+                &format!("{}_input", sensor),
+                &format!("{}\n", temperature * 1000.0),
+            );
+            tree.write(&format!("{}_crit", sensor), "100000\n");
+        }
+    }
+
+    fn write_topology(tree: &FakeTree, cpu: u32, package_id: u32, core_id: u32) {
+        let topology: String = format!("cpu/cpu{}/topology", cpu);
+        tree.write(
+            &format!("{}/physical_package_id", topology),
+            &format!("{}\n", package_id),
+        );
+        tree.write(&format!("{}/core_id", topology), &format!("{}\n", core_id));
+    }
+
+    #[test]
+    fn reads_hwmon_chips_and_converts_units() {
+        let tree: FakeTree = FakeTree::new("hwmon");
+        write_coretemp(&tree, "hwmon/hwmon1", 0, &[41.0, 43.0]);
+        // a temp10 sorts after temp2 by number, not by name
+        tree.write("hwmon/hwmon1/temp10_input", "60000\n");
+        tree.write("hwmon/hwmon0/name", "nct6775\n");
+        tree.write("hwmon/hwmon0/fan1_input", "1200\n");
+        tree.write("hwmon/hwmon0/fan1_label", "CPU fan\n");
+        tree.write("hwmon/hwmon0/in0_input", "1050\n");
+        tree.write("hwmon/hwmon0/power1_input", "15500000\n");
+        // an unconnected fan that fails to read, and files that aren't inputs
+        tree.write("hwmon/hwmon0/fan2_input", "\n");
+        tree.write("hwmon/hwmon0/fan1_min", "300\n");
+        tree.write("hwmon/hwmon0/uevent", "");
+
+        let sensors: Vec<SensorRecord> = read_hwmon_sensors(&tree.root.join("hwmon"));
+
+        let expected: Vec<SensorFields> = vec![
+            (
+                "hwmon1/temp1",
+                "coretemp",
+                "Package id 0",
+                "°C",
+                52.0,
+                Some(100.0),
+            ),
+            (
+                "hwmon1/temp2",
+                "coretemp",
+                "Core 0",
+                "°C",
+                41.0,
+                Some(100.0),
+            ),
+            (
+                "hwmon1/temp3",
+                "coretemp",
+                "Core 1",
+                "°C",
+                43.0,
+                Some(100.0),
+            ),
+            ("hwmon1/temp10", "coretemp", "temp10", "°C", 60.0, None),
+            ("hwmon0/fan1", "nct6775", "CPU fan", "RPM", 1200.0, None),
+            ("hwmon0/in0", "nct6775", "in0", "V", 1.05, None),
+            ("hwmon0/power1", "nct6775", "power1", "W", 15.5, None),
+        ]
+        .into_iter()
+        .map(|(id, chip, label, unit, value, critical)| {
+            (
+                id.to_string(),
+                chip.to_string(),
+                label.to_string(),
+                unit,
+                value,
+                critical,
+            )
+        })
+        .collect();
+        assert_eq!(sensor_fields(&sensors), expected);
+    }
+
+    #[test]
+    fn missing_hwmon_root_has_no_sensors() {
+        let tree: FakeTree = FakeTree::new("no-hwmon");
+        assert!(read_hwmon_sensors(&tree.root.join("hwmon")).is_empty());
+    }
+
+    #[test]
+    fn reads_cpu_topology_of_online_cpus_in_logical_order() {
+        let tree: FakeTree = FakeTree::new("topology");
+        for cpu in 0..4 {
+            write_topology(&tree, cpu, 0, cpu % 2);
+        }
+        write_topology(&tree, 10, 1, 3);
+        // an offline cpu keeps its directory but has no topology
+        tree.write("cpu/cpu4/online", "0\n");
+        tree.write("cpu/cpufreq/boost", "1\n");
+        tree.write("cpu/cpuidle/current_driver", "intel_idle\n");
+
+        // sysinfo lists the online cpus only, so cpu10 is the fifth
+        let expected: Vec<Option<(u32, u32)>> = vec![
+            Some((0, 0)),
+            Some((0, 1)),
+            Some((0, 0)),
+            Some((0, 1)),
+            Some((1, 3)),
+        ];
+        assert_eq!(read_cpu_topology(&tree.root.join("cpu")), expected);
+
+        tree.write("cpu/online", "0-3,10\n");
+        assert_eq!(read_cpu_topology(&tree.root.join("cpu")), expected);
+    }
+
+    #[test]
+    fn an_online_cpu_without_topology_keeps_its_index() {
+        let tree: FakeTree = FakeTree::new("no-topology");
+        write_topology(&tree, 0, 0, 0);
+        write_topology(&tree, 2, 0, 2);
+        tree.write("cpu/online", "0-2\n");
+
+        assert_eq!(
+            read_cpu_topology(&tree.root.join("cpu")),
+            vec![Some((0, 0)), None, Some((0, 2))]
+        );
+    }
+
+    #[test]
+    fn smt_siblings_share_their_core_temperature() {
+        let tree: FakeTree = FakeTree::new("smt");
+        write_coretemp(&tree, "hwmon/hwmon2", 0, &[41.0, 43.0]);
+        // two threads per core, numbered like Linux does: siblings are half the cpus apart
+        for cpu in 0..4 {
+            write_topology(&tree, cpu, 0, cpu % 2);
+        }
+        write_topology(&tree, 4, 0, 7);
+
+        let sensors: Vec<SensorRecord> = read_hwmon_sensors(&tree.root.join("hwmon"));
+        let cpu_topology: Vec<Option<(u32, u32)>> = read_cpu_topology(&tree.root.join("cpu"));
+
+        assert_eq!(
+            match_core_temperatures(&sensors, &cpu_topology),
+            vec![Some(41.0), Some(43.0), Some(41.0), Some(43.0), None]
+        );
+    }
+
+    #[test]
+    fn core_temperatures_are_matched_per_package() {
+        let tree: FakeTree = FakeTree::new("packages");
+        write_coretemp(&tree, "hwmon/hwmon3", 1, &[70.0]);
+        write_coretemp(&tree, "hwmon/hwmon4", 0, &[40.0]);
+        write_topology(&tree, 0, 0, 0);
+        write_topology(&tree, 1, 1, 0);
+
+        let sensors: Vec<SensorRecord> = read_hwmon_sensors(&tree.root.join("hwmon"));
+        let cpu_topology: Vec<Option<(u32, u32)>> = read_cpu_topology(&tree.root.join("cpu"));
+
+        assert_eq!(
+            match_core_temperatures(&sensors, &cpu_topology),
+            vec![Some(40.0), Some(70.0)]
+        );
+    }
+
+    #[test]
+    fn chips_without_core_labels_give_no_core_temperatures() {
+        let tree: FakeTree = FakeTree::new("k10temp");
+        tree.write("hwmon/hwmon0/name", "k10temp\n");
+        tree.write("hwmon/hwmon0/temp1_label", "Tctl\n");
+        tree.write("hwmon/hwmon0/temp1_input", "65000\n");
+        write_topology(&tree, 0, 0, 0);
+
+        let sensors: Vec<SensorRecord> = read_hwmon_sensors(&tree.root.join("hwmon"));
+        let cpu_topology: Vec<Option<(u32, u32)>> = read_cpu_topology(&tree.root.join("cpu"));
+
+        assert_eq!(match_core_temperatures(&sensors, &cpu_topology), vec![None]);
+    }
+}
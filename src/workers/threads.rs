@@ -0,0 +1,203 @@
+//! Threads worker.
+//!
+//! Background worker listing the threads of the process selected in the GUI.
+
+use crate::{
+    data::process_key_struct::ProcessKey,
+    snapshots::{thread_record_struct::ThreadRecord, threads_snapshot_struct::ThreadsSnapshot},
+    workers::sampling::next_sampling_wait,
+};
+use std::collections::HashMap;
+use std::sync::mpsc::{Receiver, RecvTimeoutError, Sender};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+use sysinfo::System;
+
+/// Starts a background worker to collect the threads of one process.
+///
+/// Spawns a thread that periodically sends a ThreadsSnapshot of the watched process
+/// via the channel. The GUI chooses the process to watch, or None to watch nothing.
+///
+/// * Parameters
+/// `threads_sender` The channel sender for ThreadsSnapshot data
+/// `watch_receiver` The channel receiver for the process to watch
+pub fn send_threads_snapshot(
+    threads_sender: Sender<ThreadsSnapshot>,
+    watch_receiver: Receiver<Option<ProcessKey>>,
+) {
+    let _handle: JoinHandle<()> = thread::spawn(move || {
+        let mut sys: System = System::new();
+        let mut watched: Option<ProcessKey> = None;
+        // the cpu time of each thread at the previous sample, in clock ticks
+        let mut previous_ticks: HashMap<u32, u64> = HashMap::new();
+        let mut last_sample: Instant = Instant::now();
+
+        loop {
+            if let Some(process_key) = watched {
+                let interval: Duration = last_sample.elapsed();
+                last_sample = Instant::now();
+                let threads_snapshot: ThreadsSnapshot =
+                    match read_threads(&mut sys, process_key, &mut previous_ticks, interval) {
+                        Ok(threads) => ThreadsSnapshot {
+                            process_key,
+                            threads,
+                            error: None,
+                        },
+                        Err(e) => ThreadsSnapshot {
+                            process_key,
+                            threads: Vec::new(),
+                            error: Some(e),
+                        },
+                    };
+                if let Err(e) = threads_sender.send(threads_snapshot) {
+                    eprintln!("Error sending threads snapshot: {}", e);
+                    return;
+                }
+            }
+
+            // waits for the process to watch instead of sleeping so a newly selected
+            // process is listed straight away
+            let waiting_since: Instant = Instant::now();
+            while let Some(timeout) = next_sampling_wait(waiting_since) {
+                match watch_receiver.recv_timeout(timeout) {
+                    Ok(process_key) => {
+                        watched = process_key;
+                        previous_ticks.clear();
+                        break;
+                    }
+                    Err(RecvTimeoutError::Timeout) => {}
+                    Err(RecvTimeoutError::Disconnected) => return,
+                }
+            }
+        }
+    });
+}
+
+/// Lists the threads of a process from /proc/<pid>/task.
+///
+/// * Parameters
+/// `sys` The system used to check the process is still the watched one
+/// `process_key` The pid and start time of the process
+/// `previous_ticks` The cpu time of each thread at the previous sample, replaced
+/// by the cpu times read now
+/// `interval` The time since the previous sample
+///
+/// * Returns
+/// The threads, ordered by tid, or a message explaining why they couldn't be listed
+#[cfg(target_os = "linux")]
+fn read_threads(
+    sys: &mut System,
+    process_key: ProcessKey,
+    previous_ticks: &mut HashMap<u32, u64>,
+    interval: Duration,
+) -> Result<Vec<ThreadRecord>, String> {
+    use sysinfo::{Pid, ProcessRefreshKind, ProcessStatus};
+
+    // a different start time means the pid now belongs to another process
+    let pid: Pid = Pid::from_u32(process_key.pid);
+    let is_watched_process: bool = sys.refresh_process_specifics(pid, ProcessRefreshKind::new())
+        && sys.process(pid).map(|process| process.start_time()) == Some(process_key.start_time);
+    if !is_watched_process {
+        return Err(String::from("the process has exited"));
+    }
+
+    let entries =
+        std::fs::read_dir(format!("/proc/{}/task", process_key.pid)).map_err(|e| e.to_string())?;
+    let ticks_per_second: f64 = clock_ticks_per_second();
+    let seconds: f64 = interval.as_secs_f64().max(f64::EPSILON);
+
+    let mut threads: Vec<ThreadRecord> = Vec::new();
+    let mut ticks_by_tid: HashMap<u32, u64> = HashMap::new();
+    for entry in entries.flatten() {
+        let Some(tid) = entry
+            .file_name()
+            .to_str()
+            .and_then(|name| name.parse::<u32>().ok())
+        else {
+            continue;
+        };
+        // a thread can exit between listing the directory and reading its stat file
+        let Some(thread_stat) = std::fs::read_to_string(entry.path().join("stat"))
+            .ok()
+            .and_then(|stat| parse_thread_stat(&stat))
+        else {
+            continue;
+        };
+
+        let cpu_usage: Option<f32> = previous_ticks.get(&tid).map(|previous| {
+            let ticks: u64 = thread_stat.ticks.saturating_sub(*previous);
+            (ticks as f64 / ticks_per_second / seconds * 100.0) as f32
+        });
+        ticks_by_tid.insert(tid, thread_stat.ticks);
+        threads.push(ThreadRecord {
+            tid,
+            name: thread_stat.name,
+            status: ProcessStatus::from(thread_stat.state).to_string(),
+            cpu_usage,
+            last_core: thread_stat.last_core,
+        });
+    }
+
+    // threads that have exited are dropped along with their old cpu times
+    *previous_ticks = ticks_by_tid;
+    threads.sort_by_key(|thread| thread.tid);
+    Ok(threads)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_threads(
+    _sys: &mut System,
+    _process_key: ProcessKey,
+    _previous_ticks: &mut HashMap<u32, u64>,
+    _interval: Duration,
+) -> Result<Vec<ThreadRecord>, String> {
+    Err(String::from("threads can only be listed on Linux"))
+}
+
+/// The fields of /proc/<pid>/task/<tid>/stat the thread list shows.
+#[cfg(target_os = "linux")]
+struct ThreadStat {
+    name: String,
+    state: char,
+    ticks: u64,
+    last_core: Option<usize>,
+}
+
+/// Parses the stat file of a thread.
+///
+/// * Parameters
+/// `stat` The contents of /proc/<pid>/task/<tid>/stat
+///
+/// * Returns
+/// Some(ThreadStat) if the file is well formed, None otherwise
+#[cfg(target_os = "linux")]
+fn parse_thread_stat(stat: &str) -> Option<ThreadStat> {
+    // the name in brackets may hold spaces and brackets, so it ends at the last ')'
+    let (head, tail) = stat.rsplit_once(')')?;
+    let (_, name) = head.split_once('(')?;
+    // the fields after the name start at the 3rd, the state. The user and system
+    // times are the 14th and 15th, the last core the 39th
+    let fields: Vec<&str> = tail.split_whitespace().collect();
+    let user_ticks: u64 = fields.get(11)?.parse().ok()?;
+    let system_ticks: u64 = fields.get(12)?.parse().ok()?;
+
+    Some(ThreadStat {
+        name: name.to_string(),
+        state: fields.first()?.chars().next()?,
+        ticks: user_ticks + system_ticks,
+        last_core: fields.get(36).and_then(|field| field.parse().ok()),
+    })
+}
+
+/// Finds how many clock ticks the kernel counts per second of cpu time.
+///
+/// * Returns
+/// The clock tick rate, 100 if it can't be read
+#[cfg(target_os = "linux")]
+fn clock_ticks_per_second() -> f64 {
+    // SAFETY: sysconf only reads a system setting
+    match unsafe { libc::sysconf(libc::_SC_CLK_TCK) } {
+        ticks if ticks > 0 => ticks as f64,
+        _ => 100.0,
+    }
+}